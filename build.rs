@@ -1,32 +1,236 @@
+#[cfg(feature = "generate-bindings")]
 extern crate bindgen;
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+const HEADER_NAME: &str = "NIDAQmx.h";
+
+/// Default install locations of the NI-DAQmx C header and import library per platform.
+struct PlatformDefaults {
+    include_dirs: &'static [&'static str],
+    lib_dirs: &'static [&'static str],
+    lib_name: &'static str,
+}
+
+const WINDOWS: PlatformDefaults = PlatformDefaults {
+    include_dirs: &[
+        "C:/Program Files (x86)/National Instruments/Shared/ExternalCompilerSupport/C/include",
+        "C:/Program Files/National Instruments/Shared/ExternalCompilerSupport/C/include",
+    ],
+    lib_dirs: &[
+        "C:/Program Files (x86)/National Instruments/Shared/ExternalCompilerSupport/C/lib64/msvc/",
+        "C:/Program Files/National Instruments/Shared/ExternalCompilerSupport/C/lib64/msvc/",
+    ],
+    lib_name: "NIDAQmx",
+};
+
+/// NI-DAQmx for Linux installs `/usr/include/NIDAQmx.h` and `libnidaqmx.so`.
+const LINUX: PlatformDefaults = PlatformDefaults {
+    include_dirs: &[
+        "/usr/include",
+        "/usr/local/include",
+        "/usr/local/natinst/nidaqmx/include",
+    ],
+    lib_dirs: &[
+        "/usr/lib/x86_64-linux-gnu",
+        "/usr/lib64",
+        "/usr/lib",
+        "/usr/local/lib",
+        "/usr/local/natinst/nidaqmx/lib64",
+    ],
+    lib_name: "nidaqmx",
+};
 
 fn main() {
-    println!(
-        "cargo:rustc-link-search=native=C:/Program Files (x86)/National Instruments/Shared/ExternalCompilerSupport/C/lib64/msvc/",
-    );
-    println!(
-        "cargo:rustc-link-search=native=C:/Program Files/National Instruments/Shared/ExternalCompilerSupport/C/lib64/msvc/",
-    );
-    println!("cargo:rustc-link-lib=NIDAQmx");
     println!("cargo:rerun-if-changed=wrapper.h");
+    println!("cargo:rerun-if-env-changed=NIDAQMX_INCLUDE_DIR");
+    println!("cargo:rerun-if-env-changed=NIDAQMX_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=NIDAQMX_LIB_NAME");
+
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let platform = match target_os.as_str() {
+        "windows" => &WINDOWS,
+        _ => &LINUX,
+    };
+
+    println!("cargo:rerun-if-env-changed=CARGO_FEATURE_RUNTIME_LOADING");
+    if env::var_os("CARGO_FEATURE_RUNTIME_LOADING").is_none() {
+        link_library(platform);
+    }
+
+    if env::var_os("CARGO_FEATURE_PREBUILT_BINDINGS").is_some() {
+        // `src/bindings.rs` is included directly, no header required.
+        return;
+    }
+
+    let include_dir = find_include_dir(platform, &target_os);
+    generate_bindings(&include_dir);
+}
+
+/// Runs bindgen against `wrapper.h` and writes the result to `OUT_DIR/bindings.rs`.
+///
+/// With `DAQMX_REGENERATE_BINDINGS` set, the checked-in `src/bindings.rs` used by the
+/// `prebuilt-bindings` feature is regenerated too, keeping its header comment and only the items
+/// it already declares. The pinned version in the header is updated to the installed header's.
+#[cfg(feature = "generate-bindings")]
+fn generate_bindings(include_dir: &Path) {
+    println!("cargo:rerun-if-env-changed=DAQMX_REGENERATE_BINDINGS");
+
+    let bindings = bindings_builder(include_dir)
+        .generate()
+        .expect("Unable to generate bindings");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    bindings
+        .write_to_file(out_path.join("bindings.rs"))
+        .expect("Couldn't write bindings!");
+
+    if env::var_os("DAQMX_REGENERATE_BINDINGS").is_some() {
+        regenerate_prebuilt(include_dir);
+    }
+}
 
-    let bindings = bindgen::Builder::default()
+#[cfg(feature = "generate-bindings")]
+fn bindings_builder(include_dir: &Path) -> bindgen::Builder {
+    bindgen::Builder::default()
         .header("wrapper.h")
         .clang_arg("-x")
         .clang_arg("c++")
         .clang_arg("-std=c++17")
-        .clang_arg("-IC:/Program Files (x86)/National Instruments/Shared/ExternalCompilerSupport/C/include")
-        .clang_arg("-IC:/Program Files/National Instruments/Shared/ExternalCompilerSupport/C/include")
+        .clang_arg(format!("-I{}", include_dir.display()))
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         .default_macro_constant_type(bindgen::MacroTypeVariation::Signed)
+}
+
+/// Regenerates `src/bindings.rs` from the installed header, limited to the items it declares.
+#[cfg(feature = "generate-bindings")]
+fn regenerate_prebuilt(include_dir: &Path) {
+    let prebuilt = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("src")
+        .join("bindings.rs");
+    let current = std::fs::read_to_string(&prebuilt).expect("Couldn't read prebuilt bindings!");
+
+    let version = header_version(include_dir);
+    if version.is_none() {
+        println!("cargo:warning=Couldn't find the NI-DAQmx version in {HEADER_NAME}");
+    }
+
+    let mut builder = bindings_builder(include_dir);
+    for line in current.lines().take_while(|line| line.starts_with("//")) {
+        builder = match &version {
+            Some(version) if line.starts_with(PINNED_VERSION) => {
+                builder.raw_line(format!("{PINNED_VERSION}{version}."))
+            }
+            _ => builder.raw_line(line),
+        };
+    }
+    builder = builder.raw_line("");
+    for item in declared_items(&current) {
+        builder = builder.allowlist_item(format!("^{item}$"));
+    }
+    builder
         .generate()
-        .expect("Unable to generate bindings");
+        .expect("Unable to generate prebuilt bindings")
+        .write_to_file(&prebuilt)
+        .expect("Couldn't write prebuilt bindings!");
+    println!(
+        "cargo:warning=Regenerated prebuilt bindings at {}",
+        prebuilt.display()
+    );
+}
 
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+/// The header line of `src/bindings.rs` naming the NI-DAQmx version it is pinned to.
+#[cfg(feature = "generate-bindings")]
+const PINNED_VERSION: &str = "// Pinned to NIDAQmx.h from NI-DAQmx ";
+
+/// The NI-DAQmx version named in the banner of the installed header, e.g. `23.8` from
+/// "Include file for NI-DAQmx 23.8 library support."
+#[cfg(feature = "generate-bindings")]
+fn header_version(include_dir: &Path) -> Option<String> {
+    let header = std::fs::read(include_dir.join(HEADER_NAME)).ok()?;
+    let header = String::from_utf8_lossy(&header);
+    let (_, rest) = header.split_once("Include file for NI-DAQmx ")?;
+    rest.split_whitespace().next().map(str::to_string)
+}
+
+/// The names of the functions, constants and types declared in `bindings`.
+#[cfg(feature = "generate-bindings")]
+fn declared_items(bindings: &str) -> Vec<&str> {
+    const KINDS: [&str; 5] = [
+        "pub fn ",
+        "pub const ",
+        "pub type ",
+        "pub struct ",
+        "pub union ",
+    ];
     bindings
-        .write_to_file(out_path.join("bindings.rs"))
-        .expect("Couldn't write bindings!");
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let rest = KINDS.iter().find_map(|kind| line.strip_prefix(kind))?;
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            Some(&rest[..end])
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "generate-bindings"))]
+fn generate_bindings(_include_dir: &Path) {
+    panic!(
+        "\n\nNo bindings source selected. Enable either the `generate-bindings` feature \
+         (runs bindgen against NIDAQmx.h) or the `prebuilt-bindings` feature.\n"
+    );
+}
+
+/// Emits the link search paths and library name.
+///
+/// `NIDAQMX_LIB_DIR` is searched first, followed by the platform defaults.
+/// `NIDAQMX_LIB_NAME` overrides the library name (e.g. for a renamed import library).
+fn link_library(platform: &PlatformDefaults) {
+    if let Some(dir) = env::var_os("NIDAQMX_LIB_DIR") {
+        println!(
+            "cargo:rustc-link-search=native={}",
+            PathBuf::from(dir).display()
+        );
+    }
+    for dir in platform.lib_dirs {
+        if Path::new(dir).is_dir() {
+            println!("cargo:rustc-link-search=native={dir}");
+        }
+    }
+
+    let lib_name = env::var("NIDAQMX_LIB_NAME").unwrap_or_else(|_| platform.lib_name.to_string());
+    println!("cargo:rustc-link-lib={lib_name}");
+}
+
+/// Locates the directory containing `NIDAQmx.h`.
+///
+/// `NIDAQMX_INCLUDE_DIR` takes precedence over the platform defaults.
+/// Panics with the list of searched directories if the header cannot be found.
+fn find_include_dir(platform: &PlatformDefaults, target_os: &str) -> PathBuf {
+    let mut searched: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env::var_os("NIDAQMX_INCLUDE_DIR") {
+        searched.push(PathBuf::from(dir));
+    }
+    searched.extend(platform.include_dirs.iter().map(PathBuf::from));
+
+    if let Some(dir) = searched.iter().find(|dir| dir.join(HEADER_NAME).is_file()) {
+        return dir.clone();
+    }
+
+    let listing: Vec<String> = searched
+        .iter()
+        .map(|dir| format!("  - {}", dir.display()))
+        .collect();
+    panic!(
+        "\n\nCould not find {HEADER_NAME} for target OS '{target_os}'.\n\
+         Searched:\n{}\n\n\
+         Install the NI-DAQmx driver (including the C/ANSI C development support), \
+         or set NIDAQMX_INCLUDE_DIR to the directory containing {HEADER_NAME} \
+         and NIDAQMX_LIB_DIR to the directory containing the NI-DAQmx library.\n",
+        listing.join("\n")
+    );
 }
@@ -0,0 +1,879 @@
+//! Backend forwarding to the NI-DAQmx C library.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{Backend, ChannelProperty, EventHandler, PropertyValue, TaskProperty};
+use crate::daqmx::{self, bool32, TaskHandle};
+use crate::types::buffer_to_string;
+use crate::utils::info::ChannelType;
+
+/// The NI-DAQmx driver, either linked at build time or loaded at runtime
+/// depending on the `runtime-loading` feature.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FfiBackend;
+
+/// Size of the buffer used for error descriptions.
+const ERROR_BUFFER_SIZE: usize = 2048;
+
+/// Reads a string from a DAQmx getter by first querying the required size.
+///
+/// Returns the status of the second call, so a change in length between the two calls
+/// surfaces as [`daqmx::DAQmxErrorBufferTooSmallForString`] or a truncation warning.
+fn read_string(out: &mut String, mut getter: impl FnMut(*mut c_char, u32) -> i32) -> i32 {
+    let size = getter(ptr::null_mut(), 0);
+    if size <= 0 {
+        return size;
+    }
+
+    let mut buffer = vec![0i8; size as usize];
+    let return_code = getter(buffer.as_mut_ptr() as *mut c_char, size as u32);
+    if return_code >= 0 {
+        *out = buffer_to_string(buffer);
+    }
+    return_code
+}
+
+/// Seconds from the LabWindows/CVI epoch, 1904-01-01 UTC, to the Unix epoch.
+const CVI_EPOCH_OFFSET: u64 = 2_082_844_800;
+
+/// Converts a CVI absolute time, whole seconds since 1904 in `msb` and the fraction of a second
+/// in units of 2^-64 in `lsb`, to a [`SystemTime`].
+fn system_time(time: daqmx::CVITime) -> SystemTime {
+    let epoch = UNIX_EPOCH - Duration::from_secs(CVI_EPOCH_OFFSET);
+    let fraction = Duration::from_nanos(((time.lsb as u128 * 1_000_000_000) >> 64) as u64);
+    epoch + Duration::from_secs(time.msb.max(0) as u64) + fraction
+}
+
+/// Forwards an event to the [`EventHandler`] passed as `data`.
+unsafe fn dispatch_event(data: *mut c_void, value: i32, samples: u32) -> i32 {
+    // Safety: `data` is the handler registered with the callback, which the task keeps alive.
+    // Hold a reference while it runs, as the callback may drop the last handle to the task.
+    let handler = data as *const EventHandler;
+    Arc::increment_strong_count(handler);
+    Arc::from_raw(handler).call(value, samples);
+    0
+}
+
+unsafe extern "C" fn every_n_samples_callback(
+    _task: TaskHandle,
+    event_type: i32,
+    samples: u32,
+    data: *mut c_void,
+) -> i32 {
+    dispatch_event(data, event_type, samples)
+}
+
+unsafe extern "C" fn done_callback(_task: TaskHandle, status: i32, data: *mut c_void) -> i32 {
+    dispatch_event(data, status, 0)
+}
+
+unsafe extern "C" fn signal_callback(_task: TaskHandle, signal: i32, data: *mut c_void) -> i32 {
+    dispatch_event(data, signal, 0)
+}
+
+/// The data pointer to register for `handler`, which is null when unregistering.
+fn handler_data(handler: Option<&Arc<EventHandler>>) -> *mut c_void {
+    handler.map_or(ptr::null(), Arc::as_ptr) as *mut c_void
+}
+
+fn buffer_len(len: usize) -> u32 {
+    // Just saturate the buffer size at u32 boundary.
+    // If it is larger, this will still be memory safe.
+    len.try_into().unwrap_or(u32::MAX)
+}
+
+impl Backend for FfiBackend {
+    fn extended_error_info(&self) -> String {
+        let mut buffer = vec![0i8; ERROR_BUFFER_SIZE];
+        unsafe {
+            daqmx::DAQmxGetExtendedErrorInfo(
+                buffer.as_mut_ptr() as *mut c_char,
+                ERROR_BUFFER_SIZE as u32,
+            );
+        }
+        buffer_to_string(buffer)
+    }
+
+    fn error_string(&self, code: i32) -> String {
+        let mut buffer = vec![0i8; ERROR_BUFFER_SIZE];
+        unsafe {
+            daqmx::DAQmxGetErrorString(
+                code,
+                buffer.as_mut_ptr() as *mut c_char,
+                ERROR_BUFFER_SIZE as u32,
+            );
+        }
+        buffer_to_string(buffer)
+    }
+
+    fn device_names(&self, names: &mut String) -> i32 {
+        read_string(names, |data, size| unsafe {
+            daqmx::DAQmxGetSysDevNames(data, size)
+        })
+    }
+
+    fn device_physical_channels(
+        &self,
+        device: &CStr,
+        channel_type: ChannelType,
+        channels: &mut String,
+    ) -> i32 {
+        let getter = match channel_type {
+            ChannelType::AI => daqmx::DAQmxGetDevAIPhysicalChans,
+            ChannelType::AO => daqmx::DAQmxGetDevAOPhysicalChans,
+            ChannelType::DI => daqmx::DAQmxGetDevDILines,
+            ChannelType::DO => daqmx::DAQmxGetDevDOLines,
+            ChannelType::CI => daqmx::DAQmxGetDevCIPhysicalChans,
+            ChannelType::CO => daqmx::DAQmxGetDevCOPhysicalChans,
+        };
+        read_string(channels, |data, size| unsafe {
+            getter(device.as_ptr(), data, size)
+        })
+    }
+
+    fn reset_device(&self, device: &CStr) -> i32 {
+        unsafe { daqmx::DAQmxResetDevice(device.as_ptr()) }
+    }
+
+    fn create_lin_scale(
+        &self,
+        name: &CStr,
+        slope: f64,
+        y_intercept: f64,
+        pre_scaled_units: i32,
+        scaled_units: &CStr,
+    ) -> i32 {
+        unsafe {
+            daqmx::DAQmxCreateLinScale(
+                name.as_ptr(),
+                slope,
+                y_intercept,
+                pre_scaled_units,
+                scaled_units.as_ptr(),
+            )
+        }
+    }
+
+    fn scale_scaled_units(&self, name: &CStr, units: &mut String) -> i32 {
+        read_string(units, |data, size| unsafe {
+            daqmx::DAQmxGetScaleScaledUnits(name.as_ptr(), data, size)
+        })
+    }
+
+    fn create_task(&self, name: &CStr, task: &mut TaskHandle) -> i32 {
+        unsafe { daqmx::DAQmxCreateTask(name.as_ptr(), task) }
+    }
+
+    unsafe fn clear_task(&self, task: TaskHandle) -> i32 {
+        daqmx::DAQmxClearTask(task)
+    }
+
+    unsafe fn task_name(&self, task: TaskHandle, name: &mut String) -> i32 {
+        read_string(name, |data, size| daqmx::DAQmxGetTaskName(task, data, size))
+    }
+
+    unsafe fn task_channels(&self, task: TaskHandle, channels: &mut String) -> i32 {
+        read_string(channels, |data, size| {
+            daqmx::DAQmxGetTaskChannels(task, data, size)
+        })
+    }
+
+    unsafe fn start_task(&self, task: TaskHandle) -> i32 {
+        daqmx::DAQmxStartTask(task)
+    }
+
+    unsafe fn stop_task(&self, task: TaskHandle) -> i32 {
+        daqmx::DAQmxStopTask(task)
+    }
+
+    unsafe fn wait_until_task_done(&self, task: TaskHandle, timeout: f64) -> i32 {
+        daqmx::DAQmxWaitUntilTaskDone(task, timeout)
+    }
+
+    unsafe fn is_task_done(&self, task: TaskHandle, done: &mut bool) -> i32 {
+        let mut value: bool32 = 0;
+        let return_code = daqmx::DAQmxIsTaskDone(task, &mut value);
+        *done = value != 0;
+        return_code
+    }
+
+    unsafe fn task_control(&self, task: TaskHandle, action: i32) -> i32 {
+        daqmx::DAQmxTaskControl(task, action)
+    }
+
+    unsafe fn register_every_n_samples_event(
+        &self,
+        task: TaskHandle,
+        event_type: i32,
+        samples: u32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        let callback: daqmx::DAQmxEveryNSamplesEventCallbackPtr =
+            handler.and(Some(every_n_samples_callback));
+        daqmx::DAQmxRegisterEveryNSamplesEvent(
+            task,
+            event_type,
+            samples,
+            0,
+            callback,
+            handler_data(handler),
+        )
+    }
+
+    unsafe fn register_done_event(
+        &self,
+        task: TaskHandle,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        let callback: daqmx::DAQmxDoneEventCallbackPtr = handler.and(Some(done_callback));
+        daqmx::DAQmxRegisterDoneEvent(task, 0, callback, handler_data(handler))
+    }
+
+    unsafe fn register_signal_event(
+        &self,
+        task: TaskHandle,
+        signal: i32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        let callback: daqmx::DAQmxSignalEventCallbackPtr = handler.and(Some(signal_callback));
+        daqmx::DAQmxRegisterSignalEvent(task, signal, 0, callback, handler_data(handler))
+    }
+
+    unsafe fn get_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        match property {
+            TaskProperty::ReadAutoStart => {
+                let mut data: bool32 = 0;
+                let return_code = daqmx::DAQmxGetReadAutoStart(task, &mut data);
+                *value = PropertyValue::Bool(data != 0);
+                return_code
+            }
+            TaskProperty::ReadAvailableSamplesPerChannel => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadAvailSampPerChan(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::ReadDigitalLinesBytesPerChannel => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadDigitalLinesBytesPerChan(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::WriteDigitalLinesBytesPerChannel => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetWriteDigitalLinesBytesPerChan(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::NumChannels => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetTaskNumChans(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::SampleClockRate => {
+                let mut data = 0.0;
+                let return_code = daqmx::DAQmxGetSampClkRate(task, &mut data);
+                *value = PropertyValue::F64(data);
+                return_code
+            }
+            TaskProperty::SampleMode => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetSampQuantSampMode(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::ReadCurrentReadPosition => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadCurrReadPos(task, &mut data);
+                *value = PropertyValue::U64(data);
+                return_code
+            }
+            TaskProperty::ReadTotalSamplesPerChannelAcquired => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadTotalSampPerChanAcquired(task, &mut data);
+                *value = PropertyValue::U64(data);
+                return_code
+            }
+            TaskProperty::WriteSpaceAvailable => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetWriteSpaceAvail(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::InputBufferSize => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetBufInputBufSize(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::ReadOverwrite => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadOverWrite(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::ReadRelativeTo => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadRelativeTo(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::ReadOffset => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetReadOffset(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::OutputBufferSize => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetBufOutputBufSize(task, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+            TaskProperty::WriteRegenMode => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetWriteRegenMode(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::WriteRelativeTo => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetWriteRelativeTo(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::WriteOffset => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetWriteOffset(task, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            TaskProperty::FirstSampleTimestampEnable => {
+                let mut data: bool32 = 0;
+                let return_code = daqmx::DAQmxGetFirstSampTimestampEnable(task, &mut data);
+                *value = PropertyValue::Bool(data != 0);
+                return_code
+            }
+            TaskProperty::FirstSampleTimestamp => {
+                let mut data = daqmx::CVIAbsTime { u32Data: [0; 4] };
+                let return_code = daqmx::DAQmxGetFirstSampTimestampVal(task, &mut data);
+                *value = PropertyValue::Timestamp(system_time(data.cviTime));
+                return_code
+            }
+        }
+    }
+
+    unsafe fn set_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        match (property, value) {
+            (TaskProperty::ReadAutoStart, PropertyValue::Bool(data)) => {
+                daqmx::DAQmxSetReadAutoStart(task, bool32::from(*data))
+            }
+            (TaskProperty::FirstSampleTimestampEnable, PropertyValue::Bool(data)) => {
+                daqmx::DAQmxSetFirstSampTimestampEnable(task, bool32::from(*data))
+            }
+            (TaskProperty::ReadOverwrite, PropertyValue::I32(data)) => {
+                daqmx::DAQmxSetReadOverWrite(task, *data)
+            }
+            (TaskProperty::ReadRelativeTo, PropertyValue::I32(data)) => {
+                daqmx::DAQmxSetReadRelativeTo(task, *data)
+            }
+            (TaskProperty::ReadOffset, PropertyValue::I32(data)) => {
+                daqmx::DAQmxSetReadOffset(task, *data)
+            }
+            (TaskProperty::WriteRegenMode, PropertyValue::I32(data)) => {
+                daqmx::DAQmxSetWriteRegenMode(task, *data)
+            }
+            (TaskProperty::WriteRelativeTo, PropertyValue::I32(data)) => {
+                daqmx::DAQmxSetWriteRelativeTo(task, *data)
+            }
+            (TaskProperty::WriteOffset, PropertyValue::I32(data)) => {
+                daqmx::DAQmxSetWriteOffset(task, *data)
+            }
+            _ => daqmx::DAQmxErrorInvalidAttributeValue,
+        }
+    }
+
+    unsafe fn get_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        let channel = channel.as_ptr();
+        match property {
+            ChannelProperty::PhysicalChannelName
+            | ChannelProperty::AICustomScaleName
+            | ChannelProperty::COPulseTerminal => {
+                let getter = match property {
+                    ChannelProperty::PhysicalChannelName => daqmx::DAQmxGetPhysicalChanName,
+                    ChannelProperty::AICustomScaleName => daqmx::DAQmxGetAICustomScaleName,
+                    _ => daqmx::DAQmxGetCOPulseTerm,
+                };
+                let mut data = String::new();
+                let return_code = read_string(&mut data, |buffer, size| {
+                    getter(task, channel, buffer, size)
+                });
+                *value = PropertyValue::String(data);
+                return_code
+            }
+            ChannelProperty::AIMax | ChannelProperty::AIMin => {
+                let getter = match property {
+                    ChannelProperty::AIMax => daqmx::DAQmxGetAIMax,
+                    _ => daqmx::DAQmxGetAIMin,
+                };
+                let mut data = 0.0;
+                let return_code = getter(task, channel, &mut data);
+                *value = PropertyValue::F64(data);
+                return_code
+            }
+            ChannelProperty::AITerminalConfig | ChannelProperty::AIVoltageUnits => {
+                let getter = match property {
+                    ChannelProperty::AITerminalConfig => daqmx::DAQmxGetAITermCfg,
+                    _ => daqmx::DAQmxGetAIVoltageUnits,
+                };
+                let mut data = 0;
+                let return_code = getter(task, channel, &mut data);
+                *value = PropertyValue::I32(data);
+                return_code
+            }
+            ChannelProperty::DONumLines => {
+                let mut data = 0;
+                let return_code = daqmx::DAQmxGetDONumLines(task, channel, &mut data);
+                *value = PropertyValue::U32(data);
+                return_code
+            }
+        }
+    }
+
+    unsafe fn set_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        match (property, value) {
+            (ChannelProperty::COPulseTerminal, PropertyValue::String(terminal)) => {
+                let Ok(terminal) = std::ffi::CString::new(terminal.as_str()) else {
+                    return daqmx::DAQmxErrorInvalidAttributeValue;
+                };
+                daqmx::DAQmxSetCOPulseTerm(task, channel.as_ptr(), terminal.as_ptr())
+            }
+            _ => daqmx::DAQmxErrorInvalidAttributeValue,
+        }
+    }
+
+    unsafe fn create_ai_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        terminal_config: i32,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        daqmx::DAQmxCreateAIVoltageChan(
+            task,
+            physical_channel.as_ptr(),
+            name.as_ptr(),
+            terminal_config,
+            min,
+            max,
+            units,
+            custom_scale_name.as_ptr(),
+        )
+    }
+
+    unsafe fn create_ao_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        daqmx::DAQmxCreateAOVoltageChan(
+            task,
+            physical_channel.as_ptr(),
+            name.as_ptr(),
+            min,
+            max,
+            units,
+            custom_scale_name.as_ptr(),
+        )
+    }
+
+    unsafe fn create_di_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        daqmx::DAQmxCreateDIChan(task, lines.as_ptr(), name.as_ptr(), line_grouping)
+    }
+
+    unsafe fn create_do_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        daqmx::DAQmxCreateDOChan(task, lines.as_ptr(), name.as_ptr(), line_grouping)
+    }
+
+    unsafe fn create_ci_count_edges_chan(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        edge: i32,
+        initial_count: u32,
+        count_direction: i32,
+    ) -> i32 {
+        daqmx::DAQmxCreateCICountEdgesChan(
+            task,
+            counter.as_ptr(),
+            name.as_ptr(),
+            edge,
+            initial_count,
+            count_direction,
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_time(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        low_time: f64,
+        high_time: f64,
+    ) -> i32 {
+        daqmx::DAQmxCreateCOPulseChanTime(
+            task,
+            counter.as_ptr(),
+            name.as_ptr(),
+            units,
+            idle_state,
+            initial_delay,
+            low_time,
+            high_time,
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_freq(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        frequency: f64,
+        duty_cycle: f64,
+    ) -> i32 {
+        daqmx::DAQmxCreateCOPulseChanFreq(
+            task,
+            counter.as_ptr(),
+            name.as_ptr(),
+            units,
+            idle_state,
+            initial_delay,
+            frequency,
+            duty_cycle,
+        )
+    }
+
+    unsafe fn cfg_samp_clk_timing(
+        &self,
+        task: TaskHandle,
+        source: Option<&CStr>,
+        rate: f64,
+        active_edge: i32,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        daqmx::DAQmxCfgSampClkTiming(
+            task,
+            source.map_or(ptr::null(), CStr::as_ptr),
+            rate,
+            active_edge,
+            sample_mode,
+            samples_per_channel,
+        )
+    }
+
+    unsafe fn cfg_implicit_timing(
+        &self,
+        task: TaskHandle,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        daqmx::DAQmxCfgImplicitTiming(task, sample_mode, samples_per_channel)
+    }
+
+    unsafe fn cfg_input_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32 {
+        daqmx::DAQmxCfgInputBuffer(task, samples_per_channel)
+    }
+
+    unsafe fn cfg_output_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32 {
+        daqmx::DAQmxCfgOutputBuffer(task, samples_per_channel)
+    }
+
+    unsafe fn cfg_dig_edge_start_trig(&self, task: TaskHandle, source: &CStr, edge: i32) -> i32 {
+        daqmx::DAQmxCfgDigEdgeStartTrig(task, source.as_ptr(), edge)
+    }
+
+    unsafe fn cfg_dig_edge_ref_trig(
+        &self,
+        task: TaskHandle,
+        source: &CStr,
+        edge: i32,
+        pretrigger_samples: u32,
+    ) -> i32 {
+        daqmx::DAQmxCfgDigEdgeRefTrig(task, source.as_ptr(), edge, pretrigger_samples)
+    }
+
+    unsafe fn disable_start_trig(&self, task: TaskHandle) -> i32 {
+        daqmx::DAQmxDisableStartTrig(task)
+    }
+
+    unsafe fn export_signal(&self, task: TaskHandle, signal: i32, terminal: &CStr) -> i32 {
+        daqmx::DAQmxExportSignal(task, signal, terminal.as_ptr())
+    }
+
+    unsafe fn read_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        daqmx::DAQmxReadAnalogF64(
+            task,
+            samples_per_channel,
+            timeout,
+            fill_mode,
+            buffer.as_mut_ptr(),
+            buffer_len(buffer.len()),
+            samples_read,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn read_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        daqmx::DAQmxReadAnalogScalarF64(task, timeout, value, ptr::null_mut())
+    }
+
+    unsafe fn read_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [u8],
+        samples_read: &mut i32,
+        bytes_per_sample: &mut i32,
+    ) -> i32 {
+        daqmx::DAQmxReadDigitalLines(
+            task,
+            samples_per_channel,
+            timeout,
+            fill_mode,
+            buffer.as_mut_ptr(),
+            buffer_len(buffer.len()),
+            samples_read,
+            bytes_per_sample,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn read_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        daqmx::DAQmxReadDigitalScalarU32(task, timeout, value, ptr::null_mut())
+    }
+
+    unsafe fn read_counter_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        daqmx::DAQmxReadCounterScalarU32(task, timeout, value, ptr::null_mut())
+    }
+
+    unsafe fn read_counter_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        daqmx::DAQmxReadCounterScalarF64(task, timeout, value, ptr::null_mut())
+    }
+
+    unsafe fn read_counter_u32(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [u32],
+        samples_read: &mut i32,
+    ) -> i32 {
+        daqmx::DAQmxReadCounterU32(
+            task,
+            samples_per_channel,
+            timeout,
+            buffer.as_mut_ptr(),
+            buffer_len(buffer.len()),
+            samples_read,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn read_counter_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        daqmx::DAQmxReadCounterF64(
+            task,
+            samples_per_channel,
+            timeout,
+            buffer.as_mut_ptr(),
+            buffer_len(buffer.len()),
+            samples_read,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn write_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[f64],
+        samples_written: &mut i32,
+    ) -> i32 {
+        daqmx::DAQmxWriteAnalogF64(
+            task,
+            samples_per_channel,
+            bool32::from(auto_start),
+            timeout,
+            fill_mode,
+            buffer.as_ptr(),
+            samples_written,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn write_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: f64,
+    ) -> i32 {
+        daqmx::DAQmxWriteAnalogScalarF64(
+            task,
+            bool32::from(auto_start),
+            timeout,
+            value,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn write_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[u8],
+        samples_written: &mut i32,
+    ) -> i32 {
+        daqmx::DAQmxWriteDigitalLines(
+            task,
+            samples_per_channel,
+            bool32::from(auto_start),
+            timeout,
+            fill_mode,
+            buffer.as_ptr(),
+            samples_written,
+            ptr::null_mut(),
+        )
+    }
+
+    unsafe fn write_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: u32,
+    ) -> i32 {
+        daqmx::DAQmxWriteDigitalScalarU32(
+            task,
+            bool32::from(auto_start),
+            timeout,
+            value,
+            ptr::null_mut(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cvi_time_to_system_time() {
+        let time = daqmx::CVITime {
+            lsb: 1 << 63,
+            msb: CVI_EPOCH_OFFSET as i64 + 10,
+        };
+        assert_eq!(
+            system_time(time),
+            UNIX_EPOCH + Duration::from_millis(10_500)
+        );
+    }
+}
@@ -0,0 +1,617 @@
+//! Driver backends.
+//!
+//! Every call the crate makes into NI-DAQmx goes through the [`Backend`] trait.
+//! [`FfiBackend`] forwards to the NI-DAQmx C library while [`SimulatedBackend`] models devices,
+//! tasks, sample clocks and triggers in memory so application logic can be tested without hardware.
+//!
+//! The methods mirror the C API: they return the DAQmx status code (negative for errors,
+//! positive for warnings) and write their results to the output arguments.
+//! [`crate::daqmx_call`] turns the status into a [`Result`](crate::error::Result).
+//!
+//! # Selecting a Backend
+//!
+//! [`crate::tasks::Task::new`] uses the [`default_backend`], which is the [`FfiBackend`] unless
+//! [`set_default_backend`] has been called or the `DAQMX_BACKEND` environment variable is set to
+//! `simulated`. In that case the simulated devices are named by `DAQMX_SIMULATED_DEVICES`
+//! (comma separated, default `SimDev1`). The crate's unit tests default to a simulated
+//! `PCIe-6363_test` instead of the [`FfiBackend`].
+//!
+//! With the `recording` feature, `DAQMX_BACKEND=replay` replays the calls recorded in
+//! `DAQMX_REPLAY_FILE` and `DAQMX_RECORD_FILE` records the calls of the default backend to a file.
+//! See [`recording`].
+//! A specific backend can be used for a single task with [`crate::tasks::Task::with_backend`].
+
+mod ffi;
+#[cfg(feature = "recording")]
+pub mod recording;
+pub mod simulated;
+
+pub use ffi::FfiBackend;
+#[cfg(feature = "recording")]
+pub use recording::{RecordingBackend, ReplayBackend};
+pub use simulated::{SimulatedBackend, SimulatedDevice};
+
+use log::error;
+use std::ffi::CStr;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use crate::daqmx::{bool32, TaskHandle};
+use crate::error::DaqmxError;
+use crate::utils::info::ChannelType;
+
+/// Channel properties readable (and in some cases writable) through a [`Backend`].
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelProperty {
+    /// [`PropertyValue::String`]. The physical channel the virtual channel is created on.
+    PhysicalChannelName,
+    /// [`PropertyValue::F64`]. The maximum value of the analog input range.
+    AIMax,
+    /// [`PropertyValue::F64`]. The minimum value of the analog input range.
+    AIMin,
+    /// [`PropertyValue::I32`]. The input terminal configuration.
+    AITerminalConfig,
+    /// [`PropertyValue::String`]. The custom scale applied to the channel.
+    AICustomScaleName,
+    /// [`PropertyValue::I32`]. The units of a voltage channel.
+    AIVoltageUnits,
+    /// [`PropertyValue::String`]. The terminal the counter output pulses are generated on.
+    COPulseTerminal,
+    /// [`PropertyValue::U32`]. The number of lines in a digital output channel.
+    DONumLines,
+}
+
+/// Task properties readable and writable through a [`Backend`].
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskProperty {
+    /// [`PropertyValue::Bool`]. Whether a read automatically starts the task.
+    ReadAutoStart,
+    /// [`PropertyValue::U32`], read only. Samples per channel acquired but not yet read.
+    ReadAvailableSamplesPerChannel,
+    /// [`PropertyValue::U32`], read only. Elements per channel of each sample of a digital lines
+    /// read, the largest number of lines in a channel.
+    ReadDigitalLinesBytesPerChannel,
+    /// [`PropertyValue::U32`], read only. Elements per channel of each sample of a digital lines
+    /// write, the largest number of lines in a channel.
+    WriteDigitalLinesBytesPerChannel,
+    /// [`PropertyValue::U32`], read only. The number of virtual channels in the task.
+    NumChannels,
+    /// [`PropertyValue::F64`], read only. The rate of the sample clock in samples per second,
+    /// which the device may have coerced from the requested rate.
+    SampleClockRate,
+    /// [`PropertyValue::I32`], read only. Whether the task acquires or generates a finite
+    /// number of samples or runs continuously, as a `DAQmx_Val_*` sample mode.
+    SampleMode,
+    /// [`PropertyValue::U64`], read only. The position in the acquisition of the next sample
+    /// to be read.
+    ReadCurrentReadPosition,
+    /// [`PropertyValue::U64`], read only. Samples per channel acquired since the task started.
+    ReadTotalSamplesPerChannelAcquired,
+    /// [`PropertyValue::U32`], read only. Samples per channel which fit in the output buffer
+    /// without overwriting samples not yet generated.
+    WriteSpaceAvailable,
+    /// [`PropertyValue::U32`], read only. Samples per channel the input buffer holds. Set it
+    /// with [`Backend::cfg_input_buffer`].
+    InputBufferSize,
+    /// [`PropertyValue::I32`]. Whether a continuous acquisition overwrites samples which
+    /// haven't been read, `DAQmx_Val_OverwriteUnreadSamps` or
+    /// `DAQmx_Val_DoNotOverwriteUnreadSamps`.
+    ReadOverwrite,
+    /// [`PropertyValue::I32`]. The point reads start from, offset by
+    /// [`TaskProperty::ReadOffset`]. One of the `DAQmx_Val_FirstSample`, `DAQmx_Val_CurrReadPos`,
+    /// `DAQmx_Val_RefTrig`, `DAQmx_Val_FirstPretrigSamp` and `DAQmx_Val_MostRecentSamp` values.
+    ReadRelativeTo,
+    /// [`PropertyValue::I32`]. Samples per channel from [`TaskProperty::ReadRelativeTo`] to the
+    /// first sample read.
+    ReadOffset,
+    /// [`PropertyValue::U32`], read only. Samples per channel the output buffer holds. Set it
+    /// with [`Backend::cfg_output_buffer`].
+    OutputBufferSize,
+    /// [`PropertyValue::I32`]. Whether an output generates the samples in its buffer again once
+    /// they have all been generated, `DAQmx_Val_AllowRegen` or `DAQmx_Val_DoNotAllowRegen`.
+    WriteRegenMode,
+    /// [`PropertyValue::I32`]. The point writes start from, offset by
+    /// [`TaskProperty::WriteOffset`]. `DAQmx_Val_FirstSample` or `DAQmx_Val_CurrWritePos`.
+    WriteRelativeTo,
+    /// [`PropertyValue::I32`]. Samples per channel from [`TaskProperty::WriteRelativeTo`] to the
+    /// first sample written.
+    WriteOffset,
+    /// [`PropertyValue::Bool`]. Whether the device timestamps the first sample, on devices
+    /// which support it.
+    FirstSampleTimestampEnable,
+    /// [`PropertyValue::Timestamp`], read only. When the first sample was acquired, if
+    /// [`TaskProperty::FirstSampleTimestampEnable`] is set.
+    FirstSampleTimestamp,
+}
+
+/// The value of a [`ChannelProperty`] or [`TaskProperty`].
+#[cfg_attr(
+    feature = "serde_support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    U64(u64),
+    F64(f64),
+    String(String),
+    Timestamp(SystemTime),
+}
+
+macro_rules! property_value_conversion {
+    ($variant:ident, $t:ty) => {
+        impl TryFrom<PropertyValue> for $t {
+            type Error = DaqmxError;
+
+            fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+                match value {
+                    PropertyValue::$variant(inner) => Ok(inner),
+                    other => Err(DaqmxError::UnexpectedValue(
+                        format!("{other:?} (expected {})", stringify!($variant)),
+                        0,
+                    )),
+                }
+            }
+        }
+    };
+}
+
+property_value_conversion!(Bool, bool);
+property_value_conversion!(I32, i32);
+property_value_conversion!(U32, u32);
+property_value_conversion!(U64, u64);
+property_value_conversion!(F64, f64);
+property_value_conversion!(String, String);
+property_value_conversion!(Timestamp, SystemTime);
+
+/// A Rust closure registered for a DAQmx event.
+///
+/// Backends call it with the arguments of the event: the event type and number of samples for
+/// Every N Samples events, the task status for Done events and the signal for Signal events,
+/// with 0 samples.
+/// The registering task keeps the handler alive until the task is cleared, which unregisters it.
+pub struct EventHandler {
+    callback: Mutex<Box<dyn FnMut(i32, u32) + Send>>,
+}
+
+impl EventHandler {
+    pub fn new(callback: impl FnMut(i32, u32) + Send + 'static) -> Arc<Self> {
+        Arc::new(Self {
+            callback: Mutex::new(Box::new(callback)),
+        })
+    }
+
+    /// Runs the callback. A panic is caught and logged as it must not unwind into the driver.
+    pub fn call(&self, value: i32, samples: u32) {
+        let mut callback = self.callback.lock().unwrap_or_else(|e| e.into_inner());
+        if catch_unwind(AssertUnwindSafe(|| callback(value, samples))).is_err() {
+            error!("DAQmx event callback panicked");
+        }
+    }
+}
+
+/// The interface to a DAQmx driver implementation.
+///
+/// Each method corresponds to a function in the NI-DAQmx C API and returns its status code.
+///
+/// # Safety
+///
+/// Methods taking a [`TaskHandle`] are `unsafe`: the handle must have been returned by
+/// [`Backend::create_task`] on the same backend and not yet passed to [`Backend::clear_task`].
+#[allow(clippy::missing_safety_doc)]
+pub trait Backend: Send + Sync {
+    /// The extended description of the last error raised on this thread.
+    fn extended_error_info(&self) -> String;
+    /// The description of a status code. Used for warnings.
+    fn error_string(&self, code: i32) -> String;
+
+    /// Comma separated list of the devices in the system.
+    fn device_names(&self, names: &mut String) -> i32;
+    /// Comma separated list of the physical channels of the given type on a device.
+    fn device_physical_channels(
+        &self,
+        device: &CStr,
+        channel_type: ChannelType,
+        channels: &mut String,
+    ) -> i32;
+    fn reset_device(&self, device: &CStr) -> i32;
+    fn create_lin_scale(
+        &self,
+        name: &CStr,
+        slope: f64,
+        y_intercept: f64,
+        pre_scaled_units: i32,
+        scaled_units: &CStr,
+    ) -> i32;
+    /// The units of the values scaled by the custom scale `name`.
+    fn scale_scaled_units(&self, name: &CStr, units: &mut String) -> i32;
+
+    fn create_task(&self, name: &CStr, task: &mut TaskHandle) -> i32;
+    unsafe fn clear_task(&self, task: TaskHandle) -> i32;
+    unsafe fn task_name(&self, task: TaskHandle, name: &mut String) -> i32;
+    /// The names of the virtual channels in the task, as a comma separated list.
+    unsafe fn task_channels(&self, task: TaskHandle, channels: &mut String) -> i32;
+    unsafe fn start_task(&self, task: TaskHandle) -> i32;
+    unsafe fn stop_task(&self, task: TaskHandle) -> i32;
+    unsafe fn wait_until_task_done(&self, task: TaskHandle, timeout: f64) -> i32;
+    unsafe fn is_task_done(&self, task: TaskHandle, done: &mut bool) -> i32;
+    /// `action` is one of the `DAQmx_Val_Task_*` values, see [`crate::types::TaskAction`].
+    unsafe fn task_control(&self, task: TaskHandle, action: i32) -> i32;
+    /// Calls `handler` every `samples` samples, or unregisters the event if `handler` is [`None`].
+    /// `event_type` is `DAQmx_Val_Acquired_Into_Buffer` or `DAQmx_Val_Transferred_From_Buffer`.
+    ///
+    /// The caller keeps `handler` alive until the event is unregistered or the task cleared.
+    unsafe fn register_every_n_samples_event(
+        &self,
+        task: TaskHandle,
+        event_type: i32,
+        samples: u32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32;
+    /// Calls `handler` with the status of the task when it finishes or stops on an error, or
+    /// unregisters the event if `handler` is [`None`].
+    unsafe fn register_done_event(
+        &self,
+        task: TaskHandle,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32;
+    /// Calls `handler` with `signal` each time the signal occurs, or unregisters the event if
+    /// `handler` is [`None`]. `signal` is one of the `DAQmx_Val_*Event` or
+    /// `DAQmx_Val_SampleClock` values, see [`crate::types::SignalEvent`].
+    unsafe fn register_signal_event(
+        &self,
+        task: TaskHandle,
+        signal: i32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32;
+
+    unsafe fn get_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &mut PropertyValue,
+    ) -> i32;
+    unsafe fn set_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &PropertyValue,
+    ) -> i32;
+    /// An empty `channel` refers to all channels in the task.
+    unsafe fn get_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &mut PropertyValue,
+    ) -> i32;
+    /// An empty `channel` refers to all channels in the task.
+    unsafe fn set_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &PropertyValue,
+    ) -> i32;
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn create_ai_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        terminal_config: i32,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32;
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn create_ao_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32;
+    unsafe fn create_di_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32;
+    unsafe fn create_do_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32;
+    unsafe fn create_ci_count_edges_chan(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        edge: i32,
+        initial_count: u32,
+        count_direction: i32,
+    ) -> i32;
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn create_co_pulse_chan_time(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        low_time: f64,
+        high_time: f64,
+    ) -> i32;
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn create_co_pulse_chan_freq(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        frequency: f64,
+        duty_cycle: f64,
+    ) -> i32;
+
+    /// `source` of [`None`] uses the onboard clock.
+    unsafe fn cfg_samp_clk_timing(
+        &self,
+        task: TaskHandle,
+        source: Option<&CStr>,
+        rate: f64,
+        active_edge: i32,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32;
+    unsafe fn cfg_implicit_timing(
+        &self,
+        task: TaskHandle,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32;
+    /// Sets the size of the input buffer in samples per channel, overriding the size chosen from
+    /// the timing of the task.
+    unsafe fn cfg_input_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32;
+    /// Sets the size of the output buffer in samples per channel, overriding the size chosen from
+    /// the first write.
+    unsafe fn cfg_output_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32;
+    unsafe fn cfg_dig_edge_start_trig(&self, task: TaskHandle, source: &CStr, edge: i32) -> i32;
+    unsafe fn cfg_dig_edge_ref_trig(
+        &self,
+        task: TaskHandle,
+        source: &CStr,
+        edge: i32,
+        pretrigger_samples: u32,
+    ) -> i32;
+    unsafe fn disable_start_trig(&self, task: TaskHandle) -> i32;
+    unsafe fn export_signal(&self, task: TaskHandle, signal: i32, terminal: &CStr) -> i32;
+
+    /// `samples_per_channel` of `-1` reads all available samples (continuous) or
+    /// waits for the full acquisition (finite).
+    unsafe fn read_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32;
+    unsafe fn read_analog_scalar_f64(&self, task: TaskHandle, timeout: f64, value: &mut f64)
+        -> i32;
+    /// Each sample of each channel takes `bytes_per_sample` elements, one per line.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn read_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [u8],
+        samples_read: &mut i32,
+        bytes_per_sample: &mut i32,
+    ) -> i32;
+    unsafe fn read_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32;
+    unsafe fn read_counter_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32;
+    unsafe fn read_counter_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32;
+    unsafe fn read_counter_u32(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [u32],
+        samples_read: &mut i32,
+    ) -> i32;
+    unsafe fn read_counter_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32;
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn write_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[f64],
+        samples_written: &mut i32,
+    ) -> i32;
+    unsafe fn write_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: f64,
+    ) -> i32;
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn write_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[u8],
+        samples_written: &mut i32,
+    ) -> i32;
+    unsafe fn write_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: u32,
+    ) -> i32;
+}
+
+/// The NI-DAQmx C function a [`Backend`] method corresponds to, used to describe errors.
+///
+/// Property accessors map to the generic attribute functions.
+pub fn function_name(method: &'static str) -> &'static str {
+    match method {
+        "device_names" => "DAQmxGetSysDevNames",
+        "device_physical_channels" => "DAQmxGetDevPhysicalChans",
+        "reset_device" => "DAQmxResetDevice",
+        "create_lin_scale" => "DAQmxCreateLinScale",
+        "scale_scaled_units" => "DAQmxGetScaleScaledUnits",
+        "create_task" => "DAQmxCreateTask",
+        "clear_task" => "DAQmxClearTask",
+        "task_name" => "DAQmxGetTaskName",
+        "task_channels" => "DAQmxGetTaskChannels",
+        "start_task" => "DAQmxStartTask",
+        "stop_task" => "DAQmxStopTask",
+        "wait_until_task_done" => "DAQmxWaitUntilTaskDone",
+        "is_task_done" => "DAQmxIsTaskDone",
+        "task_control" => "DAQmxTaskControl",
+        "register_every_n_samples_event" => "DAQmxRegisterEveryNSamplesEvent",
+        "register_done_event" => "DAQmxRegisterDoneEvent",
+        "register_signal_event" => "DAQmxRegisterSignalEvent",
+        "get_task_property" => "DAQmxGetTaskAttribute",
+        "set_task_property" => "DAQmxSetTaskAttribute",
+        "get_channel_property" => "DAQmxGetChanAttribute",
+        "set_channel_property" => "DAQmxSetChanAttribute",
+        "create_ai_voltage_chan" => "DAQmxCreateAIVoltageChan",
+        "create_ao_voltage_chan" => "DAQmxCreateAOVoltageChan",
+        "create_di_chan" => "DAQmxCreateDIChan",
+        "create_do_chan" => "DAQmxCreateDOChan",
+        "create_ci_count_edges_chan" => "DAQmxCreateCICountEdgesChan",
+        "create_co_pulse_chan_time" => "DAQmxCreateCOPulseChanTime",
+        "create_co_pulse_chan_freq" => "DAQmxCreateCOPulseChanFreq",
+        "cfg_samp_clk_timing" => "DAQmxCfgSampClkTiming",
+        "cfg_implicit_timing" => "DAQmxCfgImplicitTiming",
+        "cfg_input_buffer" => "DAQmxCfgInputBuffer",
+        "cfg_output_buffer" => "DAQmxCfgOutputBuffer",
+        "cfg_dig_edge_start_trig" => "DAQmxCfgDigEdgeStartTrig",
+        "cfg_dig_edge_ref_trig" => "DAQmxCfgDigEdgeRefTrig",
+        "disable_start_trig" => "DAQmxDisableStartTrig",
+        "export_signal" => "DAQmxExportSignal",
+        "read_analog_f64" => "DAQmxReadAnalogF64",
+        "read_analog_scalar_f64" => "DAQmxReadAnalogScalarF64",
+        "read_digital_lines" => "DAQmxReadDigitalLines",
+        "read_digital_scalar_u32" => "DAQmxReadDigitalScalarU32",
+        "read_counter_scalar_u32" => "DAQmxReadCounterScalarU32",
+        "read_counter_scalar_f64" => "DAQmxReadCounterScalarF64",
+        "read_counter_u32" => "DAQmxReadCounterU32",
+        "read_counter_f64" => "DAQmxReadCounterF64",
+        "write_analog_f64" => "DAQmxWriteAnalogF64",
+        "write_analog_scalar_f64" => "DAQmxWriteAnalogScalarF64",
+        "write_digital_lines" => "DAQmxWriteDigitalLines",
+        "write_digital_scalar_u32" => "DAQmxWriteDigitalScalarU32",
+        other => other,
+    }
+}
+
+static DEFAULT_BACKEND: RwLock<Option<Arc<dyn Backend>>> = RwLock::new(None);
+
+/// The backend used by tasks and functions which aren't given one explicitly.
+///
+/// See the [module documentation](self) for how it is chosen.
+pub fn default_backend() -> Arc<dyn Backend> {
+    if let Some(backend) = DEFAULT_BACKEND
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        return backend.clone();
+    }
+
+    DEFAULT_BACKEND
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(backend_from_env)
+        .clone()
+}
+
+/// Replace the default backend for tasks created after this call.
+pub fn set_default_backend(backend: Arc<dyn Backend>) {
+    *DEFAULT_BACKEND.write().unwrap_or_else(|e| e.into_inner()) = Some(backend);
+}
+
+fn backend_from_env() -> Arc<dyn Backend> {
+    let backend: Arc<dyn Backend> = match std::env::var("DAQMX_BACKEND").as_deref() {
+        Ok("simulated") => Arc::new(SimulatedBackend::from_env()),
+        #[cfg(feature = "recording")]
+        Ok("replay") => {
+            let path = std::env::var("DAQMX_REPLAY_FILE")
+                .expect("DAQMX_BACKEND=replay requires DAQMX_REPLAY_FILE");
+            Arc::new(ReplayBackend::open(path).unwrap_or_else(|e| panic!("{e:#}")))
+        }
+        // Unit tests run on the simulated test device unless a backend is picked.
+        #[cfg(test)]
+        Err(_) => Arc::new(
+            SimulatedBackend::new().with_device(SimulatedDevice::x_series("PCIe-6363_test")),
+        ),
+        _ => Arc::new(FfiBackend),
+    };
+
+    #[cfg(feature = "recording")]
+    if let Ok(path) = std::env::var("DAQMX_RECORD_FILE") {
+        return Arc::new(
+            RecordingBackend::create(backend, path).unwrap_or_else(|e| panic!("{e:#}")),
+        );
+    }
+    backend
+}
@@ -0,0 +1,1999 @@
+//! Recording and replaying driver calls.
+//!
+//! [`RecordingBackend`] wraps another backend and appends every call it forwards to a log: the
+//! function, its arguments, the status code, the values written to output arguments (including
+//! read buffers) and the error or warning description. [`ReplayBackend`] serves a log back in
+//! order, so a session captured against hardware can be re-run in CI without it.
+//!
+//! The log is JSON lines, one [`CallRecord`] per call. Task handles are recorded as the number of
+//! the task in creation order so logs don't depend on the addresses handed out by the driver.
+//!
+//! Replay is strict: each call must match the function and arguments of the next record.
+//! On a mismatch the call fails with [`REPLAY_MISMATCH`] and an error describing the expected
+//! call, and the record is kept for the next call. Calls from several threads are replayed in the
+//! order they were recorded, so sessions which depend on thread timing may not replay.
+//! Event registrations are recorded, but events are not: a replayed task never calls its
+//! event callbacks.
+//!
+//! Both can also be selected through the environment, see the [backend module](super):
+//! `DAQMX_RECORD_FILE` records the calls of the default backend to a file and
+//! `DAQMX_BACKEND=replay` replays `DAQMX_REPLAY_FILE`.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use daqmx::backend::{RecordingBackend, ReplayBackend, SimulatedBackend, SimulatedDevice};
+//! use daqmx::channels::VoltageChannel;
+//! use daqmx::tasks::{AnalogInput, InputTask, Task};
+//! use daqmx::types::Timeout;
+//!
+//! let path = std::env::temp_dir().join("daqmx-recording-doctest.jsonl");
+//! let session = |backend| -> daqmx::error::Result<f64> {
+//!     let mut task: Task<AnalogInput> = Task::with_backend("recorded", backend)?;
+//!     task.create_channel(VoltageChannel::builder("ai0", "Dev1/ai0")?.build()?)?;
+//!     task.read_scalar(Timeout::Seconds(1.0))
+//! };
+//!
+//! let simulated = Arc::new(SimulatedBackend::new().with_device(SimulatedDevice::x_series("Dev1")));
+//! let recorded = session(Arc::new(RecordingBackend::create(simulated, &path)?))?;
+//!
+//! let replay = Arc::new(ReplayBackend::open(&path)?);
+//! assert_eq!(session(replay.clone())?, recorded);
+//! assert_eq!(replay.remaining(), 0);
+//! # Ok::<(), daqmx::Error>(())
+//! ```
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use log::error;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{Backend, ChannelProperty, EventHandler, PropertyValue, TaskProperty};
+use crate::daqmx::{bool32, TaskHandle};
+use crate::error::{DaqmxError, Result};
+use crate::utils::info::ChannelType;
+
+/// Status returned by [`ReplayBackend`] when a call doesn't match the recording.
+/// This is not an NI-DAQmx status code.
+pub const REPLAY_MISMATCH: i32 = -1;
+
+/// A single recorded driver call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CallRecord {
+    /// The [`Backend`] method called.
+    pub function: String,
+    /// The input arguments, in order.
+    pub args: Vec<Value>,
+    /// The values written to the output arguments, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<Value>,
+    pub status: i32,
+    /// The extended error information for errors, or the description of warnings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+fn text(value: &CStr) -> Value {
+    Value::String(value.to_string_lossy().into_owned())
+}
+
+fn describe(function: &str, args: &[Value]) -> String {
+    let args: Vec<String> = args.iter().map(Value::to_string).collect();
+    format!("{function}({})", args.join(", "))
+}
+
+/// A backend which forwards to another and records every call. See the [module documentation](self).
+pub struct RecordingBackend {
+    inner: Arc<dyn Backend>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    /// Task numbers by handle address.
+    tasks: Mutex<HashMap<usize, u64>>,
+    next_task: AtomicU64,
+}
+
+impl RecordingBackend {
+    /// Records the calls to `inner` to `writer`, flushing after each call.
+    pub fn new(inner: Arc<dyn Backend>, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            inner,
+            writer: Mutex::new(Box::new(writer)),
+            tasks: Mutex::new(HashMap::new()),
+            next_task: AtomicU64::new(1),
+        }
+    }
+
+    /// Records the calls to `inner` to a new file at `path`, replacing any existing file.
+    pub fn create(inner: Arc<dyn Backend>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| {
+            DaqmxError::Recording(format!(
+                "Failed to create recording file {}: {e}",
+                path.display()
+            ))
+        })?;
+        Ok(Self::new(inner, LineWriter::new(file)))
+    }
+
+    fn lock_tasks(&self) -> MutexGuard<'_, HashMap<usize, u64>> {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn task(&self, task: TaskHandle) -> Value {
+        json!(self.lock_tasks().get(&task.addr()).copied().unwrap_or(0))
+    }
+
+    /// Writes the record of a call and returns its status.
+    fn record(&self, function: &str, args: Vec<Value>, status: i32, outputs: Vec<Value>) -> i32 {
+        let message = match status {
+            0 => None,
+            i32::MIN..=-1 => Some(self.inner.extended_error_info()),
+            1..=i32::MAX => Some(self.inner.error_string(status)),
+        };
+        let record = CallRecord {
+            function: function.to_string(),
+            args,
+            outputs,
+            status,
+            message,
+        };
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let written = serde_json::to_writer(&mut *writer, &record)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writer.write_all(b"\n"))
+            .and_then(|()| writer.flush());
+        if let Err(e) = written {
+            error!("Failed to record DAQmx call {function}: {e}");
+        }
+        status
+    }
+}
+
+impl Backend for RecordingBackend {
+    fn extended_error_info(&self) -> String {
+        self.inner.extended_error_info()
+    }
+
+    fn error_string(&self, code: i32) -> String {
+        self.inner.error_string(code)
+    }
+
+    fn device_names(&self, names: &mut String) -> i32 {
+        let status = self.inner.device_names(names);
+        self.record("device_names", vec![], status, vec![json!(names)])
+    }
+
+    fn device_physical_channels(
+        &self,
+        device: &CStr,
+        channel_type: ChannelType,
+        channels: &mut String,
+    ) -> i32 {
+        let status = self
+            .inner
+            .device_physical_channels(device, channel_type, channels);
+        self.record(
+            "device_physical_channels",
+            vec![text(device), json!(channel_type)],
+            status,
+            vec![json!(channels)],
+        )
+    }
+
+    fn reset_device(&self, device: &CStr) -> i32 {
+        let status = self.inner.reset_device(device);
+        self.record("reset_device", vec![text(device)], status, vec![])
+    }
+
+    fn create_lin_scale(
+        &self,
+        name: &CStr,
+        slope: f64,
+        y_intercept: f64,
+        pre_scaled_units: i32,
+        scaled_units: &CStr,
+    ) -> i32 {
+        let status =
+            self.inner
+                .create_lin_scale(name, slope, y_intercept, pre_scaled_units, scaled_units);
+        self.record(
+            "create_lin_scale",
+            vec![
+                text(name),
+                json!(slope),
+                json!(y_intercept),
+                json!(pre_scaled_units),
+                text(scaled_units),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    fn scale_scaled_units(&self, name: &CStr, units: &mut String) -> i32 {
+        let status = self.inner.scale_scaled_units(name, units);
+        self.record(
+            "scale_scaled_units",
+            vec![text(name)],
+            status,
+            vec![json!(units)],
+        )
+    }
+
+    fn create_task(&self, name: &CStr, task: &mut TaskHandle) -> i32 {
+        let status = self.inner.create_task(name, task);
+        let id = if status >= 0 {
+            let id = self.next_task.fetch_add(1, Ordering::Relaxed);
+            self.lock_tasks().insert(task.addr(), id);
+            id
+        } else {
+            0
+        };
+        self.record("create_task", vec![text(name)], status, vec![json!(id)])
+    }
+
+    unsafe fn clear_task(&self, task: TaskHandle) -> i32 {
+        let args = vec![self.task(task)];
+        let status = self.inner.clear_task(task);
+        self.lock_tasks().remove(&task.addr());
+        self.record("clear_task", args, status, vec![])
+    }
+
+    unsafe fn task_name(&self, task: TaskHandle, name: &mut String) -> i32 {
+        let status = self.inner.task_name(task, name);
+        self.record(
+            "task_name",
+            vec![self.task(task)],
+            status,
+            vec![json!(name)],
+        )
+    }
+
+    unsafe fn task_channels(&self, task: TaskHandle, channels: &mut String) -> i32 {
+        let status = self.inner.task_channels(task, channels);
+        self.record(
+            "task_channels",
+            vec![self.task(task)],
+            status,
+            vec![json!(channels)],
+        )
+    }
+
+    unsafe fn start_task(&self, task: TaskHandle) -> i32 {
+        let status = self.inner.start_task(task);
+        self.record("start_task", vec![self.task(task)], status, vec![])
+    }
+
+    unsafe fn stop_task(&self, task: TaskHandle) -> i32 {
+        let status = self.inner.stop_task(task);
+        self.record("stop_task", vec![self.task(task)], status, vec![])
+    }
+
+    unsafe fn task_control(&self, task: TaskHandle, action: i32) -> i32 {
+        let status = self.inner.task_control(task, action);
+        self.record(
+            "task_control",
+            vec![self.task(task), json!(action)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn register_every_n_samples_event(
+        &self,
+        task: TaskHandle,
+        event_type: i32,
+        samples: u32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        let status = self
+            .inner
+            .register_every_n_samples_event(task, event_type, samples, handler);
+        self.record(
+            "register_every_n_samples_event",
+            vec![
+                self.task(task),
+                json!(event_type),
+                json!(samples),
+                json!(handler.is_some()),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn register_done_event(
+        &self,
+        task: TaskHandle,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        let status = self.inner.register_done_event(task, handler);
+        self.record(
+            "register_done_event",
+            vec![self.task(task), json!(handler.is_some())],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn register_signal_event(
+        &self,
+        task: TaskHandle,
+        signal: i32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        let status = self.inner.register_signal_event(task, signal, handler);
+        self.record(
+            "register_signal_event",
+            vec![self.task(task), json!(signal), json!(handler.is_some())],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn wait_until_task_done(&self, task: TaskHandle, timeout: f64) -> i32 {
+        let status = self.inner.wait_until_task_done(task, timeout);
+        self.record(
+            "wait_until_task_done",
+            vec![self.task(task), json!(timeout)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn is_task_done(&self, task: TaskHandle, done: &mut bool) -> i32 {
+        let status = self.inner.is_task_done(task, done);
+        self.record(
+            "is_task_done",
+            vec![self.task(task)],
+            status,
+            vec![json!(done)],
+        )
+    }
+
+    unsafe fn get_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        let args = vec![self.task(task), json!(property), json!(value)];
+        let status = self.inner.get_task_property(task, property, value);
+        self.record("get_task_property", args, status, vec![json!(value)])
+    }
+
+    unsafe fn set_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        let status = self.inner.set_task_property(task, property, value);
+        self.record(
+            "set_task_property",
+            vec![self.task(task), json!(property), json!(value)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn get_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        let args = vec![
+            self.task(task),
+            text(channel),
+            json!(property),
+            json!(value),
+        ];
+        let status = self
+            .inner
+            .get_channel_property(task, channel, property, value);
+        self.record("get_channel_property", args, status, vec![json!(value)])
+    }
+
+    unsafe fn set_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        let status = self
+            .inner
+            .set_channel_property(task, channel, property, value);
+        self.record(
+            "set_channel_property",
+            vec![
+                self.task(task),
+                text(channel),
+                json!(property),
+                json!(value),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_ai_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        terminal_config: i32,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        let status = self.inner.create_ai_voltage_chan(
+            task,
+            physical_channel,
+            name,
+            terminal_config,
+            min,
+            max,
+            units,
+            custom_scale_name,
+        );
+        self.record(
+            "create_ai_voltage_chan",
+            vec![
+                self.task(task),
+                text(physical_channel),
+                text(name),
+                json!(terminal_config),
+                json!(min),
+                json!(max),
+                json!(units),
+                text(custom_scale_name),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_ao_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        let status = self.inner.create_ao_voltage_chan(
+            task,
+            physical_channel,
+            name,
+            min,
+            max,
+            units,
+            custom_scale_name,
+        );
+        self.record(
+            "create_ao_voltage_chan",
+            vec![
+                self.task(task),
+                text(physical_channel),
+                text(name),
+                json!(min),
+                json!(max),
+                json!(units),
+                text(custom_scale_name),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_di_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        let status = self.inner.create_di_chan(task, lines, name, line_grouping);
+        self.record(
+            "create_di_chan",
+            vec![
+                self.task(task),
+                text(lines),
+                text(name),
+                json!(line_grouping),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_do_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        let status = self.inner.create_do_chan(task, lines, name, line_grouping);
+        self.record(
+            "create_do_chan",
+            vec![
+                self.task(task),
+                text(lines),
+                text(name),
+                json!(line_grouping),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_ci_count_edges_chan(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        edge: i32,
+        initial_count: u32,
+        count_direction: i32,
+    ) -> i32 {
+        let status = self.inner.create_ci_count_edges_chan(
+            task,
+            counter,
+            name,
+            edge,
+            initial_count,
+            count_direction,
+        );
+        self.record(
+            "create_ci_count_edges_chan",
+            vec![
+                self.task(task),
+                text(counter),
+                text(name),
+                json!(edge),
+                json!(initial_count),
+                json!(count_direction),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_time(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        low_time: f64,
+        high_time: f64,
+    ) -> i32 {
+        let status = self.inner.create_co_pulse_chan_time(
+            task,
+            counter,
+            name,
+            units,
+            idle_state,
+            initial_delay,
+            low_time,
+            high_time,
+        );
+        self.record(
+            "create_co_pulse_chan_time",
+            vec![
+                self.task(task),
+                text(counter),
+                text(name),
+                json!(units),
+                json!(idle_state),
+                json!(initial_delay),
+                json!(low_time),
+                json!(high_time),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_freq(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        frequency: f64,
+        duty_cycle: f64,
+    ) -> i32 {
+        let status = self.inner.create_co_pulse_chan_freq(
+            task,
+            counter,
+            name,
+            units,
+            idle_state,
+            initial_delay,
+            frequency,
+            duty_cycle,
+        );
+        self.record(
+            "create_co_pulse_chan_freq",
+            vec![
+                self.task(task),
+                text(counter),
+                text(name),
+                json!(units),
+                json!(idle_state),
+                json!(initial_delay),
+                json!(frequency),
+                json!(duty_cycle),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn cfg_samp_clk_timing(
+        &self,
+        task: TaskHandle,
+        source: Option<&CStr>,
+        rate: f64,
+        active_edge: i32,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        let status = self.inner.cfg_samp_clk_timing(
+            task,
+            source,
+            rate,
+            active_edge,
+            sample_mode,
+            samples_per_channel,
+        );
+        self.record(
+            "cfg_samp_clk_timing",
+            vec![
+                self.task(task),
+                source.map_or(Value::Null, text),
+                json!(rate),
+                json!(active_edge),
+                json!(sample_mode),
+                json!(samples_per_channel),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn cfg_implicit_timing(
+        &self,
+        task: TaskHandle,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        let status = self
+            .inner
+            .cfg_implicit_timing(task, sample_mode, samples_per_channel);
+        self.record(
+            "cfg_implicit_timing",
+            vec![
+                self.task(task),
+                json!(sample_mode),
+                json!(samples_per_channel),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn cfg_input_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32 {
+        let status = self.inner.cfg_input_buffer(task, samples_per_channel);
+        self.record(
+            "cfg_input_buffer",
+            vec![self.task(task), json!(samples_per_channel)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn cfg_output_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32 {
+        let status = self.inner.cfg_output_buffer(task, samples_per_channel);
+        self.record(
+            "cfg_output_buffer",
+            vec![self.task(task), json!(samples_per_channel)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn cfg_dig_edge_start_trig(&self, task: TaskHandle, source: &CStr, edge: i32) -> i32 {
+        let status = self.inner.cfg_dig_edge_start_trig(task, source, edge);
+        self.record(
+            "cfg_dig_edge_start_trig",
+            vec![self.task(task), text(source), json!(edge)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn cfg_dig_edge_ref_trig(
+        &self,
+        task: TaskHandle,
+        source: &CStr,
+        edge: i32,
+        pretrigger_samples: u32,
+    ) -> i32 {
+        let status = self
+            .inner
+            .cfg_dig_edge_ref_trig(task, source, edge, pretrigger_samples);
+        self.record(
+            "cfg_dig_edge_ref_trig",
+            vec![
+                self.task(task),
+                text(source),
+                json!(edge),
+                json!(pretrigger_samples),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn disable_start_trig(&self, task: TaskHandle) -> i32 {
+        let status = self.inner.disable_start_trig(task);
+        self.record("disable_start_trig", vec![self.task(task)], status, vec![])
+    }
+
+    unsafe fn export_signal(&self, task: TaskHandle, signal: i32, terminal: &CStr) -> i32 {
+        let status = self.inner.export_signal(task, signal, terminal);
+        self.record(
+            "export_signal",
+            vec![self.task(task), json!(signal), text(terminal)],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn read_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        let status = self.inner.read_analog_f64(
+            task,
+            samples_per_channel,
+            timeout,
+            fill_mode,
+            buffer,
+            samples_read,
+        );
+        self.record(
+            "read_analog_f64",
+            vec![
+                self.task(task),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer.len()),
+            ],
+            status,
+            vec![json!(buffer), json!(samples_read)],
+        )
+    }
+
+    unsafe fn read_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        let status = self.inner.read_analog_scalar_f64(task, timeout, value);
+        self.record(
+            "read_analog_scalar_f64",
+            vec![self.task(task), json!(timeout)],
+            status,
+            vec![json!(value)],
+        )
+    }
+
+    unsafe fn read_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [u8],
+        samples_read: &mut i32,
+        bytes_per_sample: &mut i32,
+    ) -> i32 {
+        let status = self.inner.read_digital_lines(
+            task,
+            samples_per_channel,
+            timeout,
+            fill_mode,
+            buffer,
+            samples_read,
+            bytes_per_sample,
+        );
+        self.record(
+            "read_digital_lines",
+            vec![
+                self.task(task),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer.len()),
+            ],
+            status,
+            vec![json!(buffer), json!(samples_read), json!(bytes_per_sample)],
+        )
+    }
+
+    unsafe fn read_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        let status = self.inner.read_digital_scalar_u32(task, timeout, value);
+        self.record(
+            "read_digital_scalar_u32",
+            vec![self.task(task), json!(timeout)],
+            status,
+            vec![json!(value)],
+        )
+    }
+
+    unsafe fn read_counter_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        let status = self.inner.read_counter_scalar_u32(task, timeout, value);
+        self.record(
+            "read_counter_scalar_u32",
+            vec![self.task(task), json!(timeout)],
+            status,
+            vec![json!(value)],
+        )
+    }
+
+    unsafe fn read_counter_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        let status = self.inner.read_counter_scalar_f64(task, timeout, value);
+        self.record(
+            "read_counter_scalar_f64",
+            vec![self.task(task), json!(timeout)],
+            status,
+            vec![json!(value)],
+        )
+    }
+
+    unsafe fn read_counter_u32(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [u32],
+        samples_read: &mut i32,
+    ) -> i32 {
+        let status =
+            self.inner
+                .read_counter_u32(task, samples_per_channel, timeout, buffer, samples_read);
+        self.record(
+            "read_counter_u32",
+            vec![
+                self.task(task),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(buffer.len()),
+            ],
+            status,
+            vec![json!(buffer), json!(samples_read)],
+        )
+    }
+
+    unsafe fn read_counter_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        let status =
+            self.inner
+                .read_counter_f64(task, samples_per_channel, timeout, buffer, samples_read);
+        self.record(
+            "read_counter_f64",
+            vec![
+                self.task(task),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(buffer.len()),
+            ],
+            status,
+            vec![json!(buffer), json!(samples_read)],
+        )
+    }
+
+    unsafe fn write_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[f64],
+        samples_written: &mut i32,
+    ) -> i32 {
+        let status = self.inner.write_analog_f64(
+            task,
+            samples_per_channel,
+            auto_start,
+            timeout,
+            fill_mode,
+            buffer,
+            samples_written,
+        );
+        self.record(
+            "write_analog_f64",
+            vec![
+                self.task(task),
+                json!(samples_per_channel),
+                json!(auto_start),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer),
+            ],
+            status,
+            vec![json!(samples_written)],
+        )
+    }
+
+    unsafe fn write_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: f64,
+    ) -> i32 {
+        let status = self
+            .inner
+            .write_analog_scalar_f64(task, auto_start, timeout, value);
+        self.record(
+            "write_analog_scalar_f64",
+            vec![
+                self.task(task),
+                json!(auto_start),
+                json!(timeout),
+                json!(value),
+            ],
+            status,
+            vec![],
+        )
+    }
+
+    unsafe fn write_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[u8],
+        samples_written: &mut i32,
+    ) -> i32 {
+        let status = self.inner.write_digital_lines(
+            task,
+            samples_per_channel,
+            auto_start,
+            timeout,
+            fill_mode,
+            buffer,
+            samples_written,
+        );
+        self.record(
+            "write_digital_lines",
+            vec![
+                self.task(task),
+                json!(samples_per_channel),
+                json!(auto_start),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer),
+            ],
+            status,
+            vec![json!(samples_written)],
+        )
+    }
+
+    unsafe fn write_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: u32,
+    ) -> i32 {
+        let status = self
+            .inner
+            .write_digital_scalar_u32(task, auto_start, timeout, value);
+        self.record(
+            "write_digital_scalar_u32",
+            vec![
+                self.task(task),
+                json!(auto_start),
+                json!(timeout),
+                json!(value),
+            ],
+            status,
+            vec![],
+        )
+    }
+}
+
+thread_local! {
+    static LAST_MESSAGE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Serves the calls of a recording back. See the [module documentation](self).
+pub struct ReplayBackend {
+    calls: Mutex<VecDeque<CallRecord>>,
+}
+
+impl ReplayBackend {
+    pub fn new(calls: impl IntoIterator<Item = CallRecord>) -> Self {
+        Self {
+            calls: Mutex::new(calls.into_iter().collect()),
+        }
+    }
+
+    /// Reads a recording written by [`RecordingBackend`].
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let calls = reader
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|(number, line)| {
+                let line = line.map_err(|e| DaqmxError::Recording(e.to_string()))?;
+                serde_json::from_str(&line).map_err(|e| {
+                    DaqmxError::Recording(format!(
+                        "Invalid call record on line {}: {e}",
+                        number + 1
+                    ))
+                })
+            })
+            .collect::<Result<Vec<CallRecord>>>()?;
+        Ok(Self::new(calls))
+    }
+
+    /// Reads a recording file written by [`RecordingBackend`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            DaqmxError::Recording(format!(
+                "Failed to open recording file {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// The number of recorded calls not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<CallRecord>> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Matches a call against the next record and passes its outputs to `apply`, which returns
+    /// [`None`] if they don't fit the output arguments.
+    fn replay(
+        &self,
+        function: &str,
+        args: Vec<Value>,
+        apply: impl FnOnce(&[Value]) -> Option<()>,
+    ) -> i32 {
+        let mut calls = self.lock();
+        let call = describe(function, &args);
+        let Some(next) = calls.front() else {
+            return mismatch(format!("Expected no further calls, got {call}"));
+        };
+        if next.function != function || next.args != args {
+            return mismatch(format!(
+                "Expected {}, got {call}",
+                describe(&next.function, &next.args)
+            ));
+        }
+        if apply(&next.outputs).is_none() {
+            return mismatch(format!(
+                "The recorded outputs {:?} of {call} don't fit its arguments",
+                next.outputs
+            ));
+        }
+        let next = calls.pop_front().expect("checked above");
+        LAST_MESSAGE.with(|last| *last.borrow_mut() = next.message.unwrap_or_default());
+        next.status
+    }
+}
+
+fn mismatch(message: String) -> i32 {
+    LAST_MESSAGE.with(|last| *last.borrow_mut() = format!("Replay mismatch: {message}"));
+    REPLAY_MISMATCH
+}
+
+fn task(task: TaskHandle) -> Value {
+    json!(task.addr())
+}
+
+fn output<T: DeserializeOwned>(outputs: &[Value], index: usize) -> Option<T> {
+    serde_json::from_value(outputs.get(index)?.clone()).ok()
+}
+
+/// Copies a recorded buffer into `buffer`. Values which couldn't be represented in JSON
+/// (NaN and infinities) were recorded as `null` and replay as NaN.
+fn fill<T: DeserializeOwned + Copy>(
+    outputs: &[Value],
+    index: usize,
+    buffer: &mut [T],
+    missing: T,
+) -> Option<()> {
+    let recorded = outputs.get(index)?.as_array()?;
+    if recorded.len() != buffer.len() {
+        return None;
+    }
+    for (slot, value) in buffer.iter_mut().zip(recorded) {
+        *slot = match value {
+            Value::Null => missing,
+            value => serde_json::from_value(value.clone()).ok()?,
+        };
+    }
+    Some(())
+}
+
+fn scalar_f64(outputs: &[Value]) -> Option<f64> {
+    match outputs.first()? {
+        Value::Null => Some(f64::NAN),
+        value => value.as_f64(),
+    }
+}
+
+impl Backend for ReplayBackend {
+    fn extended_error_info(&self) -> String {
+        LAST_MESSAGE.with(|last| last.borrow().clone())
+    }
+
+    fn error_string(&self, _code: i32) -> String {
+        LAST_MESSAGE.with(|last| last.borrow().clone())
+    }
+
+    fn device_names(&self, names: &mut String) -> i32 {
+        self.replay("device_names", vec![], |outputs| {
+            *names = output(outputs, 0)?;
+            Some(())
+        })
+    }
+
+    fn device_physical_channels(
+        &self,
+        device: &CStr,
+        channel_type: ChannelType,
+        channels: &mut String,
+    ) -> i32 {
+        self.replay(
+            "device_physical_channels",
+            vec![text(device), json!(channel_type)],
+            |outputs| {
+                *channels = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    fn reset_device(&self, device: &CStr) -> i32 {
+        self.replay("reset_device", vec![text(device)], |_| Some(()))
+    }
+
+    fn create_lin_scale(
+        &self,
+        name: &CStr,
+        slope: f64,
+        y_intercept: f64,
+        pre_scaled_units: i32,
+        scaled_units: &CStr,
+    ) -> i32 {
+        self.replay(
+            "create_lin_scale",
+            vec![
+                text(name),
+                json!(slope),
+                json!(y_intercept),
+                json!(pre_scaled_units),
+                text(scaled_units),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    fn scale_scaled_units(&self, name: &CStr, units: &mut String) -> i32 {
+        self.replay("scale_scaled_units", vec![text(name)], |outputs| {
+            *units = output(outputs, 0)?;
+            Some(())
+        })
+    }
+
+    fn create_task(&self, name: &CStr, task: &mut TaskHandle) -> i32 {
+        self.replay("create_task", vec![text(name)], |outputs| {
+            let id: usize = output(outputs, 0)?;
+            *task = std::ptr::without_provenance_mut(id);
+            Some(())
+        })
+    }
+
+    unsafe fn clear_task(&self, handle: TaskHandle) -> i32 {
+        self.replay("clear_task", vec![task(handle)], |_| Some(()))
+    }
+
+    unsafe fn task_name(&self, handle: TaskHandle, name: &mut String) -> i32 {
+        self.replay("task_name", vec![task(handle)], |outputs| {
+            *name = output(outputs, 0)?;
+            Some(())
+        })
+    }
+
+    unsafe fn task_channels(&self, handle: TaskHandle, channels: &mut String) -> i32 {
+        self.replay("task_channels", vec![task(handle)], |outputs| {
+            *channels = output(outputs, 0)?;
+            Some(())
+        })
+    }
+
+    unsafe fn start_task(&self, handle: TaskHandle) -> i32 {
+        self.replay("start_task", vec![task(handle)], |_| Some(()))
+    }
+
+    unsafe fn stop_task(&self, handle: TaskHandle) -> i32 {
+        self.replay("stop_task", vec![task(handle)], |_| Some(()))
+    }
+
+    unsafe fn task_control(&self, handle: TaskHandle, action: i32) -> i32 {
+        self.replay("task_control", vec![task(handle), json!(action)], |_| {
+            Some(())
+        })
+    }
+
+    unsafe fn register_every_n_samples_event(
+        &self,
+        handle: TaskHandle,
+        event_type: i32,
+        samples: u32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        self.replay(
+            "register_every_n_samples_event",
+            vec![
+                task(handle),
+                json!(event_type),
+                json!(samples),
+                json!(handler.is_some()),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn register_done_event(
+        &self,
+        handle: TaskHandle,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        self.replay(
+            "register_done_event",
+            vec![task(handle), json!(handler.is_some())],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn register_signal_event(
+        &self,
+        handle: TaskHandle,
+        signal: i32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        self.replay(
+            "register_signal_event",
+            vec![task(handle), json!(signal), json!(handler.is_some())],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn wait_until_task_done(&self, handle: TaskHandle, timeout: f64) -> i32 {
+        self.replay(
+            "wait_until_task_done",
+            vec![task(handle), json!(timeout)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn is_task_done(&self, handle: TaskHandle, done: &mut bool) -> i32 {
+        self.replay("is_task_done", vec![task(handle)], |outputs| {
+            *done = output(outputs, 0)?;
+            Some(())
+        })
+    }
+
+    unsafe fn get_task_property(
+        &self,
+        handle: TaskHandle,
+        property: TaskProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        self.replay(
+            "get_task_property",
+            vec![task(handle), json!(property), json!(value)],
+            |outputs| {
+                *value = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn set_task_property(
+        &self,
+        handle: TaskHandle,
+        property: TaskProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        self.replay(
+            "set_task_property",
+            vec![task(handle), json!(property), json!(value)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn get_channel_property(
+        &self,
+        handle: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        self.replay(
+            "get_channel_property",
+            vec![task(handle), text(channel), json!(property), json!(value)],
+            |outputs| {
+                *value = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn set_channel_property(
+        &self,
+        handle: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        self.replay(
+            "set_channel_property",
+            vec![task(handle), text(channel), json!(property), json!(value)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_ai_voltage_chan(
+        &self,
+        handle: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        terminal_config: i32,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        self.replay(
+            "create_ai_voltage_chan",
+            vec![
+                task(handle),
+                text(physical_channel),
+                text(name),
+                json!(terminal_config),
+                json!(min),
+                json!(max),
+                json!(units),
+                text(custom_scale_name),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_ao_voltage_chan(
+        &self,
+        handle: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        self.replay(
+            "create_ao_voltage_chan",
+            vec![
+                task(handle),
+                text(physical_channel),
+                text(name),
+                json!(min),
+                json!(max),
+                json!(units),
+                text(custom_scale_name),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_di_chan(
+        &self,
+        handle: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        self.replay(
+            "create_di_chan",
+            vec![task(handle), text(lines), text(name), json!(line_grouping)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_do_chan(
+        &self,
+        handle: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        self.replay(
+            "create_do_chan",
+            vec![task(handle), text(lines), text(name), json!(line_grouping)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_ci_count_edges_chan(
+        &self,
+        handle: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        edge: i32,
+        initial_count: u32,
+        count_direction: i32,
+    ) -> i32 {
+        self.replay(
+            "create_ci_count_edges_chan",
+            vec![
+                task(handle),
+                text(counter),
+                text(name),
+                json!(edge),
+                json!(initial_count),
+                json!(count_direction),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_time(
+        &self,
+        handle: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        low_time: f64,
+        high_time: f64,
+    ) -> i32 {
+        self.replay(
+            "create_co_pulse_chan_time",
+            vec![
+                task(handle),
+                text(counter),
+                text(name),
+                json!(units),
+                json!(idle_state),
+                json!(initial_delay),
+                json!(low_time),
+                json!(high_time),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_freq(
+        &self,
+        handle: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        units: i32,
+        idle_state: i32,
+        initial_delay: f64,
+        frequency: f64,
+        duty_cycle: f64,
+    ) -> i32 {
+        self.replay(
+            "create_co_pulse_chan_freq",
+            vec![
+                task(handle),
+                text(counter),
+                text(name),
+                json!(units),
+                json!(idle_state),
+                json!(initial_delay),
+                json!(frequency),
+                json!(duty_cycle),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn cfg_samp_clk_timing(
+        &self,
+        handle: TaskHandle,
+        source: Option<&CStr>,
+        rate: f64,
+        active_edge: i32,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        self.replay(
+            "cfg_samp_clk_timing",
+            vec![
+                task(handle),
+                source.map_or(Value::Null, text),
+                json!(rate),
+                json!(active_edge),
+                json!(sample_mode),
+                json!(samples_per_channel),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn cfg_implicit_timing(
+        &self,
+        handle: TaskHandle,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        self.replay(
+            "cfg_implicit_timing",
+            vec![task(handle), json!(sample_mode), json!(samples_per_channel)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn cfg_input_buffer(&self, handle: TaskHandle, samples_per_channel: u32) -> i32 {
+        self.replay(
+            "cfg_input_buffer",
+            vec![task(handle), json!(samples_per_channel)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn cfg_output_buffer(&self, handle: TaskHandle, samples_per_channel: u32) -> i32 {
+        self.replay(
+            "cfg_output_buffer",
+            vec![task(handle), json!(samples_per_channel)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn cfg_dig_edge_start_trig(&self, handle: TaskHandle, source: &CStr, edge: i32) -> i32 {
+        self.replay(
+            "cfg_dig_edge_start_trig",
+            vec![task(handle), text(source), json!(edge)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn cfg_dig_edge_ref_trig(
+        &self,
+        handle: TaskHandle,
+        source: &CStr,
+        edge: i32,
+        pretrigger_samples: u32,
+    ) -> i32 {
+        self.replay(
+            "cfg_dig_edge_ref_trig",
+            vec![
+                task(handle),
+                text(source),
+                json!(edge),
+                json!(pretrigger_samples),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn disable_start_trig(&self, handle: TaskHandle) -> i32 {
+        self.replay("disable_start_trig", vec![task(handle)], |_| Some(()))
+    }
+
+    unsafe fn export_signal(&self, handle: TaskHandle, signal: i32, terminal: &CStr) -> i32 {
+        self.replay(
+            "export_signal",
+            vec![task(handle), json!(signal), text(terminal)],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn read_analog_f64(
+        &self,
+        handle: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        self.replay(
+            "read_analog_f64",
+            vec![
+                task(handle),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer.len()),
+            ],
+            |outputs| {
+                fill(outputs, 0, buffer, f64::NAN)?;
+                *samples_read = output(outputs, 1)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_analog_scalar_f64(
+        &self,
+        handle: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        self.replay(
+            "read_analog_scalar_f64",
+            vec![task(handle), json!(timeout)],
+            |outputs| {
+                *value = scalar_f64(outputs)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_digital_lines(
+        &self,
+        handle: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [u8],
+        samples_read: &mut i32,
+        bytes_per_sample: &mut i32,
+    ) -> i32 {
+        self.replay(
+            "read_digital_lines",
+            vec![
+                task(handle),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer.len()),
+            ],
+            |outputs| {
+                fill(outputs, 0, buffer, 0)?;
+                *samples_read = output(outputs, 1)?;
+                *bytes_per_sample = output(outputs, 2)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_digital_scalar_u32(
+        &self,
+        handle: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        self.replay(
+            "read_digital_scalar_u32",
+            vec![task(handle), json!(timeout)],
+            |outputs| {
+                *value = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_counter_scalar_u32(
+        &self,
+        handle: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        self.replay(
+            "read_counter_scalar_u32",
+            vec![task(handle), json!(timeout)],
+            |outputs| {
+                *value = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_counter_scalar_f64(
+        &self,
+        handle: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        self.replay(
+            "read_counter_scalar_f64",
+            vec![task(handle), json!(timeout)],
+            |outputs| {
+                *value = scalar_f64(outputs)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_counter_u32(
+        &self,
+        handle: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [u32],
+        samples_read: &mut i32,
+    ) -> i32 {
+        self.replay(
+            "read_counter_u32",
+            vec![
+                task(handle),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(buffer.len()),
+            ],
+            |outputs| {
+                fill(outputs, 0, buffer, 0)?;
+                *samples_read = output(outputs, 1)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn read_counter_f64(
+        &self,
+        handle: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        self.replay(
+            "read_counter_f64",
+            vec![
+                task(handle),
+                json!(samples_per_channel),
+                json!(timeout),
+                json!(buffer.len()),
+            ],
+            |outputs| {
+                fill(outputs, 0, buffer, f64::NAN)?;
+                *samples_read = output(outputs, 1)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn write_analog_f64(
+        &self,
+        handle: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[f64],
+        samples_written: &mut i32,
+    ) -> i32 {
+        self.replay(
+            "write_analog_f64",
+            vec![
+                task(handle),
+                json!(samples_per_channel),
+                json!(auto_start),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer),
+            ],
+            |outputs| {
+                *samples_written = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn write_analog_scalar_f64(
+        &self,
+        handle: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: f64,
+    ) -> i32 {
+        self.replay(
+            "write_analog_scalar_f64",
+            vec![
+                task(handle),
+                json!(auto_start),
+                json!(timeout),
+                json!(value),
+            ],
+            |_| Some(()),
+        )
+    }
+
+    unsafe fn write_digital_lines(
+        &self,
+        handle: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &[u8],
+        samples_written: &mut i32,
+    ) -> i32 {
+        self.replay(
+            "write_digital_lines",
+            vec![
+                task(handle),
+                json!(samples_per_channel),
+                json!(auto_start),
+                json!(timeout),
+                json!(fill_mode),
+                json!(buffer),
+            ],
+            |outputs| {
+                *samples_written = output(outputs, 0)?;
+                Some(())
+            },
+        )
+    }
+
+    unsafe fn write_digital_scalar_u32(
+        &self,
+        handle: TaskHandle,
+        auto_start: bool,
+        timeout: f64,
+        value: u32,
+    ) -> i32 {
+        self.replay(
+            "write_digital_scalar_u32",
+            vec![
+                task(handle),
+                json!(auto_start),
+                json!(timeout),
+                json!(value),
+            ],
+            |_| Some(()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::{SimulatedBackend, SimulatedDevice};
+    use crate::channels::VoltageChannel;
+    use crate::tasks::{AnalogInput, InputTask, Task};
+    use crate::types::{ClockEdge, DataFillMode, SampleMode, Timeout};
+
+    fn session(backend: Arc<dyn Backend>) -> Result<Vec<f64>> {
+        let mut task: Task<AnalogInput> = Task::with_backend("session", backend)?;
+        task.create_channel(VoltageChannel::builder("ai0", "Dev1/ai0:1")?.build()?)?;
+        task.configure_sample_clock_timing(
+            None,
+            1000.0,
+            ClockEdge::Rising,
+            SampleMode::FiniteSamples,
+            10,
+        )?;
+        let mut buffer = vec![0.0; 20];
+        task.read(
+            Timeout::Seconds(1.0),
+            DataFillMode::GroupByChannel,
+            Some(10),
+            &mut buffer,
+        )?;
+        Ok(buffer)
+    }
+
+    fn record() -> Vec<CallRecord> {
+        let path =
+            std::env::temp_dir().join(format!("daqmx-recording-test-{}.jsonl", std::process::id()));
+        let simulated =
+            Arc::new(SimulatedBackend::new().with_device(SimulatedDevice::x_series("Dev1")));
+        let recording = RecordingBackend::create(simulated, &path).unwrap();
+        session(Arc::new(recording)).unwrap();
+        let replay = ReplayBackend::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let records = replay.lock().iter().cloned().collect();
+        records
+    }
+
+    #[test]
+    fn test_replay_matches_recording() {
+        let records = record();
+        assert_eq!(records.first().unwrap().function, "create_task");
+        assert_eq!(records.last().unwrap().function, "clear_task");
+        let recorded = records
+            .iter()
+            .find(|r| r.function == "read_analog_f64")
+            .unwrap()
+            .outputs[0]
+            .clone();
+
+        let replay = Arc::new(ReplayBackend::new(records));
+        let data = session(replay.clone()).unwrap();
+        assert_eq!(json!(data), recorded);
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn test_replay_mismatch() {
+        let replay = Arc::new(ReplayBackend::new(record()));
+        let mut task: Task<AnalogInput> = Task::with_backend("session", replay.clone()).unwrap();
+        let error = task
+            .create_channel(
+                VoltageChannel::builder("ai0", "Dev1/ai2")
+                    .unwrap()
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap_err();
+        match error {
+            DaqmxError::DaqmxError { code, message, .. } => {
+                assert_eq!(code, REPLAY_MISMATCH);
+                assert!(message.contains("Dev1/ai0:1"), "{message}");
+            }
+            _ => panic!("Expected a DAQmx error, got {error}"),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Simulated devices and physical channel name handling.
+
+use crate::utils::info::ChannelType;
+
+/// A device exposed by the [`super::SimulatedBackend`].
+///
+/// Physical channels follow the NI naming scheme: `Dev1/ai0`, `Dev1/ao0`, `Dev1/port0/line0`
+/// and `Dev1/ctr0`. Terminals such as `/Dev1/PFI0` are not validated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedDevice {
+    name: String,
+    analog_inputs: u32,
+    analog_outputs: u32,
+    ports: Vec<u32>,
+    counters: u32,
+}
+
+impl SimulatedDevice {
+    /// A device without any channels.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            analog_inputs: 0,
+            analog_outputs: 0,
+            ports: Vec::new(),
+            counters: 0,
+        }
+    }
+
+    /// An X Series multifunction device with the channels of a PCIe-6363:
+    /// 32 analog inputs, 4 analog outputs, digital ports of 32, 8 and 8 lines and 4 counters.
+    pub fn x_series(name: &str) -> Self {
+        Self::new(name)
+            .with_analog_inputs(32)
+            .with_analog_outputs(4)
+            .with_port(32)
+            .with_port(8)
+            .with_port(8)
+            .with_counters(4)
+    }
+
+    pub fn with_analog_inputs(mut self, count: u32) -> Self {
+        self.analog_inputs = count;
+        self
+    }
+
+    pub fn with_analog_outputs(mut self, count: u32) -> Self {
+        self.analog_outputs = count;
+        self
+    }
+
+    /// Adds the next digital port with the given number of lines.
+    pub fn with_port(mut self, lines: u32) -> Self {
+        self.ports.push(lines);
+        self
+    }
+
+    pub fn with_counters(mut self, count: u32) -> Self {
+        self.counters = count;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The full names of the physical channels of the given type.
+    pub(super) fn physical_channels(&self, channel_type: ChannelType) -> Vec<String> {
+        let local: Vec<String> = match channel_type {
+            ChannelType::AI => (0..self.analog_inputs).map(|i| format!("ai{i}")).collect(),
+            ChannelType::AO => (0..self.analog_outputs).map(|i| format!("ao{i}")).collect(),
+            ChannelType::DI | ChannelType::DO => self
+                .ports
+                .iter()
+                .enumerate()
+                .flat_map(|(port, &lines)| (0..lines).map(move |l| format!("port{port}/line{l}")))
+                .collect(),
+            ChannelType::CI | ChannelType::CO => {
+                (0..self.counters).map(|i| format!("ctr{i}")).collect()
+            }
+        };
+        local
+            .into_iter()
+            .map(|l| format!("{}/{l}", self.name))
+            .collect()
+    }
+
+    /// Resolves a channel local to this device (`ai0`, `port0`, `port0/line3`, ...) to the
+    /// physical channels it covers. A digital port covers all of its lines.
+    pub(super) fn resolve(&self, channel_type: ChannelType, local: &str) -> Option<Vec<String>> {
+        let local = local.to_ascii_lowercase();
+        let full = |l: &str| format!("{}/{l}", self.name);
+        match channel_type {
+            ChannelType::AI => index_of(&local, "ai")
+                .filter(|&i| i < self.analog_inputs)
+                .map(|_| vec![full(&local)]),
+            ChannelType::AO => index_of(&local, "ao")
+                .filter(|&i| i < self.analog_outputs)
+                .map(|_| vec![full(&local)]),
+            ChannelType::CI | ChannelType::CO => index_of(&local, "ctr")
+                .filter(|&i| i < self.counters)
+                .map(|_| vec![full(&local)]),
+            ChannelType::DI | ChannelType::DO => {
+                let (port, line) = match local.split_once('/') {
+                    Some((port, line)) => (port, Some(line)),
+                    None => (&local[..], None),
+                };
+                let port = index_of(port, "port")?;
+                let lines = *self.ports.get(port as usize)?;
+                match line {
+                    Some(line) => index_of(line, "line")
+                        .filter(|&l| l < lines)
+                        .map(|_| vec![full(&local)]),
+                    None => Some(
+                        (0..lines)
+                            .map(|l| full(&format!("port{port}/line{l}")))
+                            .collect(),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+fn index_of(name: &str, prefix: &str) -> Option<u32> {
+    name.strip_prefix(prefix)?.parse().ok()
+}
+
+/// Splits a physical channel list such as `Dev1/ai0, Dev1/ai2:4` into single entries,
+/// expanding ranges.
+pub(super) fn expand_channel_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .flat_map(expand_range)
+        .collect()
+}
+
+fn expand_range(entry: &str) -> Vec<String> {
+    let Some((start, end)) = entry.rsplit_once(':') else {
+        return vec![entry.to_string()];
+    };
+    let split = start
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let (prefix, first) = start.split_at(split);
+    let last = end.trim_start_matches(|c: char| !c.is_ascii_digit());
+    match (first.parse::<u32>(), last.parse::<u32>()) {
+        (Ok(first), Ok(last)) if first <= last => {
+            (first..=last).map(|i| format!("{prefix}{i}")).collect()
+        }
+        (Ok(first), Ok(last)) => (last..=first)
+            .rev()
+            .map(|i| format!("{prefix}{i}"))
+            .collect(),
+        _ => vec![entry.to_string()],
+    }
+}
+
+/// Terminal names compare without the leading slash and case-insensitively.
+pub(super) fn terminal_key(terminal: &str) -> String {
+    terminal.trim().trim_start_matches('/').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_expand_channel_list() {
+        assert_eq!(
+            expand_channel_list("Dev1/ai0, Dev1/ai2:4"),
+            vec!["Dev1/ai0", "Dev1/ai2", "Dev1/ai3", "Dev1/ai4"]
+        );
+        assert_eq!(
+            expand_channel_list("Dev1/port0/line1:0"),
+            vec!["Dev1/port0/line1", "Dev1/port0/line0"]
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let device = SimulatedDevice::x_series("Dev1");
+        assert_eq!(
+            device.resolve(ChannelType::AI, "ai31"),
+            Some(vec!["Dev1/ai31".to_string()])
+        );
+        assert_eq!(device.resolve(ChannelType::AI, "ai32"), None);
+        assert_eq!(device.resolve(ChannelType::DO, "port1").unwrap().len(), 8);
+        assert_eq!(device.resolve(ChannelType::DI, "port2/line8"), None);
+        assert_eq!(device.resolve(ChannelType::CO, "ctr0").unwrap().len(), 1);
+    }
+}
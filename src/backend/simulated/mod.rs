@@ -0,0 +1,1857 @@
+//! An in-memory NI-DAQmx simulation.
+//!
+//! [`SimulatedBackend`] models the parts of the driver the crate uses so tasks can be exercised
+//! without hardware or the driver installed:
+//!
+//! * Devices with physical channels, validated when channels are created.
+//! * Sample clocks driven by the wall clock, including finite acquisitions completing,
+//!   read timeouts and buffer overflows of continuous acquisitions.
+//! * Digital edge start triggers. Starting a task produces an edge on its start trigger terminal
+//!   (`/Dev1/ai/StartTrigger`), exported signals and counter output terminals, releasing any
+//!   task armed on that terminal.
+//! * Synthetic data: analog channels read a 10 Hz sine within their range and digital lines
+//!   a binary count.
+//!
+//! Errors use the same status codes as NI-DAQmx. Clearing a task inside one of its own event
+//! callbacks, which NI-DAQmx doesn't support, panics.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use daqmx::backend::{SimulatedBackend, SimulatedDevice};
+//! use daqmx::channels::VoltageChannel;
+//! use daqmx::tasks::{AnalogInput, InputTask, Task};
+//! use daqmx::types::Timeout;
+//!
+//! let backend = Arc::new(SimulatedBackend::new().with_device(SimulatedDevice::x_series("Dev1")));
+//! let mut task: Task<AnalogInput> = Task::with_backend("", backend)?;
+//! task.create_channel(VoltageChannel::builder("ai0", "Dev1/ai0")?.build()?)?;
+//! assert_ne!(task.read_scalar(Timeout::Seconds(1.0))?, 0.0);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+mod device;
+mod task;
+
+pub use device::SimulatedDevice;
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Instant, SystemTime};
+
+use self::device::{expand_channel_list, terminal_key};
+use self::task::{
+    ChannelConfig, EveryNSamples, RunState, SimChannel, SimTask, Step, TaskState, Timing,
+};
+use super::{Backend, ChannelProperty, EventHandler, PropertyValue, TaskProperty};
+use crate::daqmx::{self, bool32, TaskHandle};
+use crate::utils::info::ChannelType;
+
+/// Status codes returned by the simulation, matching NI-DAQmx.
+mod status {
+    pub const INVALID_ATTRIBUTE_VALUE: i32 = crate::daqmx::DAQmxErrorInvalidAttributeValue;
+    pub const INVALID_TASK: i32 = -200088;
+    pub const DUPLICATE_TASK: i32 = -200089;
+    pub const PHYSICAL_CHANNEL_DOES_NOT_EXIST: i32 = -200170;
+    pub const INVALID_DEVICE_ID: i32 = -200220;
+    pub const READ_BUFFER_TOO_SMALL: i32 = -200229;
+    pub const INVALID_POSITION_AND_OFFSET: i32 = -200277;
+    pub const SAMPLES_WILL_NEVER_BE_AVAILABLE: i32 = -200278;
+    pub const SAMPLES_NO_LONGER_AVAILABLE: i32 = -200279;
+    pub const SAMPLES_NOT_YET_AVAILABLE: i32 = -200284;
+    pub const GENERATION_STOPPED_TO_PREVENT_REGENERATION: i32 = -200290;
+    pub const SAMPLES_NOT_YET_WRITTEN: i32 = -200292;
+    pub const INVALID_CHANNEL: i32 = -200428;
+    pub const PROPERTY_NOT_APPLICABLE: i32 = -200452;
+    pub const NO_CHANNELS: i32 = -200477;
+    pub const TASK_NOT_RUNNING: i32 = -200473;
+    pub const TASK_RUNNING: i32 = -200479;
+    pub const DUPLICATE_CHANNEL: i32 = -200489;
+    pub const WAIT_UNTIL_DONE_TIMEOUT: i32 = -200560;
+
+    pub fn description(code: i32) -> &'static str {
+        match code {
+            0 => "No error.",
+            INVALID_ATTRIBUTE_VALUE => "Requested value is not a supported value for this property.",
+            INVALID_TASK => "Task specified is invalid or does not exist.",
+            DUPLICATE_TASK => "Task name specified conflicts with an existing task name.",
+            PHYSICAL_CHANNEL_DOES_NOT_EXIST => "Physical channel specified does not exist on this device.",
+            INVALID_DEVICE_ID => "Device identifier is invalid.",
+            READ_BUFFER_TOO_SMALL => "Buffer is too small to fit read data.",
+            SAMPLES_WILL_NEVER_BE_AVAILABLE => "Attempted to read a sample beyond the final sample acquired. The acquisition has stopped, therefore the sample specified by the combination of position and offset will never be available.",
+            SAMPLES_NO_LONGER_AVAILABLE => "The application is not able to keep up with the hardware acquisition. Increasing the buffer size, reading the data more frequently, or specifying a fixed number of samples to read instead of reading all available samples might correct the problem.",
+            SAMPLES_NOT_YET_AVAILABLE => "Some or all of the samples requested have not yet been acquired. To wait for the samples to become available use a longer read timeout or read later in your program.",
+            INVALID_CHANNEL => "Value passed to the Task/Channels In control is invalid.",
+            PROPERTY_NOT_APPLICABLE => "Specified property is not supported by the device or is not applicable to the task.",
+            NO_CHANNELS => "Specified operation cannot be performed when there are no channels in the task.",
+            TASK_NOT_RUNNING => "Read cannot be performed when the Auto Start property of DAQmx Read is set to false and the task is not running or committed.",
+            TASK_RUNNING => "Specified operation cannot be performed while the task is running.",
+            DUPLICATE_CHANNEL => "Specified channel cannot be added to the task, because a channel with the same name is already in the task.",
+            WAIT_UNTIL_DONE_TIMEOUT => "Wait Until Done did not indicate that the task was done within the specified timeout.",
+            _ => "Unknown status code.",
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+    /// The task whose event handler is running on this thread.
+    static IN_EVENT: Cell<*const SimTask> = const { Cell::new(std::ptr::null()) };
+}
+
+/// Calls an event handler of `task`, noting that it runs inside the task's callback.
+fn call_handler(task: &SimTask, handler: &EventHandler, value: i32, samples: u32) {
+    IN_EVENT.with(|current| current.set(task));
+    handler.call(value, samples);
+    IN_EVENT.with(|current| current.set(std::ptr::null()));
+}
+
+/// Records the extended error information for this thread and returns `code`.
+fn fail(code: i32, task: Option<&str>, detail: &str) -> i32 {
+    let mut message = status::description(code).to_string();
+    if !detail.is_empty() {
+        message.push('\n');
+        message.push_str(detail);
+    }
+    if let Some(task) = task {
+        message.push_str(&format!("\n\nTask Name: {task}"));
+    }
+    message.push_str(&format!("\n\nStatus Code: {code}"));
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    code
+}
+
+/// Simulated NI-DAQmx driver. See the [module documentation](self).
+pub struct SimulatedBackend {
+    state: Mutex<State>,
+}
+
+struct State {
+    devices: Vec<SimulatedDevice>,
+    tasks: HashMap<usize, Arc<SimTask>>,
+    next_id: usize,
+    scales: HashMap<String, Scale>,
+}
+
+/// A custom linear scale.
+struct Scale {
+    slope: f64,
+    y_intercept: f64,
+    units: String,
+}
+
+impl Default for SimulatedBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedBackend {
+    /// A backend without any devices.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                devices: Vec::new(),
+                tasks: HashMap::new(),
+                next_id: 1,
+                scales: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Adds a device to the simulated system.
+    pub fn with_device(self, device: SimulatedDevice) -> Self {
+        self.lock().devices.push(device);
+        self
+    }
+
+    /// A backend with an X Series device for each name in `DAQMX_SIMULATED_DEVICES`
+    /// (comma separated), or a single `SimDev1` if it isn't set.
+    pub fn from_env() -> Self {
+        let names =
+            std::env::var("DAQMX_SIMULATED_DEVICES").unwrap_or_else(|_| "SimDev1".to_string());
+        names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .fold(Self::new(), |backend, name| {
+                backend.with_device(SimulatedDevice::x_series(name))
+            })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn task(&self, handle: TaskHandle) -> Result<Arc<SimTask>, i32> {
+        self.lock()
+            .tasks
+            .get(&handle.addr())
+            .cloned()
+            .ok_or_else(|| fail(status::INVALID_TASK, None, ""))
+    }
+
+    fn device(&self, name: &str) -> Option<SimulatedDevice> {
+        self.lock()
+            .devices
+            .iter()
+            .find(|d| d.name().eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// Starts a task, or arms it if it has a start trigger.
+    fn start(&self, task: &Arc<SimTask>) -> i32 {
+        let edges = {
+            let mut state = task.lock();
+            if state.channels.is_empty() {
+                return fail(status::NO_CHANNELS, Some(&task.name), "");
+            }
+            if !state.is_idle() && !state.is_done(Instant::now()) {
+                return fail(status::TASK_RUNNING, Some(&task.name), "");
+            }
+            state.read_position = 0;
+            state.starts += 1;
+            if !state.is_on_demand() {
+                spawn_events(task, &state);
+            }
+            if state.start_trigger.is_some() {
+                state.run = RunState::Armed;
+                Vec::new()
+            } else {
+                state.run = RunState::Running {
+                    started: Instant::now(),
+                    started_at: SystemTime::now(),
+                };
+                state.start_edges()
+            }
+        };
+        task.changed.notify_all();
+        self.fire(edges);
+        0
+    }
+
+    /// Delivers edges on the given terminals to armed tasks, following the edges those tasks
+    /// produce in turn when they start.
+    fn fire(&self, mut edges: Vec<String>) {
+        while let Some(terminal) = edges.pop() {
+            let tasks: Vec<Arc<SimTask>> = self.lock().tasks.values().cloned().collect();
+            for task in tasks {
+                let mut state = task.lock();
+                if state.run == RunState::Armed
+                    && state.start_trigger.as_deref() == Some(terminal.as_str())
+                {
+                    state.run = RunState::Running {
+                        started: Instant::now(),
+                        started_at: SystemTime::now(),
+                    };
+                    edges.extend(state.start_edges());
+                    drop(state);
+                    task.changed.notify_all();
+                }
+            }
+        }
+    }
+
+    /// Runs `f` on an idle task, as required for configuration changes.
+    fn configure(&self, handle: TaskHandle, f: impl FnOnce(&mut TaskState) -> i32) -> i32 {
+        let task = match self.task(handle) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let mut state = task.lock();
+        if !state.is_idle() {
+            return fail(status::TASK_RUNNING, Some(&task.name), "");
+        }
+        f(&mut state)
+    }
+
+    /// Adds virtual channels for a physical channel list.
+    ///
+    /// Digital lines are combined into one channel unless `per_line` is set.
+    fn add_channels(
+        &self,
+        handle: TaskHandle,
+        kind: ChannelType,
+        physical: &CStr,
+        name: &CStr,
+        per_line: bool,
+        config: ChannelConfig,
+    ) -> i32 {
+        let task = match self.task(handle) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let physical = physical.to_string_lossy();
+        let entries = expand_channel_list(&physical);
+        if entries.is_empty() {
+            return fail(
+                status::PHYSICAL_CHANNEL_DOES_NOT_EXIST,
+                Some(&task.name),
+                "No physical channel specified.",
+            );
+        }
+
+        // (device, entry, resolved lines)
+        let mut resolved = Vec::new();
+        for entry in &entries {
+            let (device_name, local) = entry.split_once('/').unwrap_or((entry, ""));
+            let Some(device) = self.device(device_name) else {
+                return fail(
+                    status::INVALID_DEVICE_ID,
+                    Some(&task.name),
+                    &format!("Device Specified: {device_name}"),
+                );
+            };
+            let Some(lines) = device.resolve(kind, local) else {
+                return fail(
+                    status::PHYSICAL_CHANNEL_DOES_NOT_EXIST,
+                    Some(&task.name),
+                    &format!("Physical Channel Name: {entry}"),
+                );
+            };
+            resolved.push((device.name().to_string(), entry.clone(), lines));
+        }
+
+        let digital = matches!(kind, ChannelType::DI | ChannelType::DO);
+        let mut channels: Vec<SimChannel> = if digital && !per_line {
+            let lines = resolved.iter().map(|(_, _, l)| l.len() as u32).sum();
+            vec![SimChannel {
+                name: String::new(),
+                physical: entries.join(", "),
+                device: resolved[0].0.clone(),
+                config: ChannelConfig::Digital { lines },
+            }]
+        } else {
+            resolved
+                .into_iter()
+                .flat_map(|(device, _, lines)| {
+                    let config = config.clone();
+                    lines.into_iter().map(move |physical| SimChannel {
+                        name: String::new(),
+                        physical,
+                        device: device.clone(),
+                        config: match config {
+                            ChannelConfig::Digital { .. } => ChannelConfig::Digital { lines: 1 },
+                            ref other => other.clone(),
+                        },
+                    })
+                })
+                .collect()
+        };
+
+        let name = name.to_string_lossy();
+        let names: Vec<&str> = name
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .collect();
+        let count = channels.len();
+        for (i, channel) in channels.iter_mut().enumerate() {
+            channel.name = match names.len() {
+                0 => channel.physical.clone(),
+                1 if count > 1 => format!("{}{i}", names[0]),
+                _ => names.get(i).unwrap_or(&names[0]).to_string(),
+            };
+        }
+
+        let mut state = task.lock();
+        if !state.is_idle() {
+            return fail(status::TASK_RUNNING, Some(&task.name), "");
+        }
+        for channel in &channels {
+            if state
+                .channels
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(&channel.name))
+            {
+                return fail(
+                    status::DUPLICATE_CHANNEL,
+                    Some(&task.name),
+                    &format!("Channel Name: {}", channel.name),
+                );
+            }
+        }
+        state.kind = Some(kind);
+        state.channels.extend(channels);
+        0
+    }
+
+    /// Shared implementation of the buffered and scalar reads.
+    ///
+    /// `width` is the number of buffer elements per sample per channel and `fill` copies
+    /// `count` samples starting at sample `first` into the caller's buffer.
+    #[allow(clippy::too_many_arguments)]
+    fn read(
+        &self,
+        handle: TaskHandle,
+        kinds: &[ChannelType],
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer_len: usize,
+        width: impl FnOnce(&TaskState) -> usize,
+        fill: impl FnOnce(&TaskState, u64, usize),
+        samples_read: &mut i32,
+    ) -> i32 {
+        *samples_read = 0;
+        let task = match self.task(handle) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let name = Some(task.name.as_str());
+
+        let mut state = task.lock();
+        if state.channels.is_empty() {
+            return fail(status::NO_CHANNELS, name, "");
+        }
+        if !state.kind.is_some_and(|k| kinds.contains(&k)) {
+            return fail(
+                status::PROPERTY_NOT_APPLICABLE,
+                name,
+                "Task does not support reads.",
+            );
+        }
+        if state.is_idle() {
+            if !state.read_auto_start {
+                return fail(status::TASK_NOT_RUNNING, name, "");
+            }
+            drop(state);
+            let code = self.start(&task);
+            if code < 0 {
+                return code;
+            }
+            state = task.lock();
+        }
+
+        let per_sample = state.channels.len() * width(&state);
+        let capacity = (buffer_len / per_sample) as u64;
+        if capacity == 0 {
+            return fail(status::READ_BUFFER_TOO_SMALL, name, "");
+        }
+
+        let Some(position) = state.read_start(Instant::now()) else {
+            return fail(status::INVALID_POSITION_AND_OFFSET, name, "");
+        };
+        let wanted = if state.is_on_demand() {
+            samples_per_channel.max(1) as u64
+        } else if let Some(total) = state.total() {
+            if position >= total {
+                return fail(status::SAMPLES_WILL_NEVER_BE_AVAILABLE, name, "");
+            }
+            match samples_per_channel {
+                -1 => total - position,
+                n => (n.max(0) as u64).min(total - position),
+            }
+        } else {
+            match samples_per_channel {
+                -1 => state
+                    .acquired(Instant::now())
+                    .saturating_sub(position)
+                    .min(state.buffer_size()),
+                n => n.max(0) as u64,
+            }
+        }
+        .min(capacity);
+
+        let (mut state, outcome) = task.wait_until(state, timeout, |state, now| {
+            if state.is_idle() {
+                Step::Ready(Err(status::TASK_NOT_RUNNING))
+            } else if state.overflowed(now) || state.overwritten(now, position) {
+                Step::Ready(Err(status::SAMPLES_NO_LONGER_AVAILABLE))
+            } else if state.acquired(now) >= position + wanted {
+                Step::Ready(Ok(()))
+            } else {
+                Step::Wait(state.time_until(now, position + wanted))
+            }
+        });
+
+        let (count, code) = match outcome {
+            Some(Ok(())) => (wanted, 0),
+            Some(Err(code)) => return fail(code, name, ""),
+            // Timed out: return what has been acquired along with the error.
+            None => (
+                state
+                    .acquired(Instant::now())
+                    .saturating_sub(position)
+                    .min(wanted),
+                fail(status::SAMPLES_NOT_YET_AVAILABLE, name, ""),
+            ),
+        };
+        fill(&state, position, count as usize);
+        state.read_position = position + count;
+        *samples_read = count as i32;
+        code
+    }
+
+    /// Shared implementation of the buffered writes.
+    #[allow(clippy::too_many_arguments)]
+    fn write(
+        &self,
+        handle: TaskHandle,
+        kind: ChannelType,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        buffer_len: usize,
+        samples_written: &mut i32,
+    ) -> i32 {
+        *samples_written = 0;
+        let task = match self.task(handle) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let name = Some(task.name.as_str());
+
+        let mut state = task.lock();
+        if state.channels.is_empty() {
+            return fail(status::NO_CHANNELS, name, "");
+        }
+        if state.kind != Some(kind) {
+            return fail(
+                status::PROPERTY_NOT_APPLICABLE,
+                name,
+                "Task does not support writes.",
+            );
+        }
+        let width = if kind == ChannelType::DO {
+            state.lines_per_sample()
+        } else {
+            1
+        };
+        let capacity = buffer_len / (state.channels.len() * width);
+        let wanted = match samples_per_channel {
+            -1 => capacity,
+            n => (n.max(0) as usize).min(capacity),
+        } as u64;
+
+        let mut count = wanted;
+        let mut code = 0;
+        if state.write_waits_for_space() {
+            // Writes take the space freed as samples are generated, until they all fit.
+            let (waited, outcome) = task.wait_until(state, timeout, |state, now| {
+                if state.underflowed(now) {
+                    Step::Ready(Err(status::GENERATION_STOPPED_TO_PREVENT_REGENERATION))
+                } else if state.written + wanted <= state.acquired(now) + state.buffer_size() {
+                    Step::Ready(Ok(()))
+                } else {
+                    let needed = (state.written + wanted).saturating_sub(state.buffer_size());
+                    Step::Wait(state.time_until(now, needed))
+                }
+            });
+            state = waited;
+            match outcome {
+                Some(Ok(())) => {}
+                Some(Err(code)) => return fail(code, name, ""),
+                None => {
+                    count = (state.acquired(Instant::now()) + state.buffer_size())
+                        .saturating_sub(state.written)
+                        .min(wanted);
+                    code = fail(status::SAMPLES_NOT_YET_WRITTEN, name, "");
+                }
+            }
+        }
+        state.written += count;
+        let start = auto_start && state.is_idle() && !state.is_on_demand();
+        drop(state);
+
+        if start {
+            let code = self.start(&task);
+            if code < 0 {
+                return code;
+            }
+        }
+        *samples_written = count as i32;
+        code
+    }
+
+    fn write_scalar(&self, handle: TaskHandle, kind: ChannelType) -> i32 {
+        let task = match self.task(handle) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let state = task.lock();
+        if state.channels.is_empty() {
+            return fail(status::NO_CHANNELS, Some(&task.name), "");
+        }
+        if state.kind != Some(kind) {
+            return fail(
+                status::PROPERTY_NOT_APPLICABLE,
+                Some(&task.name),
+                "Task does not support writes.",
+            );
+        }
+        0
+    }
+}
+
+/// Starts threads calling the event handlers registered on a task for its current run.
+fn spawn_events(task: &Arc<SimTask>, state: &TaskState) {
+    let run = state.starts;
+    if let Some(event) = state.every_n_samples.clone() {
+        let task = task.clone();
+        std::thread::spawn(move || {
+            let every = u64::from(event.samples);
+            sample_events(&task, run, every, || {
+                call_handler(&task, &event.handler, event.event_type, event.samples)
+            })
+        });
+    }
+    if let Some((signal, handler)) = state.signal_event.clone() {
+        if state.has_signal(signal) {
+            let task = task.clone();
+            std::thread::spawn(move || {
+                sample_events(&task, run, 1, || call_handler(&task, &handler, signal, 0))
+            });
+        }
+    }
+    // Simulated tasks only finish by completing, never on an error.
+    if let (Some(handler), Some(total)) = (state.done_event.clone(), state.total()) {
+        let task = task.clone();
+        std::thread::spawn(move || {
+            if wait_for_samples(&task, run, total) {
+                call_handler(&task, &handler, 0, 0);
+            }
+        });
+    }
+}
+
+/// Calls `event` each time another `every` samples per channel have been acquired or generated,
+/// until run `run` of the task stops or it finishes.
+fn sample_events(task: &SimTask, run: u64, every: u64, mut event: impl FnMut()) {
+    let total = task.lock().total();
+    let mut next = every;
+    while total.is_none_or(|total| next <= total) && wait_for_samples(task, run, next) {
+        event();
+        next += every;
+    }
+}
+
+/// Waits until `samples` samples per channel have been acquired in run `run` of a task.
+/// Returns false if the task stops or restarts first.
+fn wait_for_samples(task: &SimTask, run: u64, samples: u64) -> bool {
+    let (_state, reached) = task.wait_until(task.lock(), -1.0, |state, now| {
+        if state.starts != run || state.is_idle() {
+            Step::Ready(false)
+        } else if state.acquired(now) >= samples {
+            Step::Ready(true)
+        } else {
+            Step::Wait(state.time_until(now, samples))
+        }
+    });
+    reached == Some(true)
+}
+
+impl Backend for SimulatedBackend {
+    fn extended_error_info(&self) -> String {
+        LAST_ERROR.with(|last| last.borrow().clone())
+    }
+
+    fn error_string(&self, code: i32) -> String {
+        status::description(code).to_string()
+    }
+
+    fn device_names(&self, names: &mut String) -> i32 {
+        *names = self
+            .lock()
+            .devices
+            .iter()
+            .map(|d| d.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        0
+    }
+
+    fn device_physical_channels(
+        &self,
+        device: &CStr,
+        channel_type: ChannelType,
+        channels: &mut String,
+    ) -> i32 {
+        let device_name = device.to_string_lossy();
+        match self.device(&device_name) {
+            Some(device) => {
+                *channels = device.physical_channels(channel_type).join(", ");
+                0
+            }
+            None => fail(
+                status::INVALID_DEVICE_ID,
+                None,
+                &format!("Device Specified: {device_name}"),
+            ),
+        }
+    }
+
+    fn reset_device(&self, device: &CStr) -> i32 {
+        let device_name = device.to_string_lossy();
+        let Some(device) = self.device(&device_name) else {
+            return fail(
+                status::INVALID_DEVICE_ID,
+                None,
+                &format!("Device Specified: {device_name}"),
+            );
+        };
+        let tasks: Vec<Arc<SimTask>> = self.lock().tasks.values().cloned().collect();
+        for task in tasks {
+            let mut state = task.lock();
+            if state.channels.iter().any(|c| c.device == device.name()) {
+                state.run = RunState::Idle;
+                drop(state);
+                task.changed.notify_all();
+            }
+        }
+        0
+    }
+
+    fn create_lin_scale(
+        &self,
+        name: &CStr,
+        slope: f64,
+        y_intercept: f64,
+        _pre_scaled_units: i32,
+        scaled_units: &CStr,
+    ) -> i32 {
+        self.lock().scales.insert(
+            name.to_string_lossy().to_ascii_lowercase(),
+            Scale {
+                slope,
+                y_intercept,
+                units: scaled_units.to_string_lossy().into_owned(),
+            },
+        );
+        0
+    }
+
+    fn scale_scaled_units(&self, name: &CStr, units: &mut String) -> i32 {
+        let name = name.to_string_lossy();
+        match self.lock().scales.get(&name.to_ascii_lowercase()) {
+            Some(scale) => {
+                *units = scale.units.clone();
+                0
+            }
+            None => fail(
+                status::INVALID_ATTRIBUTE_VALUE,
+                None,
+                &format!("Custom Scale Name: {name}"),
+            ),
+        }
+    }
+
+    fn create_task(&self, name: &CStr, task: &mut TaskHandle) -> i32 {
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let name = match name.to_string_lossy() {
+            n if n.is_empty() => format!("_unnamedTask<{}>", id - 1),
+            n => n.into_owned(),
+        };
+        if state
+            .tasks
+            .values()
+            .any(|t| t.name.eq_ignore_ascii_case(&name))
+        {
+            return fail(status::DUPLICATE_TASK, Some(&name), "");
+        }
+
+        state.tasks.insert(id, Arc::new(SimTask::new(name)));
+        *task = std::ptr::without_provenance_mut(id);
+        0
+    }
+
+    unsafe fn clear_task(&self, task: TaskHandle) -> i32 {
+        let mut state = self.lock();
+        let in_own_event = state.tasks.get(&task.addr()).is_some_and(|task| {
+            IN_EVENT.with(|current| std::ptr::eq(current.get(), Arc::as_ptr(task)))
+        });
+        if in_own_event {
+            drop(state);
+            // NI-DAQmx doesn't support this, and it can deadlock.
+            panic!("A task was cleared inside one of its own event callbacks");
+        }
+        match state.tasks.remove(&task.addr()) {
+            Some(task) => {
+                task.lock().run = RunState::Idle;
+                task.changed.notify_all();
+                0
+            }
+            None => fail(status::INVALID_TASK, None, ""),
+        }
+    }
+
+    unsafe fn task_name(&self, task: TaskHandle, name: &mut String) -> i32 {
+        match self.task(task) {
+            Ok(task) => {
+                *name = task.name.clone();
+                0
+            }
+            Err(code) => code,
+        }
+    }
+
+    unsafe fn task_channels(&self, task: TaskHandle, channels: &mut String) -> i32 {
+        match self.task(task) {
+            Ok(task) => {
+                *channels = task
+                    .lock()
+                    .channels
+                    .iter()
+                    .map(|channel| channel.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                0
+            }
+            Err(code) => code,
+        }
+    }
+
+    unsafe fn start_task(&self, task: TaskHandle) -> i32 {
+        match self.task(task) {
+            Ok(task) => self.start(&task),
+            Err(code) => code,
+        }
+    }
+
+    unsafe fn stop_task(&self, task: TaskHandle) -> i32 {
+        match self.task(task) {
+            Ok(task) => {
+                task.lock().run = RunState::Idle;
+                task.changed.notify_all();
+                0
+            }
+            Err(code) => code,
+        }
+    }
+
+    unsafe fn task_control(&self, handle: TaskHandle, action: i32) -> i32 {
+        match action {
+            daqmx::DAQmx_Val_Task_Start => self.start_task(handle),
+            // Resources aren't modelled, so releasing them only stops the task.
+            daqmx::DAQmx_Val_Task_Stop
+            | daqmx::DAQmx_Val_Task_Abort
+            | daqmx::DAQmx_Val_Task_Unreserve => self.stop_task(handle),
+            daqmx::DAQmx_Val_Task_Verify
+            | daqmx::DAQmx_Val_Task_Commit
+            | daqmx::DAQmx_Val_Task_Reserve => match self.task(handle) {
+                Ok(task) if task.lock().channels.is_empty() => {
+                    fail(status::NO_CHANNELS, Some(&task.name), "")
+                }
+                Ok(_) => 0,
+                Err(code) => code,
+            },
+            _ => fail(
+                status::INVALID_ATTRIBUTE_VALUE,
+                None,
+                "Unknown task control action.",
+            ),
+        }
+    }
+
+    unsafe fn register_every_n_samples_event(
+        &self,
+        task: TaskHandle,
+        event_type: i32,
+        samples: u32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        self.configure(task, |state| match handler {
+            Some(_) if samples == 0 => fail(
+                status::INVALID_ATTRIBUTE_VALUE,
+                None,
+                "The number of samples of an Every N Samples event must be greater than 0.",
+            ),
+            Some(handler) => {
+                state.every_n_samples = Some(EveryNSamples {
+                    event_type,
+                    samples,
+                    handler: handler.clone(),
+                });
+                0
+            }
+            None => {
+                state.every_n_samples = None;
+                0
+            }
+        })
+    }
+
+    unsafe fn register_done_event(
+        &self,
+        task: TaskHandle,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        self.configure(task, |state| {
+            state.done_event = handler.cloned();
+            0
+        })
+    }
+
+    unsafe fn register_signal_event(
+        &self,
+        task: TaskHandle,
+        signal: i32,
+        handler: Option<&Arc<EventHandler>>,
+    ) -> i32 {
+        const SIGNALS: [i32; 4] = [
+            daqmx::DAQmx_Val_SampleClock,
+            daqmx::DAQmx_Val_SampleCompleteEvent,
+            daqmx::DAQmx_Val_ChangeDetectionEvent,
+            daqmx::DAQmx_Val_CounterOutputEvent,
+        ];
+        self.configure(task, |state| match handler {
+            Some(_) if !SIGNALS.contains(&signal) => fail(
+                status::INVALID_ATTRIBUTE_VALUE,
+                None,
+                &format!("Requested signal: {signal}"),
+            ),
+            Some(handler) => {
+                state.signal_event = Some((signal, handler.clone()));
+                0
+            }
+            None => {
+                state.signal_event = None;
+                0
+            }
+        })
+    }
+
+    unsafe fn wait_until_task_done(&self, task: TaskHandle, timeout: f64) -> i32 {
+        let task = match self.task(task) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let state = task.lock();
+        let (_state, done) = task.wait_until(state, timeout, |state, now| {
+            if state.is_done(now) {
+                Step::Ready(())
+            } else {
+                Step::Wait(state.time_until_done(now))
+            }
+        });
+        match done {
+            Some(()) => 0,
+            None => fail(status::WAIT_UNTIL_DONE_TIMEOUT, Some(&task.name), ""),
+        }
+    }
+
+    unsafe fn is_task_done(&self, task: TaskHandle, done: &mut bool) -> i32 {
+        match self.task(task) {
+            Ok(task) => {
+                let state = task.lock();
+                let now = Instant::now();
+                *done = state.is_done(now);
+                if state.underflowed(now) {
+                    return fail(
+                        status::GENERATION_STOPPED_TO_PREVENT_REGENERATION,
+                        Some(&task.name),
+                        "",
+                    );
+                }
+                0
+            }
+            Err(code) => code,
+        }
+    }
+
+    unsafe fn get_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        let task = match self.task(task) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let state = task.lock();
+        let result = match property {
+            TaskProperty::ReadAutoStart => Some(PropertyValue::Bool(state.read_auto_start)),
+            TaskProperty::ReadDigitalLinesBytesPerChannel
+            | TaskProperty::WriteDigitalLinesBytesPerChannel => {
+                Some(PropertyValue::U32(state.lines_per_sample() as u32))
+            }
+            TaskProperty::NumChannels => Some(PropertyValue::U32(state.channels.len() as u32)),
+            TaskProperty::ReadAvailableSamplesPerChannel => Some(PropertyValue::U32(
+                state
+                    .available(Instant::now())
+                    .try_into()
+                    .unwrap_or(u32::MAX),
+            )),
+            TaskProperty::SampleClockRate => match state.timing {
+                Timing::SampleClock { rate, .. } => Some(PropertyValue::F64(rate)),
+                _ => None,
+            },
+            TaskProperty::SampleMode => match state.timing {
+                Timing::SampleClock { finite, .. } | Timing::Implicit { finite, .. } => {
+                    Some(PropertyValue::I32(if finite {
+                        daqmx::DAQmx_Val_FiniteSamps
+                    } else {
+                        daqmx::DAQmx_Val_ContSamps
+                    }))
+                }
+                Timing::OnDemand => None,
+            },
+            TaskProperty::ReadCurrentReadPosition => Some(PropertyValue::U64(state.read_position)),
+            TaskProperty::ReadTotalSamplesPerChannelAcquired => {
+                Some(PropertyValue::U64(if state.is_on_demand() {
+                    state.read_position
+                } else {
+                    state.acquired(Instant::now())
+                }))
+            }
+            TaskProperty::WriteSpaceAvailable if !state.is_on_demand() => Some(PropertyValue::U32(
+                state
+                    .write_space(Instant::now())
+                    .try_into()
+                    .unwrap_or(u32::MAX),
+            )),
+            TaskProperty::WriteSpaceAvailable => None,
+            TaskProperty::OutputBufferSize => Some(PropertyValue::U32(
+                state.buffer_size().try_into().unwrap_or(u32::MAX),
+            )),
+            TaskProperty::WriteRegenMode => Some(PropertyValue::I32(if state.regenerate {
+                daqmx::DAQmx_Val_AllowRegen
+            } else {
+                daqmx::DAQmx_Val_DoNotAllowRegen
+            })),
+            TaskProperty::WriteRelativeTo => Some(PropertyValue::I32(state.write_relative_to)),
+            TaskProperty::WriteOffset => Some(PropertyValue::I32(state.write_offset)),
+            TaskProperty::InputBufferSize => Some(PropertyValue::U32(
+                state.buffer_size().try_into().unwrap_or(u32::MAX),
+            )),
+            TaskProperty::ReadOverwrite => Some(PropertyValue::I32(if state.read_overwrite {
+                daqmx::DAQmx_Val_OverwriteUnreadSamps
+            } else {
+                daqmx::DAQmx_Val_DoNotOverwriteUnreadSamps
+            })),
+            TaskProperty::ReadRelativeTo => Some(PropertyValue::I32(state.read_relative_to)),
+            TaskProperty::ReadOffset => Some(PropertyValue::I32(state.read_offset)),
+            TaskProperty::FirstSampleTimestampEnable => {
+                Some(PropertyValue::Bool(state.first_sample_timestamp))
+            }
+            TaskProperty::FirstSampleTimestamp => match state.run {
+                RunState::Running { started_at, .. } if state.first_sample_timestamp => {
+                    Some(PropertyValue::Timestamp(started_at))
+                }
+                _ => None,
+            },
+        };
+        match result {
+            Some(result) => {
+                *value = result;
+                0
+            }
+            None => fail(
+                status::PROPERTY_NOT_APPLICABLE,
+                Some(&task.name),
+                &format!("{property:?}"),
+            ),
+        }
+    }
+
+    unsafe fn set_task_property(
+        &self,
+        task: TaskHandle,
+        property: TaskProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        let task = match self.task(task) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let mut state = task.lock();
+        match (property, value) {
+            (TaskProperty::ReadAutoStart, PropertyValue::Bool(value)) => {
+                state.read_auto_start = *value;
+                0
+            }
+            (TaskProperty::FirstSampleTimestampEnable, PropertyValue::Bool(value)) => {
+                state.first_sample_timestamp = *value;
+                0
+            }
+            (
+                TaskProperty::ReadOverwrite,
+                PropertyValue::I32(
+                    value @ (daqmx::DAQmx_Val_OverwriteUnreadSamps
+                    | daqmx::DAQmx_Val_DoNotOverwriteUnreadSamps),
+                ),
+            ) => {
+                state.read_overwrite = *value == daqmx::DAQmx_Val_OverwriteUnreadSamps;
+                0
+            }
+            (
+                TaskProperty::ReadRelativeTo,
+                PropertyValue::I32(
+                    value @ (daqmx::DAQmx_Val_FirstSample
+                    | daqmx::DAQmx_Val_CurrReadPos
+                    | daqmx::DAQmx_Val_RefTrig
+                    | daqmx::DAQmx_Val_FirstPretrigSamp
+                    | daqmx::DAQmx_Val_MostRecentSamp),
+                ),
+            ) => {
+                state.read_relative_to = *value;
+                0
+            }
+            (TaskProperty::ReadOffset, PropertyValue::I32(value)) => {
+                state.read_offset = *value;
+                0
+            }
+            (
+                TaskProperty::WriteRegenMode,
+                PropertyValue::I32(
+                    value @ (daqmx::DAQmx_Val_AllowRegen | daqmx::DAQmx_Val_DoNotAllowRegen),
+                ),
+            ) => {
+                state.regenerate = *value == daqmx::DAQmx_Val_AllowRegen;
+                0
+            }
+            (
+                TaskProperty::WriteRelativeTo,
+                PropertyValue::I32(
+                    value @ (daqmx::DAQmx_Val_FirstSample | daqmx::DAQmx_Val_CurrWritePos),
+                ),
+            ) => {
+                state.write_relative_to = *value;
+                0
+            }
+            (TaskProperty::WriteOffset, PropertyValue::I32(value)) => {
+                state.write_offset = *value;
+                0
+            }
+            _ => fail(status::INVALID_ATTRIBUTE_VALUE, Some(&task.name), ""),
+        }
+    }
+
+    unsafe fn get_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &mut PropertyValue,
+    ) -> i32 {
+        let task = match self.task(task) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let name = Some(task.name.as_str());
+        let state = task.lock();
+        let channel_name = channel.to_string_lossy();
+        let found = if channel_name.is_empty() {
+            state.channels.first()
+        } else {
+            state
+                .channels
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(&channel_name))
+        };
+        let Some(channel) = found else {
+            return fail(
+                status::INVALID_CHANNEL,
+                name,
+                &format!("Channel Name: {channel_name}"),
+            );
+        };
+
+        let result = match (property, &channel.config) {
+            (ChannelProperty::PhysicalChannelName, _) => {
+                Some(PropertyValue::String(channel.physical.clone()))
+            }
+            (ChannelProperty::AIMax, ChannelConfig::Analog { max, .. }) => {
+                Some(PropertyValue::F64(*max))
+            }
+            (ChannelProperty::AIMin, ChannelConfig::Analog { min, .. }) => {
+                Some(PropertyValue::F64(*min))
+            }
+            (
+                ChannelProperty::AITerminalConfig,
+                ChannelConfig::Analog {
+                    terminal_config, ..
+                },
+            ) => Some(PropertyValue::I32(match *terminal_config {
+                // The default terminal configuration of an X Series device is RSE.
+                daqmx::DAQmx_Val_Cfg_Default => daqmx::DAQmx_Val_RSE,
+                other => other,
+            })),
+            (ChannelProperty::AICustomScaleName, ChannelConfig::Analog { scale_name, .. }) => {
+                Some(PropertyValue::String(scale_name.clone()))
+            }
+            (ChannelProperty::AIVoltageUnits, ChannelConfig::Analog { units, .. }) => {
+                Some(PropertyValue::I32(*units))
+            }
+            (ChannelProperty::COPulseTerminal, ChannelConfig::Pulse { terminal, .. }) => {
+                Some(PropertyValue::String(terminal.clone()))
+            }
+            (ChannelProperty::DONumLines, ChannelConfig::Digital { lines }) => {
+                Some(PropertyValue::U32(*lines))
+            }
+            _ => None,
+        };
+        match result {
+            Some(result) => {
+                *value = result;
+                0
+            }
+            None => fail(
+                status::PROPERTY_NOT_APPLICABLE,
+                name,
+                &format!("{property:?}"),
+            ),
+        }
+    }
+
+    unsafe fn set_channel_property(
+        &self,
+        task: TaskHandle,
+        channel: &CStr,
+        property: ChannelProperty,
+        value: &PropertyValue,
+    ) -> i32 {
+        let task = match self.task(task) {
+            Ok(task) => task,
+            Err(code) => return code,
+        };
+        let name = Some(task.name.as_str());
+        let mut state = task.lock();
+        let channel_name = channel.to_string_lossy();
+        let mut matched = false;
+        for channel in state
+            .channels
+            .iter_mut()
+            .filter(|c| channel_name.is_empty() || c.name.eq_ignore_ascii_case(&channel_name))
+        {
+            matched = true;
+            match (property, &mut channel.config, value) {
+                (
+                    ChannelProperty::COPulseTerminal,
+                    ChannelConfig::Pulse { terminal, .. },
+                    PropertyValue::String(value),
+                ) => *terminal = value.clone(),
+                _ => {
+                    return fail(
+                        status::PROPERTY_NOT_APPLICABLE,
+                        name,
+                        &format!("{property:?}"),
+                    )
+                }
+            }
+        }
+        if !matched {
+            return fail(
+                status::INVALID_CHANNEL,
+                name,
+                &format!("Channel Name: {channel_name}"),
+            );
+        }
+        0
+    }
+
+    unsafe fn create_ai_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        terminal_config: i32,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        let scale_name = custom_scale_name.to_string_lossy().into_owned();
+        let scale = if units == daqmx::DAQmx_Val_FromCustomScale {
+            match self.lock().scales.get(&scale_name.to_ascii_lowercase()) {
+                Some(scale) => Some((scale.slope, scale.y_intercept)),
+                None => {
+                    return fail(
+                        status::INVALID_ATTRIBUTE_VALUE,
+                        None,
+                        &format!("Custom Scale Name: {scale_name}"),
+                    )
+                }
+            }
+        } else {
+            None
+        };
+        self.add_channels(
+            task,
+            ChannelType::AI,
+            physical_channel,
+            name,
+            false,
+            ChannelConfig::Analog {
+                min,
+                max,
+                terminal_config,
+                units,
+                scale_name,
+                scale,
+            },
+        )
+    }
+
+    unsafe fn create_ao_voltage_chan(
+        &self,
+        task: TaskHandle,
+        physical_channel: &CStr,
+        name: &CStr,
+        min: f64,
+        max: f64,
+        units: i32,
+        custom_scale_name: &CStr,
+    ) -> i32 {
+        self.add_channels(
+            task,
+            ChannelType::AO,
+            physical_channel,
+            name,
+            false,
+            ChannelConfig::Analog {
+                min,
+                max,
+                terminal_config: daqmx::DAQmx_Val_Cfg_Default,
+                units,
+                scale_name: custom_scale_name.to_string_lossy().into_owned(),
+                scale: None,
+            },
+        )
+    }
+
+    unsafe fn create_di_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        self.add_channels(
+            task,
+            ChannelType::DI,
+            lines,
+            name,
+            line_grouping != daqmx::DAQmx_Val_ChanForAllLines,
+            ChannelConfig::Digital { lines: 1 },
+        )
+    }
+
+    unsafe fn create_do_chan(
+        &self,
+        task: TaskHandle,
+        lines: &CStr,
+        name: &CStr,
+        line_grouping: i32,
+    ) -> i32 {
+        self.add_channels(
+            task,
+            ChannelType::DO,
+            lines,
+            name,
+            line_grouping != daqmx::DAQmx_Val_ChanForAllLines,
+            ChannelConfig::Digital { lines: 1 },
+        )
+    }
+
+    unsafe fn create_ci_count_edges_chan(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        _edge: i32,
+        initial_count: u32,
+        count_direction: i32,
+    ) -> i32 {
+        self.add_channels(
+            task,
+            ChannelType::CI,
+            counter,
+            name,
+            false,
+            ChannelConfig::CountEdges {
+                initial_count,
+                count_direction,
+            },
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_time(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        _units: i32,
+        _idle_state: i32,
+        initial_delay: f64,
+        low_time: f64,
+        high_time: f64,
+    ) -> i32 {
+        self.add_channels(
+            task,
+            ChannelType::CO,
+            counter,
+            name,
+            false,
+            ChannelConfig::Pulse {
+                initial_delay,
+                low_time,
+                high_time,
+                terminal: String::new(),
+            },
+        )
+    }
+
+    unsafe fn create_co_pulse_chan_freq(
+        &self,
+        task: TaskHandle,
+        counter: &CStr,
+        name: &CStr,
+        _units: i32,
+        _idle_state: i32,
+        initial_delay: f64,
+        frequency: f64,
+        duty_cycle: f64,
+    ) -> i32 {
+        if frequency <= 0.0 {
+            return fail(
+                status::INVALID_ATTRIBUTE_VALUE,
+                None,
+                "Frequency must be positive.",
+            );
+        }
+        self.add_channels(
+            task,
+            ChannelType::CO,
+            counter,
+            name,
+            false,
+            ChannelConfig::Pulse {
+                initial_delay,
+                low_time: (1.0 - duty_cycle) / frequency,
+                high_time: duty_cycle / frequency,
+                terminal: String::new(),
+            },
+        )
+    }
+
+    unsafe fn cfg_samp_clk_timing(
+        &self,
+        task: TaskHandle,
+        _source: Option<&CStr>,
+        rate: f64,
+        _active_edge: i32,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        self.configure(task, |state| {
+            if rate <= 0.0 || !rate.is_finite() {
+                return fail(
+                    status::INVALID_ATTRIBUTE_VALUE,
+                    None,
+                    &format!("Sample Clock Rate: {rate}"),
+                );
+            }
+            state.timing = Timing::SampleClock {
+                rate,
+                finite: sample_mode == daqmx::DAQmx_Val_FiniteSamps,
+                samples: samples_per_channel,
+            };
+            0
+        })
+    }
+
+    unsafe fn cfg_implicit_timing(
+        &self,
+        task: TaskHandle,
+        sample_mode: i32,
+        samples_per_channel: u64,
+    ) -> i32 {
+        self.configure(task, |state| {
+            state.timing = Timing::Implicit {
+                finite: sample_mode == daqmx::DAQmx_Val_FiniteSamps,
+                samples: samples_per_channel,
+            };
+            0
+        })
+    }
+
+    unsafe fn cfg_input_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32 {
+        self.configure(task, |state| {
+            state.input_buffer = Some(samples_per_channel.into());
+            0
+        })
+    }
+
+    unsafe fn cfg_output_buffer(&self, task: TaskHandle, samples_per_channel: u32) -> i32 {
+        self.configure(task, |state| {
+            state.output_buffer = Some(samples_per_channel.into());
+            0
+        })
+    }
+
+    unsafe fn cfg_dig_edge_start_trig(&self, task: TaskHandle, source: &CStr, _edge: i32) -> i32 {
+        self.configure(task, |state| {
+            state.start_trigger = Some(terminal_key(&source.to_string_lossy()));
+            0
+        })
+    }
+
+    unsafe fn cfg_dig_edge_ref_trig(
+        &self,
+        task: TaskHandle,
+        source: &CStr,
+        _edge: i32,
+        _pretrigger_samples: u32,
+    ) -> i32 {
+        self.configure(task, |state| {
+            state.reference_trigger = Some(terminal_key(&source.to_string_lossy()));
+            0
+        })
+    }
+
+    unsafe fn disable_start_trig(&self, task: TaskHandle) -> i32 {
+        self.configure(task, |state| {
+            state.start_trigger = None;
+            0
+        })
+    }
+
+    unsafe fn export_signal(&self, task: TaskHandle, signal: i32, terminal: &CStr) -> i32 {
+        self.configure(task, |state| {
+            state.exports.retain(|(s, _)| *s != signal);
+            state
+                .exports
+                .push((signal, terminal_key(&terminal.to_string_lossy())));
+            0
+        })
+    }
+
+    unsafe fn read_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        let by_scan = fill_mode as i32 == daqmx::DAQmx_Val_GroupByScanNumber;
+        let buffer_len = buffer.len();
+        self.read(
+            task,
+            &[ChannelType::AI],
+            samples_per_channel,
+            timeout,
+            buffer_len,
+            |_| 1,
+            |state, first, count| state.fill_analog(first, count, by_scan, buffer),
+            samples_read,
+        )
+    }
+
+    unsafe fn read_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        let mut read = 0;
+        self.read(
+            task,
+            &[ChannelType::AI],
+            1,
+            timeout,
+            usize::MAX,
+            |_| 1,
+            |state, first, _| *value = state.analog_value(0, first),
+            &mut read,
+        )
+    }
+
+    unsafe fn read_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: bool32,
+        buffer: &mut [u8],
+        samples_read: &mut i32,
+        bytes_per_sample: &mut i32,
+    ) -> i32 {
+        let by_scan = fill_mode as i32 == daqmx::DAQmx_Val_GroupByScanNumber;
+        let buffer_len = buffer.len();
+        self.read(
+            task,
+            &[ChannelType::DI],
+            samples_per_channel,
+            timeout,
+            buffer_len,
+            |state| {
+                *bytes_per_sample = state.lines_per_sample() as i32;
+                state.lines_per_sample()
+            },
+            |state, first, count| state.fill_digital(first, count, by_scan, buffer),
+            samples_read,
+        )
+    }
+
+    unsafe fn read_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        let mut read = 0;
+        self.read(
+            task,
+            &[ChannelType::DI],
+            1,
+            timeout,
+            usize::MAX,
+            |_| 1,
+            |state, first, _| {
+                let lines = state.lines_per_sample().min(32);
+                *value = (0..lines)
+                    .map(|l| u32::from(state.digital_line(0, l, first)) << l)
+                    .sum();
+            },
+            &mut read,
+        )
+    }
+
+    unsafe fn read_counter_scalar_u32(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut u32,
+    ) -> i32 {
+        let mut read = 0;
+        self.read(
+            task,
+            &[ChannelType::CI],
+            1,
+            timeout,
+            usize::MAX,
+            |_| 1,
+            |state, _, _| *value = state.count(Instant::now()),
+            &mut read,
+        )
+    }
+
+    unsafe fn read_counter_scalar_f64(
+        &self,
+        task: TaskHandle,
+        timeout: f64,
+        value: &mut f64,
+    ) -> i32 {
+        let mut read = 0;
+        // Period of the simulated 1 kHz input.
+        self.read(
+            task,
+            &[ChannelType::CI],
+            1,
+            timeout,
+            usize::MAX,
+            |_| 1,
+            |_, _, _| *value = 0.001,
+            &mut read,
+        )
+    }
+
+    unsafe fn read_counter_u32(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [u32],
+        samples_read: &mut i32,
+    ) -> i32 {
+        let buffer_len = buffer.len();
+        self.read(
+            task,
+            &[ChannelType::CI],
+            samples_per_channel,
+            timeout,
+            buffer_len,
+            |_| 1,
+            |state, first, count| state.fill_counts(first, count, buffer),
+            samples_read,
+        )
+    }
+
+    unsafe fn read_counter_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        timeout: f64,
+        buffer: &mut [f64],
+        samples_read: &mut i32,
+    ) -> i32 {
+        let buffer_len = buffer.len();
+        self.read(
+            task,
+            &[ChannelType::CI],
+            samples_per_channel,
+            timeout,
+            buffer_len,
+            |_| 1,
+            |_, _, count| buffer[..count].fill(0.001),
+            samples_read,
+        )
+    }
+
+    unsafe fn write_analog_f64(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        _fill_mode: bool32,
+        buffer: &[f64],
+        samples_written: &mut i32,
+    ) -> i32 {
+        self.write(
+            task,
+            ChannelType::AO,
+            samples_per_channel,
+            auto_start,
+            timeout,
+            buffer.len(),
+            samples_written,
+        )
+    }
+
+    unsafe fn write_analog_scalar_f64(
+        &self,
+        task: TaskHandle,
+        _auto_start: bool,
+        _timeout: f64,
+        _value: f64,
+    ) -> i32 {
+        self.write_scalar(task, ChannelType::AO)
+    }
+
+    unsafe fn write_digital_lines(
+        &self,
+        task: TaskHandle,
+        samples_per_channel: i32,
+        auto_start: bool,
+        timeout: f64,
+        _fill_mode: bool32,
+        buffer: &[u8],
+        samples_written: &mut i32,
+    ) -> i32 {
+        self.write(
+            task,
+            ChannelType::DO,
+            samples_per_channel,
+            auto_start,
+            timeout,
+            buffer.len(),
+            samples_written,
+        )
+    }
+
+    unsafe fn write_digital_scalar_u32(
+        &self,
+        task: TaskHandle,
+        _auto_start: bool,
+        _timeout: f64,
+        _value: u32,
+    ) -> i32 {
+        self.write_scalar(task, ChannelType::DO)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::CString;
+
+    fn backend() -> SimulatedBackend {
+        SimulatedBackend::new().with_device(SimulatedDevice::x_series("Dev1"))
+    }
+
+    fn create_task(backend: &SimulatedBackend, name: &str) -> TaskHandle {
+        let mut handle = std::ptr::null_mut();
+        let name = CString::new(name).unwrap();
+        assert_eq!(backend.create_task(&name, &mut handle), 0);
+        handle
+    }
+
+    #[test]
+    fn test_unnamed_tasks_get_unique_names() {
+        let backend = backend();
+        let first = create_task(&backend, "");
+        let second = create_task(&backend, "");
+        let (mut a, mut b) = (String::new(), String::new());
+        unsafe {
+            backend.task_name(first, &mut a);
+            backend.task_name(second, &mut b);
+        }
+        assert!(!a.is_empty());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_duplicate_task_name() {
+        let backend = backend();
+        create_task(&backend, "task");
+        let mut handle = std::ptr::null_mut();
+        let code = backend.create_task(&CString::new("task").unwrap(), &mut handle);
+        assert_eq!(code, status::DUPLICATE_TASK);
+        assert!(backend.extended_error_info().contains("task"));
+    }
+
+    #[test]
+    fn test_invalid_physical_channel() {
+        let backend = backend();
+        let task = create_task(&backend, "");
+        let empty = CString::default();
+        let code = unsafe {
+            backend.create_do_chan(task, &CString::new("Dev1/port9/line0").unwrap(), &empty, 1)
+        };
+        assert_eq!(code, status::PHYSICAL_CHANNEL_DOES_NOT_EXIST);
+        let code = unsafe {
+            backend.create_do_chan(task, &CString::new("Dev2/port0/line0").unwrap(), &empty, 1)
+        };
+        assert_eq!(code, status::INVALID_DEVICE_ID);
+    }
+
+    #[test]
+    fn test_start_trigger_releases_armed_task() {
+        let backend = backend();
+        let empty = CString::default();
+        let waiting = create_task(&backend, "");
+        let source = create_task(&backend, "");
+        unsafe {
+            backend.create_di_chan(
+                waiting,
+                &CString::new("Dev1/port0/line0").unwrap(),
+                &empty,
+                1,
+            );
+            backend.cfg_dig_edge_start_trig(
+                waiting,
+                &CString::new("/Dev1/ai/StartTrigger").unwrap(),
+                daqmx::DAQmx_Val_Rising,
+            );
+            backend.create_ai_voltage_chan(
+                source,
+                &CString::new("Dev1/ai0").unwrap(),
+                &empty,
+                daqmx::DAQmx_Val_Cfg_Default,
+                -5.0,
+                5.0,
+                daqmx::DAQmx_Val_Volts,
+                &empty,
+            );
+
+            assert_eq!(backend.start_task(waiting), 0);
+            let mut done = true;
+            backend.is_task_done(waiting, &mut done);
+            assert!(!done);
+
+            assert_eq!(backend.start_task(source), 0);
+            let task = backend.task(waiting).unwrap();
+            assert!(matches!(task.lock().run, RunState::Running { .. }));
+        }
+    }
+}
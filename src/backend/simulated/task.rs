@@ -0,0 +1,579 @@
+//! State of a simulated task.
+//!
+//! Acquisition and generation are modelled from the wall clock: a running task with a sample
+//! clock has produced `elapsed * rate` samples, so reads block and time out as they would
+//! against hardware without a thread generating data in the background.
+
+use std::f64::consts::TAU;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::device::terminal_key;
+use crate::backend::EventHandler;
+use crate::daqmx;
+use crate::utils::info::ChannelType;
+
+pub(super) struct SimTask {
+    pub(super) name: String,
+    state: Mutex<TaskState>,
+    /// Notified whenever the run state changes.
+    pub(super) changed: Condvar,
+}
+
+impl SimTask {
+    pub(super) fn new(name: String) -> Self {
+        Self {
+            name,
+            state: Mutex::new(TaskState::new()),
+            changed: Condvar::new(),
+        }
+    }
+
+    pub(super) fn lock(&self) -> MutexGuard<'_, TaskState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Waits until `check` is ready or `timeout` seconds (negative for forever) have elapsed,
+    /// in which case [`None`] is returned.
+    pub(super) fn wait_until<'a, R>(
+        &'a self,
+        mut state: MutexGuard<'a, TaskState>,
+        timeout: f64,
+        mut check: impl FnMut(&TaskState, Instant) -> Step<R>,
+    ) -> (MutexGuard<'a, TaskState>, Option<R>) {
+        let deadline = (timeout >= 0.0).then(|| Instant::now() + Duration::from_secs_f64(timeout));
+        loop {
+            let now = Instant::now();
+            let hint = match check(&state, now) {
+                Step::Ready(result) => return (state, Some(result)),
+                Step::Wait(hint) => hint,
+            };
+            let remaining = match deadline {
+                Some(deadline) if deadline <= now => return (state, None),
+                Some(deadline) => Some(deadline - now),
+                None => None,
+            };
+            state = match (hint, remaining) {
+                (None, None) => self.changed.wait(state).unwrap_or_else(|e| e.into_inner()),
+                (a, b) => {
+                    let wait = a.unwrap_or(Duration::MAX).min(b.unwrap_or(Duration::MAX));
+                    self.changed
+                        .wait_timeout(state, wait)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+            };
+        }
+    }
+}
+
+pub(super) enum Step<R> {
+    Ready(R),
+    /// Not ready yet. Holds how long until it will be, if that is known.
+    Wait(Option<Duration>),
+}
+
+#[derive(Clone, Debug)]
+pub(super) enum ChannelConfig {
+    Analog {
+        min: f64,
+        max: f64,
+        terminal_config: i32,
+        units: i32,
+        scale_name: String,
+        /// Slope and intercept of a linear custom scale.
+        scale: Option<(f64, f64)>,
+    },
+    Digital {
+        lines: u32,
+    },
+    CountEdges {
+        initial_count: u32,
+        count_direction: i32,
+    },
+    Pulse {
+        initial_delay: f64,
+        low_time: f64,
+        high_time: f64,
+        terminal: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct SimChannel {
+    pub(super) name: String,
+    pub(super) physical: String,
+    pub(super) device: String,
+    pub(super) config: ChannelConfig,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Timing {
+    OnDemand,
+    SampleClock {
+        rate: f64,
+        finite: bool,
+        samples: u64,
+    },
+    Implicit {
+        finite: bool,
+        samples: u64,
+    },
+}
+
+/// A registered Every N Samples event.
+#[derive(Clone)]
+pub(super) struct EveryNSamples {
+    pub(super) event_type: i32,
+    pub(super) samples: u32,
+    pub(super) handler: Arc<EventHandler>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum RunState {
+    Idle,
+    /// Started and waiting for the start trigger.
+    Armed,
+    Running {
+        started: Instant,
+        /// The wall clock time of `started`, for timestamps.
+        started_at: SystemTime,
+    },
+}
+
+pub(super) struct TaskState {
+    pub(super) kind: Option<ChannelType>,
+    pub(super) channels: Vec<SimChannel>,
+    pub(super) timing: Timing,
+    pub(super) start_trigger: Option<String>,
+    pub(super) reference_trigger: Option<String>,
+    /// Exported signals and the terminal key they are routed to.
+    pub(super) exports: Vec<(i32, String)>,
+    pub(super) read_auto_start: bool,
+    /// Whether the first sample of each run is timestamped.
+    pub(super) first_sample_timestamp: bool,
+    pub(super) run: RunState,
+    /// Samples per channel read since the task started.
+    pub(super) read_position: u64,
+    /// The input buffer size set with `DAQmxCfgInputBuffer`, overriding the default.
+    pub(super) input_buffer: Option<u64>,
+    /// Whether a continuous acquisition overwrites unread samples rather than failing.
+    pub(super) read_overwrite: bool,
+    /// The `DAQmx_Val_*` position reads start from, and the offset from it.
+    pub(super) read_relative_to: i32,
+    pub(super) read_offset: i32,
+    /// Samples per channel written to the output buffer.
+    pub(super) written: u64,
+    /// The output buffer size set with `DAQmxCfgOutputBuffer`, overriding the default.
+    pub(super) output_buffer: Option<u64>,
+    /// Whether an output generates its buffer again rather than stopping when it runs out.
+    pub(super) regenerate: bool,
+    /// The `DAQmx_Val_*` position writes start from, and the offset from it.
+    pub(super) write_relative_to: i32,
+    pub(super) write_offset: i32,
+    pub(super) every_n_samples: Option<EveryNSamples>,
+    pub(super) done_event: Option<Arc<EventHandler>>,
+    /// The registered signal and its handler.
+    pub(super) signal_event: Option<(i32, Arc<EventHandler>)>,
+    /// Incremented on every start, so event threads of an earlier run can tell they are stale.
+    pub(super) starts: u64,
+    created: Instant,
+}
+
+impl TaskState {
+    fn new() -> Self {
+        Self {
+            kind: None,
+            channels: Vec::new(),
+            timing: Timing::OnDemand,
+            start_trigger: None,
+            reference_trigger: None,
+            exports: Vec::new(),
+            read_auto_start: true,
+            first_sample_timestamp: false,
+            run: RunState::Idle,
+            read_position: 0,
+            input_buffer: None,
+            read_overwrite: false,
+            read_relative_to: daqmx::DAQmx_Val_CurrReadPos,
+            read_offset: 0,
+            written: 0,
+            output_buffer: None,
+            regenerate: true,
+            write_relative_to: daqmx::DAQmx_Val_CurrWritePos,
+            write_offset: 0,
+            every_n_samples: None,
+            done_event: None,
+            signal_event: None,
+            starts: 0,
+            created: Instant::now(),
+        }
+    }
+
+    pub(super) fn is_idle(&self) -> bool {
+        self.run == RunState::Idle
+    }
+
+    fn is_output(&self) -> bool {
+        matches!(
+            self.kind,
+            Some(ChannelType::AO | ChannelType::DO | ChannelType::CO)
+        )
+    }
+
+    /// Whether samples are taken on request rather than by a clock.
+    pub(super) fn is_on_demand(&self) -> bool {
+        self.rate().is_none()
+    }
+
+    fn pulse(&self) -> Option<(f64, f64)> {
+        self.channels.iter().find_map(|c| match c.config {
+            ChannelConfig::Pulse {
+                initial_delay,
+                low_time,
+                high_time,
+                ..
+            } => Some((initial_delay, low_time + high_time)),
+            _ => None,
+        })
+    }
+
+    /// The sample (or pulse) rate.
+    pub(super) fn rate(&self) -> Option<f64> {
+        match self.timing {
+            Timing::SampleClock { rate, .. } => Some(rate),
+            _ => self.pulse().map(|(_, period)| 1.0 / period),
+        }
+    }
+
+    /// Samples per channel of a finite task.
+    pub(super) fn total(&self) -> Option<u64> {
+        match self.timing {
+            Timing::SampleClock {
+                finite: true,
+                samples,
+                ..
+            }
+            | Timing::Implicit {
+                finite: true,
+                samples,
+            } => Some(samples),
+            // Counter output without timing generates a single pulse.
+            Timing::OnDemand if self.kind == Some(ChannelType::CO) => Some(1),
+            _ => None,
+        }
+    }
+
+    /// The size of the buffer of a continuous task, following the DAQmx defaults for small requests
+    /// unless the input or output buffer has been configured.
+    pub(super) fn buffer_size(&self) -> u64 {
+        if let Some(total) = self.total() {
+            return total;
+        }
+        let configured = if self.is_output() {
+            self.output_buffer
+        } else {
+            self.input_buffer
+        };
+        if let Some(size) = configured {
+            return size;
+        }
+        let requested = match self.timing {
+            Timing::SampleClock { samples, .. } | Timing::Implicit { samples, .. } => samples,
+            Timing::OnDemand => 0,
+        };
+        let minimum = match self.rate().unwrap_or(0.0) {
+            r if r <= 100.0 => 1_000,
+            r if r <= 10_000.0 => 10_000,
+            r if r <= 1_000_000.0 => 100_000,
+            _ => 1_000_000,
+        };
+        requested.max(minimum)
+    }
+
+    fn start_offset(&self) -> f64 {
+        self.pulse().map_or(0.0, |(delay, _)| delay)
+    }
+
+    /// Samples per channel produced since the start, capped for finite tasks.
+    pub(super) fn acquired(&self, now: Instant) -> u64 {
+        let RunState::Running { started, .. } = self.run else {
+            return 0;
+        };
+        let Some(rate) = self.rate() else {
+            return u64::MAX;
+        };
+        let elapsed = now.saturating_duration_since(started).as_secs_f64() - self.start_offset();
+        let acquired = (elapsed.max(0.0) * rate) as u64;
+        self.total().map_or(acquired, |total| acquired.min(total))
+    }
+
+    /// Time until `samples` samples per channel have been acquired, if the task is running.
+    pub(super) fn time_until(&self, now: Instant, samples: u64) -> Option<Duration> {
+        let RunState::Running { started, .. } = self.run else {
+            return None;
+        };
+        let rate = self.rate()?;
+        let at = started
+            + Duration::from_secs_f64(self.start_offset() + samples as f64 / rate)
+            + Duration::from_micros(1);
+        Some(at.saturating_duration_since(now))
+    }
+
+    /// Samples per channel which fit in the output buffer without overwriting samples not yet
+    /// generated.
+    pub(super) fn write_space(&self, now: Instant) -> u64 {
+        let pending = self.written.saturating_sub(self.acquired(now));
+        self.buffer_size().saturating_sub(pending)
+    }
+
+    /// Whether a continuous output which doesn't regenerate has generated every sample written,
+    /// which stops the generation.
+    pub(super) fn underflowed(&self, now: Instant) -> bool {
+        !self.regenerate
+            && self.is_output()
+            && self.total().is_none()
+            && !self.is_on_demand()
+            && matches!(self.run, RunState::Running { .. })
+            && self.acquired(now) > self.written
+    }
+
+    /// Whether writes of a running continuous output wait for space in the buffer. Writes
+    /// relative to the first sample replace the buffer instead, which isn't tracked.
+    pub(super) fn write_waits_for_space(&self) -> bool {
+        self.total().is_none()
+            && !self.is_on_demand()
+            && matches!(self.run, RunState::Running { .. })
+            && self.write_relative_to == daqmx::DAQmx_Val_CurrWritePos
+    }
+
+    /// Samples acquired but not yet read. Unread samples which have been overwritten no longer
+    /// count.
+    pub(super) fn available(&self, now: Instant) -> u64 {
+        let available = self.acquired(now).saturating_sub(self.read_position);
+        if self.overwrites() {
+            available.min(self.buffer_size())
+        } else {
+            available
+        }
+    }
+
+    /// Whether a continuous acquisition overwrites unread samples when the buffer is full.
+    fn overwrites(&self) -> bool {
+        self.read_overwrite && self.total().is_none() && !self.is_on_demand()
+    }
+
+    /// Whether unread samples of a continuous acquisition have been overwritten, when that is an
+    /// error.
+    pub(super) fn overflowed(&self, now: Instant) -> bool {
+        self.total().is_none()
+            && !self.is_on_demand()
+            && !self.read_overwrite
+            && self.available(now) > self.buffer_size()
+    }
+
+    /// The sample the next read starts from, following the read position and offset. `None` if it
+    /// would be before the first sample. The reference trigger isn't simulated, so positions
+    /// relative to it are relative to the first sample.
+    pub(super) fn read_start(&self, now: Instant) -> Option<u64> {
+        let base = match self.read_relative_to {
+            daqmx::DAQmx_Val_CurrReadPos => self.read_position,
+            daqmx::DAQmx_Val_MostRecentSamp if !self.is_on_demand() => self.acquired(now),
+            daqmx::DAQmx_Val_MostRecentSamp => self.read_position,
+            _ => 0,
+        };
+        base.checked_add_signed(self.read_offset.into())
+    }
+
+    /// Whether sample `position` has been overwritten by newer samples.
+    pub(super) fn overwritten(&self, now: Instant, position: u64) -> bool {
+        self.overwrites() && position + self.buffer_size() < self.acquired(now)
+    }
+
+    pub(super) fn is_done(&self, now: Instant) -> bool {
+        match self.run {
+            RunState::Idle => true,
+            RunState::Armed => false,
+            RunState::Running { .. } => match self.total() {
+                Some(total) => self.acquired(now) >= total,
+                None => false,
+            },
+        }
+    }
+
+    pub(super) fn time_until_done(&self, now: Instant) -> Option<Duration> {
+        self.time_until(now, self.total()?)
+    }
+
+    /// Whether the signal occurs with every sample of the task. Change detection isn't simulated.
+    pub(super) fn has_signal(&self, signal: i32) -> bool {
+        match signal {
+            daqmx::DAQmx_Val_SampleClock | daqmx::DAQmx_Val_SampleCompleteEvent => {
+                matches!(self.timing, Timing::SampleClock { .. })
+            }
+            daqmx::DAQmx_Val_CounterOutputEvent => self.kind == Some(ChannelType::CO),
+            _ => false,
+        }
+    }
+
+    /// The terminals which see an edge when the task starts running.
+    pub(super) fn start_edges(&self) -> Vec<String> {
+        let mut edges = Vec::new();
+        let kind = match self.kind {
+            Some(ChannelType::AI) => Some("ai"),
+            Some(ChannelType::AO) => Some("ao"),
+            Some(ChannelType::DI) => Some("di"),
+            Some(ChannelType::DO) => Some("do"),
+            _ => None,
+        };
+        for channel in &self.channels {
+            if let Some(kind) = kind {
+                edges.push(terminal_key(&format!(
+                    "{}/{kind}/StartTrigger",
+                    channel.device
+                )));
+            }
+            if let ChannelConfig::Pulse { terminal, .. } = &channel.config {
+                let counter = channel.physical.rsplit('/').next().unwrap_or_default();
+                edges.push(terminal_key(&format!(
+                    "{}/{counter}InternalOutput",
+                    channel.device
+                )));
+                if !terminal.is_empty() {
+                    edges.push(terminal_key(terminal));
+                }
+            }
+        }
+        edges.extend(
+            self.exports
+                .iter()
+                .filter(|(signal, _)| {
+                    matches!(
+                        *signal,
+                        daqmx::DAQmx_Val_StartTrigger
+                            | daqmx::DAQmx_Val_SampleClock
+                            | daqmx::DAQmx_Val_CounterOutputEvent
+                    )
+                })
+                .map(|(_, terminal)| terminal.clone()),
+        );
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+
+    /// Bytes per sample in digital reads and writes: the line count of the widest channel.
+    pub(super) fn lines_per_sample(&self) -> usize {
+        self.channels
+            .iter()
+            .map(|c| match c.config {
+                ChannelConfig::Digital { lines } => lines as usize,
+                _ => 1,
+            })
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Time of sample `index` in seconds from the start of the acquisition.
+    fn sample_time(&self, index: u64) -> f64 {
+        match self.rate() {
+            Some(rate) => index as f64 / rate,
+            // On demand samples follow the time they are read.
+            None => self.created.elapsed().as_secs_f64(),
+        }
+    }
+
+    /// The synthetic analog signal: a 10 Hz sine between a quarter and three quarters of the
+    /// channel range, phase shifted per channel, passed through the custom scale if any.
+    pub(super) fn analog_value(&self, channel: usize, index: u64) -> f64 {
+        let ChannelConfig::Analog { max, scale, .. } = self.channels[channel].config else {
+            return 0.0;
+        };
+        let phase = channel as f64 * TAU / 8.0;
+        let volts = max * (0.5 + 0.25 * (TAU * 10.0 * self.sample_time(index) + phase).sin());
+        match scale {
+            Some((slope, intercept)) => slope * volts + intercept,
+            None => volts,
+        }
+    }
+
+    /// The synthetic digital signal: line `l` toggles every `2^l` samples.
+    pub(super) fn digital_line(&self, channel: usize, line: usize, index: u64) -> u8 {
+        (((index + channel as u64) >> line) & 1) as u8
+    }
+
+    /// Edges counted by a counter input, simulating a 1 kHz input signal.
+    pub(super) fn count(&self, now: Instant) -> u32 {
+        match self.run {
+            RunState::Running { started, .. } => {
+                self.count_after(now.duration_since(started).as_secs_f64())
+            }
+            _ => self.count_after(0.0),
+        }
+    }
+
+    /// The count `seconds` after the start.
+    fn count_after(&self, seconds: f64) -> u32 {
+        let Some(ChannelConfig::CountEdges {
+            initial_count,
+            count_direction,
+        }) = self.channels.first().map(|c| &c.config)
+        else {
+            return 0;
+        };
+        let edges = (seconds * 1000.0) as u32;
+        if *count_direction == daqmx::DAQmx_Val_CountDown {
+            initial_count.wrapping_sub(edges)
+        } else {
+            initial_count.wrapping_add(edges)
+        }
+    }
+
+    /// Writes the counts latched by `count` sample clock ticks starting at `first` into `buffer`.
+    pub(super) fn fill_counts(&self, first: u64, count: usize, buffer: &mut [u32]) {
+        for (k, value) in buffer[..count].iter_mut().enumerate() {
+            *value = self.count_after(self.sample_time(first + k as u64));
+        }
+    }
+
+    /// Writes `count` samples starting at `first` into `buffer` using the DAQmx data layout.
+    pub(super) fn fill_analog(&self, first: u64, count: usize, by_scan: bool, buffer: &mut [f64]) {
+        let channels = self.channels.len();
+        for c in 0..channels {
+            for k in 0..count {
+                buffer[layout_index(by_scan, c, k, channels, count)] =
+                    self.analog_value(c, first + k as u64);
+            }
+        }
+    }
+
+    pub(super) fn fill_digital(&self, first: u64, count: usize, by_scan: bool, buffer: &mut [u8]) {
+        let channels = self.channels.len();
+        let width = self.lines_per_sample();
+        for (c, channel) in self.channels.iter().enumerate() {
+            let lines = match channel.config {
+                ChannelConfig::Digital { lines } => lines as usize,
+                _ => 1,
+            };
+            for k in 0..count {
+                let base = layout_index(by_scan, c, k, channels, count) * width;
+                for l in 0..width {
+                    buffer[base + l] = if l < lines {
+                        self.digital_line(c, l, first + k as u64)
+                    } else {
+                        0
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Index of sample `k` of channel `c` in an interleaved (by scan) or grouped (by channel) buffer.
+fn layout_index(by_scan: bool, c: usize, k: usize, channels: usize, count: usize) -> usize {
+    if by_scan {
+        k * channels + c
+    } else {
+        c * count + k
+    }
+}
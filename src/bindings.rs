@@ -0,0 +1,528 @@
+// Hand-maintained subset of the NI-DAQmx C API, written the way bindgen declares it.
+// Pinned to NIDAQmx.h from NI-DAQmx 23.8.
+//
+// Only the types, constants and functions used by this crate are kept, so the file stays
+// reviewable. It is included instead of the bindgen output when the `prebuilt-bindings` feature
+// is enabled.
+//
+// To add a symbol, copy its declaration from the bindgen output of a default build
+// (`OUT_DIR/bindings.rs`) into this file. Functions also need an entry in the runtime-loading list
+// in `src/daqmx/mod.rs`.
+//
+// `DAQMX_REGENERATE_BINDINGS=1 cargo build` without the `prebuilt-bindings` feature regenerates
+// this file from the installed driver, keeping this header and only the items declared below, and
+// updates the pinned version above.
+
+pub type int32 = ::std::os::raw::c_int;
+pub type uInt8 = ::std::os::raw::c_uchar;
+pub type uInt32 = ::std::os::raw::c_uint;
+pub type uInt64 = ::std::os::raw::c_ulonglong;
+pub type int64 = ::std::os::raw::c_longlong;
+pub type float64 = f64;
+pub type bool32 = uInt32;
+pub type TaskHandle = *mut ::std::os::raw::c_void;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct CVITime {
+    pub lsb: uInt64,
+    pub msb: int64,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union CVIAbsTime {
+    pub cviTime: CVITime,
+    pub u32Data: [uInt32; 4usize],
+}
+pub type DAQmxEveryNSamplesEventCallbackPtr = ::std::option::Option<
+    unsafe extern "C" fn(
+        taskHandle: TaskHandle,
+        everyNsamplesEventType: int32,
+        nSamples: uInt32,
+        callbackData: *mut ::std::os::raw::c_void,
+    ) -> int32,
+>;
+pub type DAQmxDoneEventCallbackPtr = ::std::option::Option<
+    unsafe extern "C" fn(
+        taskHandle: TaskHandle,
+        status: int32,
+        callbackData: *mut ::std::os::raw::c_void,
+    ) -> int32,
+>;
+pub type DAQmxSignalEventCallbackPtr = ::std::option::Option<
+    unsafe extern "C" fn(
+        taskHandle: TaskHandle,
+        signalID: int32,
+        callbackData: *mut ::std::os::raw::c_void,
+    ) -> int32,
+>;
+
+pub const DAQmx_Val_Cfg_Default: i32 = -1;
+pub const DAQmx_Val_WaitInfinitely: f64 = -1.0;
+
+pub const DAQmx_Val_GroupByChannel: i32 = 0;
+pub const DAQmx_Val_GroupByScanNumber: i32 = 1;
+
+pub const DAQmx_Val_ChanForAllLines: i32 = 1;
+
+pub const DAQmx_Val_Acquired_Into_Buffer: i32 = 1;
+pub const DAQmx_Val_Transferred_From_Buffer: i32 = 2;
+
+pub const DAQmx_Val_Task_Start: i32 = 0;
+pub const DAQmx_Val_Task_Stop: i32 = 1;
+pub const DAQmx_Val_Task_Verify: i32 = 2;
+pub const DAQmx_Val_Task_Commit: i32 = 3;
+pub const DAQmx_Val_Task_Reserve: i32 = 4;
+pub const DAQmx_Val_Task_Unreserve: i32 = 5;
+pub const DAQmx_Val_Task_Abort: i32 = 6;
+
+pub const DAQmx_Val_RSE: i32 = 10083;
+pub const DAQmx_Val_NRSE: i32 = 10078;
+pub const DAQmx_Val_Diff: i32 = 10106;
+pub const DAQmx_Val_PseudoDiff: i32 = 12529;
+
+pub const DAQmx_Val_Rising: i32 = 10280;
+pub const DAQmx_Val_Falling: i32 = 10171;
+
+pub const DAQmx_Val_Low: i32 = 10214;
+pub const DAQmx_Val_High: i32 = 10192;
+
+pub const DAQmx_Val_CountUp: i32 = 10128;
+pub const DAQmx_Val_CountDown: i32 = 10124;
+pub const DAQmx_Val_ExtControlled: i32 = 10326;
+
+pub const DAQmx_Val_FiniteSamps: i32 = 10178;
+pub const DAQmx_Val_ContSamps: i32 = 10123;
+pub const DAQmx_Val_HWTimedSinglePoint: i32 = 12522;
+
+pub const DAQmx_Val_AIConvertClock: i32 = 12484;
+pub const DAQmx_Val_SampleClock: i32 = 12487;
+pub const DAQmx_Val_ReferenceTrigger: i32 = 12490;
+pub const DAQmx_Val_StartTrigger: i32 = 12491;
+pub const DAQmx_Val_CounterOutputEvent: i32 = 12494;
+pub const DAQmx_Val_ChangeDetectionEvent: i32 = 12511;
+pub const DAQmx_Val_SampleCompleteEvent: i32 = 12530;
+pub const DAQmx_Val_ArmStartTrigger: i32 = 14641;
+
+pub const DAQmx_Val_OverwriteUnreadSamps: i32 = 10252;
+pub const DAQmx_Val_DoNotOverwriteUnreadSamps: i32 = 10159;
+
+pub const DAQmx_Val_FirstSample: i32 = 10424;
+pub const DAQmx_Val_CurrReadPos: i32 = 10425;
+pub const DAQmx_Val_RefTrig: i32 = 10426;
+pub const DAQmx_Val_FirstPretrigSamp: i32 = 10427;
+pub const DAQmx_Val_MostRecentSamp: i32 = 10428;
+pub const DAQmx_Val_CurrWritePos: i32 = 10430;
+
+pub const DAQmx_Val_AllowRegen: i32 = 10097;
+pub const DAQmx_Val_DoNotAllowRegen: i32 = 10158;
+
+pub const DAQmx_Val_FromCustomScale: i32 = 10065;
+pub const DAQmx_Val_FromTEDS: i32 = 12516;
+pub const DAQmx_Val_Volts: i32 = 10348;
+pub const DAQmx_Val_Amps: i32 = 10342;
+pub const DAQmx_Val_DegF: i32 = 10144;
+pub const DAQmx_Val_DegC: i32 = 10143;
+pub const DAQmx_Val_DegR: i32 = 10145;
+pub const DAQmx_Val_Kelvins: i32 = 10325;
+pub const DAQmx_Val_Strain: i32 = 10299;
+pub const DAQmx_Val_Ohms: i32 = 10384;
+pub const DAQmx_Val_Hz: i32 = 10373;
+pub const DAQmx_Val_Seconds: i32 = 10364;
+pub const DAQmx_Val_Meters: i32 = 10219;
+pub const DAQmx_Val_Inches: i32 = 10379;
+pub const DAQmx_Val_Degrees: i32 = 10146;
+pub const DAQmx_Val_Radians: i32 = 10273;
+pub const DAQmx_Val_g: i32 = 10186;
+pub const DAQmx_Val_MetersPerSecondSquared: i32 = 12470;
+pub const DAQmx_Val_Newtons: i32 = 15875;
+pub const DAQmx_Val_Pounds: i32 = 15876;
+pub const DAQmx_Val_PoundsPerSquareInch: i32 = 15879;
+pub const DAQmx_Val_Bar: i32 = 15880;
+pub const DAQmx_Val_Pascals: i32 = 10081;
+pub const DAQmx_Val_VoltsPerVolt: i32 = 15896;
+pub const DAQmx_Val_mVoltsPerVolt: i32 = 15897;
+pub const DAQmx_Val_NewtonMeters: i32 = 15881;
+pub const DAQmx_Val_InchOunces: i32 = 15882;
+pub const DAQmx_Val_InchPounds: i32 = 15883;
+pub const DAQmx_Val_FootPounds: i32 = 15884;
+
+pub const DAQmxErrorInvalidAttributeValue: i32 = -200077;
+pub const DAQmxErrorBufferTooSmallForString: i32 = -200228;
+pub const DAQmxWarningCAPIStringTruncatedToFitBuffer: i32 = 200026;
+
+unsafe extern "C" {
+    pub fn DAQmxCreateTask(
+        taskName: *const ::std::os::raw::c_char,
+        taskHandle: *mut TaskHandle,
+    ) -> int32;
+    pub fn DAQmxStartTask(taskHandle: TaskHandle) -> int32;
+    pub fn DAQmxStopTask(taskHandle: TaskHandle) -> int32;
+    pub fn DAQmxClearTask(taskHandle: TaskHandle) -> int32;
+    pub fn DAQmxWaitUntilTaskDone(taskHandle: TaskHandle, timeToWait: float64) -> int32;
+    pub fn DAQmxIsTaskDone(taskHandle: TaskHandle, isTaskDone: *mut bool32) -> int32;
+    pub fn DAQmxTaskControl(taskHandle: TaskHandle, action: int32) -> int32;
+    pub fn DAQmxRegisterEveryNSamplesEvent(
+        task: TaskHandle,
+        everyNsamplesEventType: int32,
+        nSamples: uInt32,
+        options: uInt32,
+        callbackFunction: DAQmxEveryNSamplesEventCallbackPtr,
+        callbackData: *mut ::std::os::raw::c_void,
+    ) -> int32;
+    pub fn DAQmxRegisterDoneEvent(
+        task: TaskHandle,
+        options: uInt32,
+        callbackFunction: DAQmxDoneEventCallbackPtr,
+        callbackData: *mut ::std::os::raw::c_void,
+    ) -> int32;
+    pub fn DAQmxRegisterSignalEvent(
+        task: TaskHandle,
+        signalID: int32,
+        options: uInt32,
+        callbackFunction: DAQmxSignalEventCallbackPtr,
+        callbackData: *mut ::std::os::raw::c_void,
+    ) -> int32;
+    pub fn DAQmxGetTaskName(
+        taskHandle: TaskHandle,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetTaskChannels(
+        taskHandle: TaskHandle,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+
+    pub fn DAQmxCreateAIVoltageChan(
+        taskHandle: TaskHandle,
+        physicalChannel: *const ::std::os::raw::c_char,
+        nameToAssignToChannel: *const ::std::os::raw::c_char,
+        terminalConfig: int32,
+        minVal: float64,
+        maxVal: float64,
+        units: int32,
+        customScaleName: *const ::std::os::raw::c_char,
+    ) -> int32;
+    pub fn DAQmxCreateAOVoltageChan(
+        taskHandle: TaskHandle,
+        physicalChannel: *const ::std::os::raw::c_char,
+        nameToAssignToChannel: *const ::std::os::raw::c_char,
+        minVal: float64,
+        maxVal: float64,
+        units: int32,
+        customScaleName: *const ::std::os::raw::c_char,
+    ) -> int32;
+    pub fn DAQmxCreateDIChan(
+        taskHandle: TaskHandle,
+        lines: *const ::std::os::raw::c_char,
+        nameToAssignToLines: *const ::std::os::raw::c_char,
+        lineGrouping: int32,
+    ) -> int32;
+    pub fn DAQmxCreateDOChan(
+        taskHandle: TaskHandle,
+        lines: *const ::std::os::raw::c_char,
+        nameToAssignToLines: *const ::std::os::raw::c_char,
+        lineGrouping: int32,
+    ) -> int32;
+    pub fn DAQmxCreateCICountEdgesChan(
+        taskHandle: TaskHandle,
+        counter: *const ::std::os::raw::c_char,
+        nameToAssignToChannel: *const ::std::os::raw::c_char,
+        edge: int32,
+        initialCount: uInt32,
+        countDirection: int32,
+    ) -> int32;
+    pub fn DAQmxCreateCOPulseChanFreq(
+        taskHandle: TaskHandle,
+        counter: *const ::std::os::raw::c_char,
+        nameToAssignToChannel: *const ::std::os::raw::c_char,
+        units: int32,
+        idleState: int32,
+        initialDelay: float64,
+        freq: float64,
+        dutyCycle: float64,
+    ) -> int32;
+    pub fn DAQmxCreateCOPulseChanTime(
+        taskHandle: TaskHandle,
+        counter: *const ::std::os::raw::c_char,
+        nameToAssignToChannel: *const ::std::os::raw::c_char,
+        units: int32,
+        idleState: int32,
+        initialDelay: float64,
+        lowTime: float64,
+        highTime: float64,
+    ) -> int32;
+
+    pub fn DAQmxCfgSampClkTiming(
+        taskHandle: TaskHandle,
+        source: *const ::std::os::raw::c_char,
+        rate: float64,
+        activeEdge: int32,
+        sampleMode: int32,
+        sampsPerChan: uInt64,
+    ) -> int32;
+    pub fn DAQmxCfgImplicitTiming(
+        taskHandle: TaskHandle,
+        sampleMode: int32,
+        sampsPerChan: uInt64,
+    ) -> int32;
+    pub fn DAQmxCfgInputBuffer(taskHandle: TaskHandle, numSampsPerChan: uInt32) -> int32;
+    pub fn DAQmxCfgOutputBuffer(taskHandle: TaskHandle, numSampsPerChan: uInt32) -> int32;
+
+    pub fn DAQmxDisableStartTrig(taskHandle: TaskHandle) -> int32;
+    pub fn DAQmxCfgDigEdgeStartTrig(
+        taskHandle: TaskHandle,
+        triggerSource: *const ::std::os::raw::c_char,
+        triggerEdge: int32,
+    ) -> int32;
+    pub fn DAQmxCfgDigEdgeRefTrig(
+        taskHandle: TaskHandle,
+        triggerSource: *const ::std::os::raw::c_char,
+        triggerEdge: int32,
+        pretriggerSamples: uInt32,
+    ) -> int32;
+
+    pub fn DAQmxReadAnalogF64(
+        taskHandle: TaskHandle,
+        numSampsPerChan: int32,
+        timeout: float64,
+        fillMode: bool32,
+        readArray: *mut float64,
+        arraySizeInSamps: uInt32,
+        sampsPerChanRead: *mut int32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadAnalogScalarF64(
+        taskHandle: TaskHandle,
+        timeout: float64,
+        value: *mut float64,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadDigitalScalarU32(
+        taskHandle: TaskHandle,
+        timeout: float64,
+        value: *mut uInt32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadDigitalLines(
+        taskHandle: TaskHandle,
+        numSampsPerChan: int32,
+        timeout: float64,
+        fillMode: bool32,
+        readArray: *mut uInt8,
+        arraySizeInBytes: uInt32,
+        sampsPerChanRead: *mut int32,
+        numBytesPerSamp: *mut int32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadCounterScalarF64(
+        taskHandle: TaskHandle,
+        timeout: float64,
+        value: *mut float64,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadCounterScalarU32(
+        taskHandle: TaskHandle,
+        timeout: float64,
+        value: *mut uInt32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadCounterF64(
+        taskHandle: TaskHandle,
+        numSampsPerChan: int32,
+        timeout: float64,
+        readArray: *mut float64,
+        arraySizeInSamps: uInt32,
+        sampsPerChanRead: *mut int32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxReadCounterU32(
+        taskHandle: TaskHandle,
+        numSampsPerChan: int32,
+        timeout: float64,
+        readArray: *mut uInt32,
+        arraySizeInSamps: uInt32,
+        sampsPerChanRead: *mut int32,
+        reserved: *mut bool32,
+    ) -> int32;
+
+    pub fn DAQmxWriteAnalogF64(
+        taskHandle: TaskHandle,
+        numSampsPerChan: int32,
+        autoStart: bool32,
+        timeout: float64,
+        dataLayout: bool32,
+        writeArray: *const float64,
+        sampsPerChanWritten: *mut int32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxWriteAnalogScalarF64(
+        taskHandle: TaskHandle,
+        autoStart: bool32,
+        timeout: float64,
+        value: float64,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxWriteDigitalScalarU32(
+        taskHandle: TaskHandle,
+        autoStart: bool32,
+        timeout: float64,
+        value: uInt32,
+        reserved: *mut bool32,
+    ) -> int32;
+    pub fn DAQmxWriteDigitalLines(
+        taskHandle: TaskHandle,
+        numSampsPerChan: int32,
+        autoStart: bool32,
+        timeout: float64,
+        dataLayout: bool32,
+        writeArray: *const uInt8,
+        sampsPerChanWritten: *mut int32,
+        reserved: *mut bool32,
+    ) -> int32;
+
+    pub fn DAQmxExportSignal(
+        taskHandle: TaskHandle,
+        signalID: int32,
+        outputTerminal: *const ::std::os::raw::c_char,
+    ) -> int32;
+
+    pub fn DAQmxCreateLinScale(
+        name: *const ::std::os::raw::c_char,
+        slope: float64,
+        yIntercept: float64,
+        preScaledUnits: int32,
+        scaledUnits: *const ::std::os::raw::c_char,
+    ) -> int32;
+    pub fn DAQmxGetScaleScaledUnits(
+        scaleName: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+
+    pub fn DAQmxResetDevice(deviceName: *const ::std::os::raw::c_char) -> int32;
+
+    pub fn DAQmxGetErrorString(
+        errorCode: int32,
+        errorString: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetExtendedErrorInfo(
+        errorString: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+
+    pub fn DAQmxGetAIMax(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut float64,
+    ) -> int32;
+    pub fn DAQmxGetAIMin(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut float64,
+    ) -> int32;
+    pub fn DAQmxGetAICustomScaleName(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetAITermCfg(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut int32,
+    ) -> int32;
+    pub fn DAQmxGetAIVoltageUnits(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut int32,
+    ) -> int32;
+    pub fn DAQmxGetDONumLines(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut uInt32,
+    ) -> int32;
+    pub fn DAQmxGetCOPulseTerm(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxSetCOPulseTerm(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *const ::std::os::raw::c_char,
+    ) -> int32;
+    pub fn DAQmxGetPhysicalChanName(
+        taskHandle: TaskHandle,
+        channel: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+
+    pub fn DAQmxGetDevAIPhysicalChans(
+        device: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetDevAOPhysicalChans(
+        device: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetDevDILines(
+        device: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetDevDOLines(
+        device: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetDevCIPhysicalChans(
+        device: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+    pub fn DAQmxGetDevCOPhysicalChans(
+        device: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_char,
+        bufferSize: uInt32,
+    ) -> int32;
+
+    pub fn DAQmxGetReadAutoStart(taskHandle: TaskHandle, data: *mut bool32) -> int32;
+    pub fn DAQmxSetReadAutoStart(taskHandle: TaskHandle, data: bool32) -> int32;
+    pub fn DAQmxGetReadAvailSampPerChan(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    pub fn DAQmxGetReadDigitalLinesBytesPerChan(taskHandle: TaskHandle, data: *mut uInt32)
+        -> int32;
+    pub fn DAQmxGetWriteDigitalLinesBytesPerChan(taskHandle: TaskHandle, data: *mut uInt32)
+        -> int32;
+    pub fn DAQmxGetTaskNumChans(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    pub fn DAQmxGetSampClkRate(taskHandle: TaskHandle, data: *mut float64) -> int32;
+    pub fn DAQmxGetSampQuantSampMode(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxGetReadCurrReadPos(taskHandle: TaskHandle, data: *mut uInt64) -> int32;
+    pub fn DAQmxGetReadTotalSampPerChanAcquired(taskHandle: TaskHandle, data: *mut uInt64)
+        -> int32;
+    pub fn DAQmxGetWriteSpaceAvail(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    pub fn DAQmxGetBufInputBufSize(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    pub fn DAQmxGetReadOverWrite(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxSetReadOverWrite(taskHandle: TaskHandle, data: int32) -> int32;
+    pub fn DAQmxGetReadRelativeTo(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxSetReadRelativeTo(taskHandle: TaskHandle, data: int32) -> int32;
+    pub fn DAQmxGetReadOffset(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxSetReadOffset(taskHandle: TaskHandle, data: int32) -> int32;
+    pub fn DAQmxGetBufOutputBufSize(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    pub fn DAQmxGetWriteRegenMode(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxSetWriteRegenMode(taskHandle: TaskHandle, data: int32) -> int32;
+    pub fn DAQmxGetWriteRelativeTo(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxSetWriteRelativeTo(taskHandle: TaskHandle, data: int32) -> int32;
+    pub fn DAQmxGetWriteOffset(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    pub fn DAQmxSetWriteOffset(taskHandle: TaskHandle, data: int32) -> int32;
+    pub fn DAQmxGetFirstSampTimestampEnable(taskHandle: TaskHandle, data: *mut bool32) -> int32;
+    pub fn DAQmxSetFirstSampTimestampEnable(taskHandle: TaskHandle, data: bool32) -> int32;
+    pub fn DAQmxGetFirstSampTimestampVal(taskHandle: TaskHandle, data: *mut CVIAbsTime) -> int32;
+
+    pub fn DAQmxGetSysDevNames(data: *mut ::std::os::raw::c_char, bufferSize: uInt32) -> int32;
+}
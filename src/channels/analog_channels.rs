@@ -3,15 +3,16 @@ use std::ffi::CString;
 
 use derive_builder::Builder;
 
-use crate::daqmx;
 use crate::daqmx::*;
 
-use super::{Channel, ChannelBuilderInput, ChannelBuilderOutput};
+use super::{builder_error_context, Channel, ChannelBuilderInput, ChannelBuilderOutput};
+use crate::backend::{Backend, ChannelProperty};
 use crate::daqmx_call;
 use crate::error::DaqmxError;
+use crate::error::Result;
 use crate::scales::PreScaledUnits;
 use crate::tasks::{AnalogInput, AnalogOutput, Task};
-use anyhow::Result;
+use crate::warnings::TaskWarnings;
 
 macro_rules! delegate_ai_channel {
     () => {
@@ -48,32 +49,40 @@ impl<T: AnalogChannelType> AnalogChannelTrait<T> for AnalogChannelBase<T> {
 }
 
 impl<T: AnalogChannelType> Channel for AnalogChannelBase<T> {
-    fn raw_handle(&self) -> *mut std::os::raw::c_void {
+    fn raw_handle(&self) -> TaskHandle {
         self.task.raw_handle()
     }
 
+    fn backend(&self) -> &dyn Backend {
+        self.task.backend()
+    }
+
     fn name(&self) -> &std::ffi::CStr {
         &self.name
     }
+
+    fn warning_state(&self) -> &TaskWarnings {
+        self.task.warning_state()
+    }
 }
 
 impl<T: AnalogChannelType> AnalogChannelBase<T> {
     /// Not needed for [AnalogOutput] channels]
     pub fn physical_channel(&self) -> Result<String> {
-        self.read_channel_property_string(daqmx::DAQmxGetPhysicalChanName)
+        self.read_channel_property_string(ChannelProperty::PhysicalChannelName)
     }
     pub fn ai_max(&self) -> Result<f64> {
-        self.read_channel_property(daqmx::DAQmxGetAIMax)
+        self.read_channel_property(ChannelProperty::AIMax)
     }
     pub fn ai_min(&self) -> Result<f64> {
-        self.read_channel_property(daqmx::DAQmxGetAIMin)
+        self.read_channel_property(ChannelProperty::AIMin)
     }
     pub fn ai_terminal_config(&self) -> Result<AnalogTerminalConfig> {
-        self.read_channel_property(daqmx::DAQmxGetAITermCfg)?
+        self.read_channel_property::<i32>(ChannelProperty::AITerminalConfig)?
             .try_into()
     }
     pub fn custom_scale_name(&self) -> Result<String> {
-        self.read_channel_property_string(daqmx::DAQmxGetAICustomScaleName)
+        self.read_channel_property_string(ChannelProperty::AICustomScaleName)
     }
 }
 
@@ -86,7 +95,7 @@ impl<T: AnalogChannelType> VoltageChannelBase<T> {
     pub fn scale(&self) -> Result<VoltageScale> {
         let scale: VoltageScale = self
             .ai_channel
-            .read_channel_property(DAQmxGetAIVoltageUnits)?
+            .read_channel_property::<i32>(ChannelProperty::AIVoltageUnits)?
             .try_into()?;
 
         if let VoltageScale::CustomScale(_) = scale {
@@ -96,6 +105,25 @@ impl<T: AnalogChannelType> VoltageChannelBase<T> {
             Ok(scale)
         }
     }
+
+    /// The units of the values of the channel: `Volts`, or the scaled units of its custom scale.
+    /// Empty for units from TEDS.
+    pub fn units(&self) -> Result<String> {
+        match self.scale()? {
+            VoltageScale::Volts => Ok("Volts".to_string()),
+            VoltageScale::CustomScale(Some(name)) => {
+                let mut units = String::new();
+                daqmx_call!(
+                    self.ai_channel.backend(),
+                    scale_scaled_units(&name, &mut units),
+                    self.ai_channel.error_context(),
+                    self.ai_channel.warning_state()
+                )?;
+                Ok(units)
+            }
+            VoltageScale::CustomScale(None) | VoltageScale::FromTEDS => Ok(String::new()),
+        }
+    }
 }
 
 impl<T: AnalogChannelType> AnalogChannelTrait<T> for VoltageChannelBase<T> {
@@ -123,9 +151,9 @@ pub enum AnalogTerminalConfig {
 }
 
 impl TryFrom<i32> for AnalogTerminalConfig {
-    type Error = anyhow::Error;
+    type Error = DaqmxError;
 
-    fn try_from(value: i32) -> anyhow::Result<Self> {
+    fn try_from(value: i32) -> std::result::Result<Self, Self::Error> {
         //The if statements look wierd but seemed like the best way for the type conversion to be combined.
         match value {
             DAQmx_Val_Cfg_Default => Ok(Self::Default),
@@ -133,9 +161,9 @@ impl TryFrom<i32> for AnalogTerminalConfig {
             DAQmx_Val_NRSE => Ok(Self::NRSE),
             DAQmx_Val_Diff => Ok(Self::Differential),
             DAQmx_Val_PseudoDiff => Ok(Self::PseudoDifferential),
-            _ => Err(anyhow::anyhow!(
-                "AnalogTerminalConfig value {} not recognized",
-                value
+            _ => Err(DaqmxError::UnexpectedValue(
+                "AnalogTerminalConfig".to_string(),
+                value,
             )),
         }
     }
@@ -193,7 +221,7 @@ impl TryFrom<i32> for VoltageScale {
 pub trait AnalogChannelBuilderTrait: ChannelBuilderInput {}
 
 #[derive(Builder, Debug, Clone)]
-#[builder(setter(into))]
+#[builder(setter(into), build_fn(error = "crate::error::DaqmxError"))]
 pub struct VoltageChannel {
     physical_channel: CString,
     name: Option<CString>,
@@ -219,33 +247,41 @@ impl VoltageChannel {
 }
 
 impl ChannelBuilderInput for VoltageChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateAIVoltageChan(
-            task,
-            self.physical_channel.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            self.terminal_config as i32,
-            -self.max, // Set min as -max
-            self.max,  // max value
-            self.scale.clone().into(),
-            CString::from(self.scale).as_ptr(),
-        ))
+        daqmx_call!(
+            backend,
+            create_ai_voltage_chan(
+                task,
+                &self.physical_channel,
+                self.name.as_ref().unwrap_or(&empty_string),
+                self.terminal_config as i32,
+                -self.max, // Set min as -max
+                self.max,  // max value
+                self.scale.clone().into(),
+                &CString::from(self.scale),
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_channel)
+        )
     }
 }
 
 impl ChannelBuilderOutput for VoltageChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateAOVoltageChan(
-            task,
-            self.physical_channel.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            -self.max, // Set min as -max
-            self.max,  // max value
-            self.scale.clone().into(),
-            CString::from(self.scale).as_ptr(),
-        ))
+        daqmx_call!(
+            backend,
+            create_ao_voltage_chan(
+                task,
+                &self.physical_channel,
+                self.name.as_ref().unwrap_or(&empty_string),
+                -self.max, // Set min as -max
+                self.max,  // max value
+                self.scale.clone().into(),
+                &CString::from(self.scale),
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_channel)
+        )
     }
 }
 impl AnalogChannelBuilderTrait for VoltageChannel {}
@@ -1,14 +1,14 @@
-use anyhow::bail;
-use anyhow::Result;
+use crate::error::{DaqmxError, Result};
 use derive_builder::Builder;
 use std::ffi::CString;
 
-use super::{Channel, ChannelBuilderInput, ChannelBuilderOutput};
-use crate::daqmx;
+use super::{builder_error_context, Channel, ChannelBuilderInput, ChannelBuilderOutput};
+use crate::backend::{Backend, ChannelProperty};
 use crate::daqmx::*;
 use crate::daqmx_call;
 use crate::tasks::{CounterInput, CounterOutput, Task};
 use crate::types::{ClockEdge, CountDirection, FrequencyUnits, IdleState, TimeUnits};
+use crate::warnings::TaskWarnings;
 
 pub trait CounterChannelType {}
 
@@ -32,22 +32,30 @@ impl<T: CounterChannelType> CounterChannelTrait<T> for CounterChannelBase<T> {
 }
 
 impl<T: CounterChannelType> Channel for CounterChannelBase<T> {
-    fn raw_handle(&self) -> *mut std::os::raw::c_void {
+    fn raw_handle(&self) -> TaskHandle {
         self.task.raw_handle()
     }
+
+    fn backend(&self) -> &dyn Backend {
+        self.task.backend()
+    }
     fn name(&self) -> &std::ffi::CStr {
         &self.name
     }
+
+    fn warning_state(&self) -> &TaskWarnings {
+        self.task.warning_state()
+    }
 }
 
 impl<T: CounterChannelType> CounterChannelBase<T> {
     pub fn physical_channel(&self) -> Result<String> {
-        self.read_channel_property_string(daqmx::DAQmxGetPhysicalChanName)
+        self.read_channel_property_string(ChannelProperty::PhysicalChannelName)
     }
 }
 
 #[derive(Builder, Debug, Clone)]
-#[builder(setter(into))]
+#[builder(setter(into), build_fn(error = "crate::error::DaqmxError"))]
 pub struct CounterOutputPulseTimeChannel {
     physical_counter: CString,
     #[builder(default)]
@@ -78,33 +86,43 @@ impl CounterOutputPulseTimeChannel {
 }
 
 impl ChannelBuilderOutput for CounterOutputPulseTimeChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         if self.low_time <= 0.0 {
-            bail!("low_time must be > 0.0 seconds");
+            return Err(DaqmxError::Validation(
+                "low_time must be > 0.0 seconds".to_string(),
+            ));
         }
         if self.high_time <= 0.0 {
-            bail!("high_time must be > 0.0 seconds");
+            return Err(DaqmxError::Validation(
+                "high_time must be > 0.0 seconds".to_string(),
+            ));
         }
         if self.initial_delay < 0.0 {
-            bail!("initial_delay must be >= 0.0 seconds");
+            return Err(DaqmxError::Validation(
+                "initial_delay must be >= 0.0 seconds".to_string(),
+            ));
         }
 
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateCOPulseChanTime(
-            task,
-            self.physical_counter.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            self.units.into(),
-            self.idle_state.into(),
-            self.initial_delay,
-            self.low_time,
-            self.high_time
-        ))
+        daqmx_call!(
+            backend,
+            create_co_pulse_chan_time(
+                task,
+                &self.physical_counter,
+                self.name.as_ref().unwrap_or(&empty_string),
+                self.units.into(),
+                self.idle_state.into(),
+                self.initial_delay,
+                self.low_time,
+                self.high_time
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_counter)
+        )
     }
 }
 
 #[derive(Builder, Debug, Clone)]
-#[builder(setter(into))]
+#[builder(setter(into), build_fn(error = "crate::error::DaqmxError"))]
 pub struct CounterOutputPulseFreqChannel {
     physical_counter: CString,
     #[builder(default)]
@@ -135,33 +153,43 @@ impl CounterOutputPulseFreqChannel {
 }
 
 impl ChannelBuilderOutput for CounterOutputPulseFreqChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         if self.frequency <= 0.0 {
-            bail!("frequency must be > 0.0 Hz");
+            return Err(DaqmxError::Validation(
+                "frequency must be > 0.0 Hz".to_string(),
+            ));
         }
         if !(0.0 < self.duty_cycle && self.duty_cycle < 1.0) {
-            bail!("duty_cycle must be in range (0.0, 1.0)");
+            return Err(DaqmxError::Validation(
+                "duty_cycle must be in range (0.0, 1.0)".to_string(),
+            ));
         }
         if self.initial_delay < 0.0 {
-            bail!("initial_delay must be >= 0.0 seconds");
+            return Err(DaqmxError::Validation(
+                "initial_delay must be >= 0.0 seconds".to_string(),
+            ));
         }
 
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateCOPulseChanFreq(
-            task,
-            self.physical_counter.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            self.units.into(),
-            self.idle_state.into(),
-            self.initial_delay,
-            self.frequency,
-            self.duty_cycle
-        ))
+        daqmx_call!(
+            backend,
+            create_co_pulse_chan_freq(
+                task,
+                &self.physical_counter,
+                self.name.as_ref().unwrap_or(&empty_string),
+                self.units.into(),
+                self.idle_state.into(),
+                self.initial_delay,
+                self.frequency,
+                self.duty_cycle
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_counter)
+        )
     }
 }
 
 #[derive(Builder, Debug, Clone)]
-#[builder(setter(into))]
+#[builder(setter(into), build_fn(error = "crate::error::DaqmxError"))]
 pub struct CounterInputCountEdgesChannel {
     physical_counter: CString,
     #[builder(default)]
@@ -188,15 +216,19 @@ impl CounterInputCountEdgesChannel {
 }
 
 impl ChannelBuilderInput for CounterInputCountEdgesChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateCICountEdgesChan(
-            task,
-            self.physical_counter.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            self.edge.into(),
-            self.initial_count,
-            self.count_direction.into()
-        ))
+        daqmx_call!(
+            backend,
+            create_ci_count_edges_chan(
+                task,
+                &self.physical_counter,
+                self.name.as_ref().unwrap_or(&empty_string),
+                self.edge.into(),
+                self.initial_count,
+                self.count_direction.into()
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_counter)
+        )
     }
 }
@@ -1,10 +1,12 @@
-use super::{Channel, ChannelBuilderInput, ChannelBuilderOutput};
+use super::{builder_error_context, Channel, ChannelBuilderInput, ChannelBuilderOutput};
+use crate::backend::{Backend, ChannelProperty};
 use crate::daqmx;
 use crate::daqmx::*;
 use crate::daqmx_call;
+use crate::error::Result;
 use crate::tasks::DigitalOutput;
 use crate::tasks::{DigitalInput, Task};
-use anyhow::Result;
+use crate::warnings::TaskWarnings;
 use derive_builder::Builder;
 use std::ffi::CString;
 
@@ -30,22 +32,38 @@ impl<T: DigitalChannelType> DigitalChannelTrait<T> for DigitalChannelBase<T> {
 }
 
 impl<T: DigitalChannelType> Channel for DigitalChannelBase<T> {
-    fn raw_handle(&self) -> *mut std::os::raw::c_void {
+    fn raw_handle(&self) -> TaskHandle {
         self.task.raw_handle()
     }
+
+    fn backend(&self) -> &dyn Backend {
+        self.task.backend()
+    }
     fn name(&self) -> &std::ffi::CStr {
         &self.name
     }
+
+    fn warning_state(&self) -> &TaskWarnings {
+        self.task.warning_state()
+    }
 }
 
 impl<T: DigitalChannelType> DigitalChannelBase<T> {
     pub fn physical_channel(&self) -> Result<String> {
-        self.read_channel_property_string(daqmx::DAQmxGetPhysicalChanName)
+        self.read_channel_property_string(ChannelProperty::PhysicalChannelName)
+    }
+}
+
+impl DigitalChannelBase<DigitalOutput> {
+    /// The number of lines in the channel, which is the number of line states it takes per
+    /// sample.
+    pub fn num_lines(&self) -> Result<u32> {
+        self.read_channel_property(ChannelProperty::DONumLines)
     }
 }
 
 #[derive(Builder, Debug, Clone)]
-#[builder(setter(into))]
+#[builder(setter(into), build_fn(error = "crate::error::DaqmxError"))]
 pub struct DigitalChannel {
     physical_channel: CString,
     #[builder(default)]
@@ -77,26 +95,34 @@ impl DigitalChannel {
 
 /// Digital Input impl
 impl ChannelBuilderInput for DigitalChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateDIChan(
-            task,
-            self.physical_channel.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            daqmx::DAQmx_Val_ChanForAllLines
-        ))
+        daqmx_call!(
+            backend,
+            create_di_chan(
+                task,
+                &self.physical_channel,
+                self.name.as_ref().unwrap_or(&empty_string),
+                daqmx::DAQmx_Val_ChanForAllLines
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_channel)
+        )
     }
 }
 
 /// Digital Output impl
 impl ChannelBuilderOutput for DigitalChannel {
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()> {
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()> {
         let empty_string = CString::default();
-        daqmx_call!(daqmx::DAQmxCreateDOChan(
-            task,
-            self.physical_channel.as_ptr(),
-            self.name.as_ref().unwrap_or(&empty_string).as_ptr(),
-            daqmx::DAQmx_Val_ChanForAllLines
-        ))
+        daqmx_call!(
+            backend,
+            create_do_chan(
+                task,
+                &self.physical_channel,
+                self.name.as_ref().unwrap_or(&empty_string),
+                daqmx::DAQmx_Val_ChanForAllLines
+            ),
+            builder_error_context(backend, task, self.name.as_ref(), &self.physical_channel)
+        )
     }
 }
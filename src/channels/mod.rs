@@ -6,88 +6,98 @@ pub use analog_channels::*;
 pub use counter_channels::*;
 pub use digital_channels::*;
 
+use crate::backend::{Backend, ChannelProperty, PropertyValue};
 use crate::daqmx::TaskHandle;
-use crate::error::{handle_error, string_property_size_error, DaqmxError};
-use crate::{daqmx, daqmx_call};
-use anyhow::Result;
-use std::ffi::CStr;
-use std::os::raw::{c_char, c_void};
+use crate::error::Result;
+use crate::error::{handle_status, string_property_size_error, DaqmxError, ErrorContext};
+use crate::warnings::TaskWarnings;
+use std::ffi::{CStr, CString};
 
 trait Channel {
-    fn raw_handle(&self) -> *mut c_void;
+    fn raw_handle(&self) -> TaskHandle;
+    fn backend(&self) -> &dyn Backend;
     fn name(&self) -> &CStr;
+    fn warning_state(&self) -> &TaskWarnings;
 
-    ///Read a channel property as a string, given a raw DAQmx Function.
-    fn read_channel_property_string(
-        &self,
-        daqmx_fn: unsafe extern "C" fn(daqmx::TaskHandle, *const c_char, *mut c_char, u32) -> i32,
-    ) -> Result<String> {
-        let return_value = unsafe {
-            daqmx_fn(
-                self.raw_handle(),
-                self.name().as_ptr(),
-                std::ptr::null_mut(),
-                0,
-            )
-        };
-
-        if return_value < 0 {
-            handle_error(return_value)?;
-        }
-
-        let buffer_size = return_value as u32;
-
-        let mut buffer = vec![0u8; return_value as usize];
+    /// Identifies the channel and its task in errors.
+    fn error_context(&self) -> ErrorContext {
+        ErrorContext::for_task(self.backend(), self.raw_handle())
+            .with_channel(self.name().to_string_lossy())
+    }
 
+    ///Read a channel property as a string.
+    fn read_channel_property_string(&self, property: ChannelProperty) -> Result<String> {
+        let mut value = PropertyValue::String(String::new());
         let return_value = unsafe {
-            daqmx_fn(
+            self.backend().get_channel_property(
                 self.raw_handle(),
-                self.name().as_ptr(),
-                buffer.as_mut_ptr() as *mut c_char,
-                buffer_size,
+                self.name(),
+                property,
+                &mut value,
             )
         };
 
-        let should_retry = string_property_size_error(return_value)?;
+        let should_retry = string_property_size_error(self.backend(), return_value)?;
 
         if should_retry {
             // Just error for now - will review retries in the future.
-            return Err(DaqmxError::StringPropertyLengthChanged.into());
+            return Err(DaqmxError::StringPropertyLengthChanged);
         }
-
-        //pop the null off.
-        buffer.pop();
-        Ok(String::from_utf8(buffer)?)
+        handle_status(
+            self.backend(),
+            return_value,
+            || {
+                self.error_context()
+                    .with_operation(crate::backend::function_name("get_channel_property"))
+            },
+            self.warning_state(),
+        )?;
+
+        String::try_from(value)
     }
 
-    fn read_channel_property<T: Default + Copy>(
+    fn read_channel_property<T: TryFrom<PropertyValue, Error = DaqmxError>>(
         &self,
-        daqmx_fn: unsafe extern "C" fn(daqmx::TaskHandle, *const c_char, *mut T) -> i32,
+        property: ChannelProperty,
     ) -> Result<T> {
-        let mut value: T = T::default();
+        let mut value = PropertyValue::I32(0);
 
-        daqmx_call!(daqmx_fn(
-            self.raw_handle(),
-            self.name().as_ptr(),
-            &mut value
-        ))?;
+        crate::daqmx_call!(
+            self.backend(),
+            get_channel_property(self.raw_handle(), self.name(), property, &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
 
-        Ok(value)
+        T::try_from(value)
     }
 }
 
+/// Identifies a channel being added to a task in errors, by its name or else its physical channel.
+fn builder_error_context(
+    backend: &dyn Backend,
+    task: TaskHandle,
+    name: Option<&CString>,
+    physical_channel: &CStr,
+) -> ErrorContext {
+    let channel = name
+        .filter(|name| !name.is_empty())
+        .map_or(physical_channel, CString::as_c_str);
+    ErrorContext::for_task(backend, task).with_channel(channel.to_string_lossy())
+}
+
 pub trait ChannelBuilderInput {
     /// Add this channel configuration to an existing DAQmx task handle.
     ///
     /// # Safety
-    /// Caller must provide a valid, live DAQmx task handle created by NI-DAQmx.
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()>;
+    /// Caller must provide a valid, live task handle created by `backend`.
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()>;
 }
 
 pub trait ChannelBuilderOutput {
     /// Add this channel configuration to an existing DAQmx task handle.
     ///
     /// # Safety
-    /// Caller must provide a valid, live DAQmx task handle created by NI-DAQmx.
-    unsafe fn add_to_task(self, task: TaskHandle) -> Result<()>;
+    /// Caller must provide a valid, live task handle created by `backend`.
+    unsafe fn add_to_task(self, backend: &dyn Backend, task: TaskHandle) -> Result<()>;
 }
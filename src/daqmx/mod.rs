@@ -0,0 +1,133 @@
+//! Raw bindings to the NI-DAQmx C API.
+//!
+//! By default the functions are linked at build time. With the `runtime-loading` feature they
+//! are resolved from the NI-DAQmx shared library on first use (see [`runtime`]) so binaries
+//! still start on machines without the driver installed.
+
+mod bindings {
+    #[cfg(feature = "prebuilt-bindings")]
+    include!("../bindings.rs");
+    #[cfg(not(feature = "prebuilt-bindings"))]
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+pub use bindings::*;
+
+use crate::error::DaqmxError;
+
+/// Checks the NI-DAQmx library is available, returning [`DaqmxError::DriverNotInstalled`] if not.
+///
+/// This always succeeds when the library is linked at build time.
+pub fn ensure_loaded() -> Result<(), DaqmxError> {
+    match load_error() {
+        Some(reason) => Err(DaqmxError::DriverNotInstalled(reason.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// The reason the NI-DAQmx library could not be loaded, if it failed.
+pub(crate) fn load_error() -> Option<&'static str> {
+    #[cfg(feature = "runtime-loading")]
+    return runtime::functions().err();
+    #[cfg(not(feature = "runtime-loading"))]
+    return None;
+}
+
+#[cfg(feature = "runtime-loading")]
+#[macro_use]
+pub mod runtime;
+
+#[cfg(feature = "runtime-loading")]
+use std::os::raw::{c_char, c_void};
+
+#[cfg(feature = "runtime-loading")]
+runtime_functions! {
+    fn DAQmxCreateTask(taskName: *const c_char, taskHandle: *mut TaskHandle) -> int32;
+    fn DAQmxStartTask(taskHandle: TaskHandle) -> int32;
+    fn DAQmxStopTask(taskHandle: TaskHandle) -> int32;
+    fn DAQmxClearTask(taskHandle: TaskHandle) -> int32;
+    fn DAQmxWaitUntilTaskDone(taskHandle: TaskHandle, timeToWait: float64) -> int32;
+    fn DAQmxIsTaskDone(taskHandle: TaskHandle, isTaskDone: *mut bool32) -> int32;
+    fn DAQmxTaskControl(taskHandle: TaskHandle, action: int32) -> int32;
+    fn DAQmxRegisterEveryNSamplesEvent(task: TaskHandle, everyNsamplesEventType: int32, nSamples: uInt32, options: uInt32, callbackFunction: DAQmxEveryNSamplesEventCallbackPtr, callbackData: *mut c_void) -> int32;
+    fn DAQmxRegisterDoneEvent(task: TaskHandle, options: uInt32, callbackFunction: DAQmxDoneEventCallbackPtr, callbackData: *mut c_void) -> int32;
+    fn DAQmxRegisterSignalEvent(task: TaskHandle, signalID: int32, options: uInt32, callbackFunction: DAQmxSignalEventCallbackPtr, callbackData: *mut c_void) -> int32;
+    fn DAQmxGetTaskName(taskHandle: TaskHandle, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetTaskChannels(taskHandle: TaskHandle, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxCreateAIVoltageChan(taskHandle: TaskHandle, physicalChannel: *const c_char, nameToAssignToChannel: *const c_char, terminalConfig: int32, minVal: float64, maxVal: float64, units: int32, customScaleName: *const c_char) -> int32;
+    fn DAQmxCreateAOVoltageChan(taskHandle: TaskHandle, physicalChannel: *const c_char, nameToAssignToChannel: *const c_char, minVal: float64, maxVal: float64, units: int32, customScaleName: *const c_char) -> int32;
+    fn DAQmxCreateDIChan(taskHandle: TaskHandle, lines: *const c_char, nameToAssignToLines: *const c_char, lineGrouping: int32) -> int32;
+    fn DAQmxCreateDOChan(taskHandle: TaskHandle, lines: *const c_char, nameToAssignToLines: *const c_char, lineGrouping: int32) -> int32;
+    fn DAQmxCreateCICountEdgesChan(taskHandle: TaskHandle, counter: *const c_char, nameToAssignToChannel: *const c_char, edge: int32, initialCount: uInt32, countDirection: int32) -> int32;
+    fn DAQmxCreateCOPulseChanFreq(taskHandle: TaskHandle, counter: *const c_char, nameToAssignToChannel: *const c_char, units: int32, idleState: int32, initialDelay: float64, freq: float64, dutyCycle: float64) -> int32;
+    fn DAQmxCreateCOPulseChanTime(taskHandle: TaskHandle, counter: *const c_char, nameToAssignToChannel: *const c_char, units: int32, idleState: int32, initialDelay: float64, lowTime: float64, highTime: float64) -> int32;
+    fn DAQmxCfgSampClkTiming(taskHandle: TaskHandle, source: *const c_char, rate: float64, activeEdge: int32, sampleMode: int32, sampsPerChan: uInt64) -> int32;
+    fn DAQmxCfgImplicitTiming(taskHandle: TaskHandle, sampleMode: int32, sampsPerChan: uInt64) -> int32;
+    fn DAQmxCfgInputBuffer(taskHandle: TaskHandle, numSampsPerChan: uInt32) -> int32;
+    fn DAQmxCfgOutputBuffer(taskHandle: TaskHandle, numSampsPerChan: uInt32) -> int32;
+    fn DAQmxDisableStartTrig(taskHandle: TaskHandle) -> int32;
+    fn DAQmxCfgDigEdgeStartTrig(taskHandle: TaskHandle, triggerSource: *const c_char, triggerEdge: int32) -> int32;
+    fn DAQmxCfgDigEdgeRefTrig(taskHandle: TaskHandle, triggerSource: *const c_char, triggerEdge: int32, pretriggerSamples: uInt32) -> int32;
+    fn DAQmxReadAnalogF64(taskHandle: TaskHandle, numSampsPerChan: int32, timeout: float64, fillMode: bool32, readArray: *mut float64, arraySizeInSamps: uInt32, sampsPerChanRead: *mut int32, reserved: *mut bool32) -> int32;
+    fn DAQmxReadAnalogScalarF64(taskHandle: TaskHandle, timeout: float64, value: *mut float64, reserved: *mut bool32) -> int32;
+    fn DAQmxReadDigitalScalarU32(taskHandle: TaskHandle, timeout: float64, value: *mut uInt32, reserved: *mut bool32) -> int32;
+    fn DAQmxReadDigitalLines(taskHandle: TaskHandle, numSampsPerChan: int32, timeout: float64, fillMode: bool32, readArray: *mut uInt8, arraySizeInBytes: uInt32, sampsPerChanRead: *mut int32, numBytesPerSamp: *mut int32, reserved: *mut bool32) -> int32;
+    fn DAQmxReadCounterScalarF64(taskHandle: TaskHandle, timeout: float64, value: *mut float64, reserved: *mut bool32) -> int32;
+    fn DAQmxReadCounterScalarU32(taskHandle: TaskHandle, timeout: float64, value: *mut uInt32, reserved: *mut bool32) -> int32;
+    fn DAQmxReadCounterF64(taskHandle: TaskHandle, numSampsPerChan: int32, timeout: float64, readArray: *mut float64, arraySizeInSamps: uInt32, sampsPerChanRead: *mut int32, reserved: *mut bool32) -> int32;
+    fn DAQmxReadCounterU32(taskHandle: TaskHandle, numSampsPerChan: int32, timeout: float64, readArray: *mut uInt32, arraySizeInSamps: uInt32, sampsPerChanRead: *mut int32, reserved: *mut bool32) -> int32;
+    fn DAQmxWriteAnalogF64(taskHandle: TaskHandle, numSampsPerChan: int32, autoStart: bool32, timeout: float64, dataLayout: bool32, writeArray: *const float64, sampsPerChanWritten: *mut int32, reserved: *mut bool32) -> int32;
+    fn DAQmxWriteAnalogScalarF64(taskHandle: TaskHandle, autoStart: bool32, timeout: float64, value: float64, reserved: *mut bool32) -> int32;
+    fn DAQmxWriteDigitalScalarU32(taskHandle: TaskHandle, autoStart: bool32, timeout: float64, value: uInt32, reserved: *mut bool32) -> int32;
+    fn DAQmxWriteDigitalLines(taskHandle: TaskHandle, numSampsPerChan: int32, autoStart: bool32, timeout: float64, dataLayout: bool32, writeArray: *const uInt8, sampsPerChanWritten: *mut int32, reserved: *mut bool32) -> int32;
+    fn DAQmxExportSignal(taskHandle: TaskHandle, signalID: int32, outputTerminal: *const c_char) -> int32;
+    fn DAQmxCreateLinScale(name: *const c_char, slope: float64, yIntercept: float64, preScaledUnits: int32, scaledUnits: *const c_char) -> int32;
+    fn DAQmxGetScaleScaledUnits(scaleName: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxResetDevice(deviceName: *const c_char) -> int32;
+    fn DAQmxGetErrorString(errorCode: int32, errorString: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetExtendedErrorInfo(errorString: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetAIMax(taskHandle: TaskHandle, channel: *const c_char, data: *mut float64) -> int32;
+    fn DAQmxGetAIMin(taskHandle: TaskHandle, channel: *const c_char, data: *mut float64) -> int32;
+    fn DAQmxGetAICustomScaleName(taskHandle: TaskHandle, channel: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetAITermCfg(taskHandle: TaskHandle, channel: *const c_char, data: *mut int32) -> int32;
+    fn DAQmxGetAIVoltageUnits(taskHandle: TaskHandle, channel: *const c_char, data: *mut int32) -> int32;
+    fn DAQmxGetDONumLines(taskHandle: TaskHandle, channel: *const c_char, data: *mut uInt32) -> int32;
+    fn DAQmxGetCOPulseTerm(taskHandle: TaskHandle, channel: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxSetCOPulseTerm(taskHandle: TaskHandle, channel: *const c_char, data: *const c_char) -> int32;
+    fn DAQmxGetPhysicalChanName(taskHandle: TaskHandle, channel: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetDevAIPhysicalChans(device: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetDevAOPhysicalChans(device: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetDevDILines(device: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetDevDOLines(device: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetDevCIPhysicalChans(device: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetDevCOPhysicalChans(device: *const c_char, data: *mut c_char, bufferSize: uInt32) -> int32;
+    fn DAQmxGetReadAutoStart(taskHandle: TaskHandle, data: *mut bool32) -> int32;
+    fn DAQmxSetReadAutoStart(taskHandle: TaskHandle, data: bool32) -> int32;
+    fn DAQmxGetReadAvailSampPerChan(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetReadDigitalLinesBytesPerChan(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetWriteDigitalLinesBytesPerChan(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetTaskNumChans(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetSampClkRate(taskHandle: TaskHandle, data: *mut float64) -> int32;
+    fn DAQmxGetSampQuantSampMode(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxGetReadCurrReadPos(taskHandle: TaskHandle, data: *mut uInt64) -> int32;
+    fn DAQmxGetReadTotalSampPerChanAcquired(taskHandle: TaskHandle, data: *mut uInt64) -> int32;
+    fn DAQmxGetWriteSpaceAvail(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetBufInputBufSize(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetReadOverWrite(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxSetReadOverWrite(taskHandle: TaskHandle, data: int32) -> int32;
+    fn DAQmxGetReadRelativeTo(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxSetReadRelativeTo(taskHandle: TaskHandle, data: int32) -> int32;
+    fn DAQmxGetReadOffset(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxSetReadOffset(taskHandle: TaskHandle, data: int32) -> int32;
+    fn DAQmxGetBufOutputBufSize(taskHandle: TaskHandle, data: *mut uInt32) -> int32;
+    fn DAQmxGetWriteRegenMode(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxSetWriteRegenMode(taskHandle: TaskHandle, data: int32) -> int32;
+    fn DAQmxGetWriteRelativeTo(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxSetWriteRelativeTo(taskHandle: TaskHandle, data: int32) -> int32;
+    fn DAQmxGetWriteOffset(taskHandle: TaskHandle, data: *mut int32) -> int32;
+    fn DAQmxSetWriteOffset(taskHandle: TaskHandle, data: int32) -> int32;
+    fn DAQmxGetFirstSampTimestampEnable(taskHandle: TaskHandle, data: *mut bool32) -> int32;
+    fn DAQmxSetFirstSampTimestampEnable(taskHandle: TaskHandle, data: bool32) -> int32;
+    fn DAQmxGetFirstSampTimestampVal(taskHandle: TaskHandle, data: *mut CVIAbsTime) -> int32;
+    fn DAQmxGetSysDevNames(data: *mut c_char, bufferSize: uInt32) -> int32;
+}
@@ -0,0 +1,96 @@
+//! Loads the NI-DAQmx shared library on first use instead of linking to it at build time.
+//!
+//! Each DAQmx function in [`crate::daqmx`] is replaced by a shim with the same signature which
+//! forwards to the entry point resolved here. If the library cannot be loaded the shims return
+//! [`DRIVER_NOT_LOADED`] rather than crashing the process, which
+//! [`crate::error::handle_error`] turns into [`crate::error::DaqmxError::DriverNotInstalled`].
+
+use std::ffi::OsString;
+use std::sync::OnceLock;
+
+/// Return code used by the shims when the NI-DAQmx library is unavailable.
+///
+/// This is outside of the range of codes used by DAQmx.
+pub const DRIVER_NOT_LOADED: i32 = i32::MIN;
+
+#[cfg(target_os = "windows")]
+const LIBRARY_NAMES: &[&str] = &["nicaiu.dll"];
+#[cfg(not(target_os = "windows"))]
+const LIBRARY_NAMES: &[&str] = &["libnidaqmx.so.1", "libnidaqmx.so"];
+
+/// The library names tried in order, preceded by the `NIDAQMX_LIBRARY` override if set.
+fn candidate_names() -> Vec<OsString> {
+    std::env::var_os("NIDAQMX_LIBRARY")
+        .into_iter()
+        .chain(LIBRARY_NAMES.iter().map(OsString::from))
+        .collect()
+}
+
+pub(super) fn functions() -> Result<&'static super::FunctionTable, &'static str> {
+    static FUNCTIONS: OnceLock<Result<super::FunctionTable, String>> = OnceLock::new();
+    FUNCTIONS.get_or_init(load).as_ref().map_err(String::as_str)
+}
+
+fn load() -> Result<super::FunctionTable, String> {
+    let mut failures = Vec::new();
+    for name in candidate_names() {
+        // Safety: loading the library runs its initialisers, which is what linking would do as well.
+        match unsafe { libloading::Library::new(&name) } {
+            Ok(library) => {
+                return unsafe { super::FunctionTable::load(library) }.map_err(|e| {
+                    format!(
+                    "{} was loaded but is missing an entry point, the driver may be too old: {e}",
+                    name.to_string_lossy()
+                )
+                })
+            }
+            Err(e) => failures.push(format!("{}: {e}", name.to_string_lossy())),
+        }
+    }
+    Err(format!(
+        "Could not load the NI-DAQmx library ({})",
+        failures.join("; ")
+    ))
+}
+
+/// Declares the runtime loaded DAQmx functions.
+///
+/// Generates the function table and a shim for each function which shadows the declaration
+/// from the bindings. The shims are checked against the bindings signatures at compile time.
+macro_rules! runtime_functions {
+    ($(fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty;)*) => {
+        pub(crate) struct FunctionTable {
+            _library: libloading::Library,
+            $($name: unsafe extern "C" fn($($ty),*) -> $ret,)*
+        }
+
+        impl FunctionTable {
+            /// # Safety
+            /// `library` must be the NI-DAQmx C API library so the symbols match the declared signatures.
+            unsafe fn load(library: libloading::Library) -> Result<Self, libloading::Error> {
+                $(
+                    let $name = *library.get::<unsafe extern "C" fn($($ty),*) -> $ret>(
+                        concat!(stringify!($name), "\0").as_bytes(),
+                    )?;
+                )*
+                Ok(Self { _library: library, $($name,)* })
+            }
+        }
+
+        $(
+            /// Runtime loaded shim, see [`crate::daqmx::runtime`].
+            ///
+            /// # Safety
+            /// Same requirements as the NI-DAQmx C function of the same name.
+            pub unsafe extern "C" fn $name($($arg: $ty),*) -> $ret {
+                const _: () = {
+                    let _matches_bindings: unsafe extern "C" fn($($ty),*) -> $ret = bindings::$name;
+                };
+                match runtime::functions() {
+                    Ok(functions) => (functions.$name)($($arg),*),
+                    Err(_) => runtime::DRIVER_NOT_LOADED,
+                }
+            }
+        )*
+    };
+}
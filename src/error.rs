@@ -1,49 +1,365 @@
 // This file contains code derived from the daqmx-rs project:
 // https://github.com/WiresmithTech/daqmx-rs
 
-use log::warn;
 /// Error handling types and functions.
 use thiserror::Error;
 
-use crate::{daqmx, types::buffer_to_string};
-use anyhow::Result as AnyResult;
+use crate::backend::Backend;
+use crate::daqmx;
+use crate::warnings::{handle_warning, TaskWarnings, Warning};
+
+/// The error type returned by the crate.
+///
+/// It implements [`std::error::Error`], so `?` converts it into an [`anyhow::Error`] for
+/// applications using `anyhow`, from which it can be recovered with
+/// [`anyhow::Error::downcast_ref`].
+pub type Error = DaqmxError;
+
+/// A [`Result`](std::result::Result) with the crate [`Error`](tyalias@Error).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Generates [`ErrorKind`] and its lookup from a table of kinds and the status codes they cover.
+macro_rules! error_kinds {
+    ($($(#[$meta:meta])* $kind:ident => [$($code:literal),+ $(,)?],)+) => {
+        /// Classification of well-known NI-DAQmx error codes.
+        ///
+        /// Codes not listed here are [`ErrorKind::Other`]; the raw code is always available from
+        /// [`DaqmxError::code`].
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ErrorKind {
+            $($(#[$meta])* $kind,)+
+            /// Any other error code.
+            Other,
+        }
+
+        impl ErrorKind {
+            /// The kind of a DAQmx error code.
+            pub fn from_code(code: i32) -> Self {
+                match code {
+                    $($($code)|+ => ErrorKind::$kind,)+
+                    _ => ErrorKind::Other,
+                }
+            }
+
+            /// The error codes of this kind.
+            pub fn codes(self) -> &'static [i32] {
+                match self {
+                    $(ErrorKind::$kind => &[$($code),+],)+
+                    ErrorKind::Other => &[],
+                }
+            }
+        }
+    };
+}
+
+error_kinds! {
+    /// A read, write or wait did not complete within its timeout.
+    Timeout => [
+        -200284, // Samples not yet available.
+        -200292, // Samples can not yet be written.
+        -200474, // Operation timed out.
+        -200560, // Wait until done does not indicate done.
+    ],
+    /// A resource needed by the task is reserved by another task.
+    ResourceReserved => [
+        -50103,  // The specified resource is reserved.
+        -200022, // Resource already reserved by a different task.
+    ],
+    /// The device overwrote data before it was transferred to the buffer.
+    BufferOverwritten => [
+        -200141, // Data was overwritten before it could be read by the system.
+        -200361, // Onboard device memory overflow.
+    ],
+    /// The application did not read fast enough and samples in the buffer were overwritten.
+    SamplesNoLongerAvailable => [-200279],
+    /// The read position is beyond the end of a finished finite acquisition.
+    SamplesWillNeverBeAvailable => [-200278],
+    /// An output ran out of data to generate.
+    OutputUnderflow => [
+        -200016, // Onboard device memory underflow.
+        -200018, // DAC conversion attempted before data was available.
+        -200290, // Generation stopped to prevent regeneration of old samples.
+    ],
+    /// The device does not exist or cannot be accessed.
+    DeviceNotFound => [
+        -200220, // Device identifier is invalid.
+        -201003, // Device cannot be accessed.
+    ],
+    /// A physical or virtual channel name is invalid for the device or task.
+    InvalidChannel => [
+        -200170, // Physical channel does not exist.
+        -200428, // Invalid channel.
+        -200489, // Duplicate channel name.
+    ],
+    /// A property was set to an unsupported value.
+    InvalidAttributeValue => [-200077],
+    /// A property is not supported by the device or doesn't apply to the task in its current
+    /// configuration.
+    PropertyNotSupported => [-200452],
+    /// A signal could not be routed between the requested terminals.
+    RouteFailed => [
+        -89120, // Source terminal not found.
+        -89121, // Destination terminal not found.
+        -89125, // No common trigger line for route.
+        -89136, // Route not supported by hardware.
+        -89137, // Route resources in use.
+    ],
+    /// The task handle or name is invalid.
+    InvalidTask => [
+        -200088, // Task specified is invalid or does not exist.
+        -200089, // Task name conflicts with an existing task.
+    ],
+    /// The operation is not allowed in the current state of the task, e.g. reading a stopped task.
+    InvalidTaskState => [
+        -200473, // Read of a task which is not running with auto start off.
+        -200477, // No channels in the task.
+        -200479, // Task is running.
+    ],
+    /// The buffer passed to a read is too small.
+    BufferTooSmall => [
+        -200228, // Buffer too small for string.
+        -200229, // Read buffer too small.
+    ],
+}
+
+impl ErrorKind {
+    /// Whether the operation can succeed when retried, possibly after restarting the task,
+    /// rather than indicating a configuration or programming error.
+    pub fn is_recoverable(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Timeout
+                | ErrorKind::ResourceReserved
+                | ErrorKind::BufferOverwritten
+                | ErrorKind::SamplesNoLongerAvailable
+                | ErrorKind::OutputUnderflow
+        )
+    }
+}
+
+/// Where a DAQmx error or warning was raised. It is included in the message so logs identify the
+/// failing call.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The NI-DAQmx function which failed, e.g. `DAQmxCfgSampClkTiming`.
+    pub operation: Option<&'static str>,
+    /// The name of the task.
+    pub task: Option<String>,
+    /// The name of the channel, for channel operations.
+    pub channel: Option<String>,
+}
+
+impl ErrorContext {
+    /// Context for an operation on the named task.
+    pub fn task(name: impl Into<String>) -> Self {
+        Self {
+            task: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Context for an operation on a task handle, looking up the task name from the backend.
+    pub(crate) fn for_task(backend: &dyn Backend, task: daqmx::TaskHandle) -> Self {
+        let mut name = String::new();
+        match unsafe { backend.task_name(task, &mut name) } {
+            0 => Self::task(name),
+            _ => Self::default(),
+        }
+    }
+
+    /// Names the channel the operation acted on.
+    pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    /// Sets the operation unless one is already set.
+    pub fn with_operation(mut self, operation: &'static str) -> Self {
+        self.operation.get_or_insert(operation);
+        self
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(operation) = self.operation {
+            write!(f, " in {operation}")?;
+        }
+        match (&self.task, &self.channel) {
+            (Some(task), Some(channel)) => write!(f, " (task \"{task}\", channel \"{channel}\")"),
+            (Some(task), None) => write!(f, " (task \"{task}\")"),
+            (None, Some(channel)) => write!(f, " (channel \"{channel}\")"),
+            (None, None) => Ok(()),
+        }
+    }
+}
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum DaqmxError {
-    /// A DAQmx Generated Error. The i32 is the return code and the string is the extended description.
-    #[error("DAQmx Generated Error: {1}")]
-    DaqmxError(i32, String),
+    /// A DAQmx Generated Error with its return code, classification, extended description and
+    /// where it was raised.
+    #[error("DAQmx Generated Error{context}: {message}")]
+    DaqmxError {
+        code: i32,
+        kind: ErrorKind,
+        message: String,
+        context: ErrorContext,
+    },
     #[error("String Value Not Valid for DAQmx API. Probably Contains Null")]
     CStringError(#[from] std::ffi::NulError),
     #[error("String value from DAQmx API does not contain valid Unicode (UTF8). This should not be possible and probably indicates corruption")]
     Utf8Error(#[from] std::string::FromUtf8Error),
     #[error("String property length changed between reading the required length and reading the value. This is likely a race condition with another piece of code and a retry will probably correct this.")]
     StringPropertyLengthChanged,
+    /// A value returned by DAQmx could not be converted to the given type.
     #[error("Value for given type ({0}) isn't a value that is expected: {1}")]
     UnexpectedValue(String, i32),
+    /// An argument was rejected before calling DAQmx.
+    #[error("Invalid argument: {0}")]
+    Validation(String),
+    /// An [`OutputStreamer`](crate::tasks::OutputStreamer) didn't keep the buffer of a
+    /// non-regenerating output topped up, so the generation stopped. Holds the samples per channel
+    /// written before it stopped and the error DAQmx reported.
+    #[error("Output underflow after {samples_written} samples per channel were written: {source}")]
+    OutputUnderflow {
+        samples_written: u64,
+        #[source]
+        source: Box<DaqmxError>,
+    },
+    /// The NI-DAQmx library could not be loaded at runtime. The string describes what was tried.
+    #[error("NI-DAQmx driver not installed: {0}")]
+    DriverNotInstalled(String),
+    /// Reading or writing a call recording failed.
+    #[cfg(feature = "recording")]
+    #[error("Recording error: {0}")]
+    Recording(String),
+}
+
+/// Channel builders report missing fields as validation errors.
+impl From<derive_builder::UninitializedFieldError> for DaqmxError {
+    fn from(error: derive_builder::UninitializedFieldError) -> Self {
+        DaqmxError::Validation(error.to_string())
+    }
+}
+
+impl DaqmxError {
+    /// A DAQmx generated error, classified by its code.
+    pub fn from_code(code: i32, message: String) -> Self {
+        DaqmxError::DaqmxError {
+            code,
+            kind: ErrorKind::from_code(code),
+            message,
+            context: ErrorContext::default(),
+        }
+    }
+
+    /// Where a driver generated error was raised.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            DaqmxError::DaqmxError { context, .. } => Some(context),
+            DaqmxError::OutputUnderflow { source, .. } => source.context(),
+            _ => None,
+        }
+    }
+
+    /// The DAQmx status code of a driver generated error.
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            DaqmxError::DaqmxError { code, .. } => Some(*code),
+            DaqmxError::OutputUnderflow { source, .. } => source.code(),
+            _ => None,
+        }
+    }
+
+    /// The kind of a driver generated error.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            DaqmxError::DaqmxError { kind, .. } => Some(*kind),
+            DaqmxError::OutputUnderflow { source, .. } => source.kind(),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a read, write or wait timeout.
+    pub fn is_timeout(&self) -> bool {
+        self.kind() == Some(ErrorKind::Timeout)
+    }
+
+    /// See [`ErrorKind::is_recoverable`].
+    pub fn is_recoverable(&self) -> bool {
+        self.kind().is_some_and(ErrorKind::is_recoverable)
+    }
+}
+
+/// Converts a DAQmx status code into a result, using `backend` to describe errors and warnings.
+///
+/// Warnings are handled by the [global warning policy](crate::warnings::global_warning_policy).
+pub fn handle_error(backend: &dyn Backend, return_code: i32) -> Result<()> {
+    handle_error_with(backend, return_code, ErrorContext::default)
+}
+
+/// Like [`handle_error`], adding the context returned by `context` to errors and warnings.
+/// `context` is only called if `return_code` isn't a success.
+pub fn handle_error_with(
+    backend: &dyn Backend,
+    return_code: i32,
+    context: impl FnOnce() -> ErrorContext,
+) -> Result<()> {
+    handle_status(backend, return_code, context, None)
 }
 
-pub fn handle_error(return_code: i32) -> AnyResult<()> {
+/// Converts the status a task reports to an event callback. The extended error info only belongs
+/// to calls made on the same thread, so errors are described by their code instead.
+pub(crate) fn handle_event_status(
+    backend: &dyn Backend,
+    status: i32,
+    context: impl FnOnce() -> ErrorContext,
+    warnings: &TaskWarnings,
+) -> Result<()> {
+    if status < 0 {
+        return Err(DaqmxError::DaqmxError {
+            code: status,
+            kind: ErrorKind::from_code(status),
+            message: backend.error_string(status),
+            context: context(),
+        });
+    }
+    handle_status(backend, status, context, warnings)
+}
+
+/// Like [`handle_error_with`], applying the warning policy of `warnings` rather than the global one.
+pub(crate) fn handle_status<'a>(
+    backend: &dyn Backend,
+    return_code: i32,
+    context: impl FnOnce() -> ErrorContext,
+    warnings: impl Into<Option<&'a TaskWarnings>>,
+) -> Result<()> {
+    #[cfg(feature = "runtime-loading")]
+    if return_code == daqmx::runtime::DRIVER_NOT_LOADED {
+        daqmx::ensure_loaded()?;
+    }
+
     match return_code {
         0 => Ok(()), // Do nothing if no error.
         i32::MIN..=-1 => {
-            // Use extended info for errors.
-            unsafe {
-                let mut buffer = vec![0i8; 2048];
-                daqmx::DAQmxGetExtendedErrorInfo(buffer.as_mut_ptr(), 2048);
-                let message = buffer_to_string(buffer);
-                Err(DaqmxError::DaqmxError(return_code, message).into())
-            }
+            // Use extended info for errors. Read it before the context, which may call the driver.
+            let message = backend.extended_error_info();
+            Err(DaqmxError::DaqmxError {
+                code: return_code,
+                kind: ErrorKind::from_code(return_code),
+                message,
+                context: context(),
+            })
         }
         1..=i32::MAX => {
-            // Use error string for warning. Just report to log.
-            unsafe {
-                let mut buffer = vec![0i8; 2048];
-                daqmx::DAQmxGetErrorString(return_code, buffer.as_mut_ptr(), 2048);
-                let message = buffer_to_string(buffer);
-                warn!("DAQmx Warning: {}", message);
-            }
-            Ok(())
+            // Use error string for warning, then let the warning policy decide.
+            let warning = Warning {
+                code: return_code,
+                message: backend.error_string(return_code),
+                context: context(),
+            };
+            handle_warning(warning, warnings.into())
         }
     }
 }
@@ -53,7 +369,7 @@ pub fn handle_error(return_code: i32) -> AnyResult<()> {
 /// * Errors if it is an unexpected error.
 /// * Returns `true` if there is a size error.
 /// * Returns `false` if there is no error.
-pub fn string_property_size_error(return_code: i32) -> AnyResult<bool> {
+pub fn string_property_size_error(backend: &dyn Backend, return_code: i32) -> Result<bool> {
     const TRUNCATED_WARNING: i32 = daqmx::DAQmxWarningCAPIStringTruncatedToFitBuffer;
     match return_code {
         daqmx::DAQmxErrorBufferTooSmallForString | TRUNCATED_WARNING => {
@@ -62,7 +378,7 @@ pub fn string_property_size_error(return_code: i32) -> AnyResult<bool> {
         }
         //Given we know this rante of codes provides an error, the map should never be called.
         //Just used to satisfy the type system.
-        i32::MIN..=-1 => handle_error(return_code).map(|()| false),
+        i32::MIN..=-1 => handle_error(backend, return_code).map(|()| false),
         _ => Ok(false),
     }
 }
@@ -70,24 +386,93 @@ pub fn string_property_size_error(return_code: i32) -> AnyResult<bool> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::backend::SimulatedBackend;
 
     #[test]
     fn test_string_property_size_error() {
-        assert!(!string_property_size_error(0).unwrap());
-        assert!(string_property_size_error(daqmx::DAQmxErrorBufferTooSmallForString).unwrap());
+        let backend = SimulatedBackend::new();
+        assert!(!string_property_size_error(&backend, 0).unwrap());
         assert!(
-            string_property_size_error(daqmx::DAQmxWarningCAPIStringTruncatedToFitBuffer).unwrap()
+            string_property_size_error(&backend, daqmx::DAQmxErrorBufferTooSmallForString).unwrap()
         );
+        assert!(string_property_size_error(
+            &backend,
+            daqmx::DAQmxWarningCAPIStringTruncatedToFitBuffer
+        )
+        .unwrap());
 
-        match string_property_size_error(-1000) {
-            Err(e) => {
-                if let Some(DaqmxError::DaqmxError(code, _)) = e.downcast_ref::<DaqmxError>() {
-                    assert_eq!(*code, -1000);
-                } else {
-                    panic!("Expected DaqmxError::DaqmxError");
-                }
+        match string_property_size_error(&backend, -1000) {
+            Err(DaqmxError::DaqmxError { code, kind, .. }) => {
+                assert_eq!(code, -1000);
+                assert_eq!(kind, ErrorKind::Other);
             }
+            Err(e) => panic!("Expected DaqmxError::DaqmxError, got {e:?}"),
             Ok(_) => panic!("Expected an error"),
         }
     }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(ErrorKind::from_code(-200284), ErrorKind::Timeout);
+        assert_eq!(ErrorKind::from_code(-50103), ErrorKind::ResourceReserved);
+        assert_eq!(
+            ErrorKind::from_code(-200077),
+            ErrorKind::InvalidAttributeValue
+        );
+        assert_eq!(
+            ErrorKind::from_code(-200452),
+            ErrorKind::PropertyNotSupported
+        );
+        assert_eq!(ErrorKind::from_code(-1), ErrorKind::Other);
+        assert!(ErrorKind::Timeout.codes().contains(&-200560));
+
+        let error = DaqmxError::from_code(-200279, String::new());
+        assert_eq!(error.code(), Some(-200279));
+        assert!(!error.is_timeout());
+        assert!(error.is_recoverable());
+        assert!(!DaqmxError::from_code(-200220, String::new()).is_recoverable());
+        assert_eq!(DaqmxError::StringPropertyLengthChanged.kind(), None);
+    }
+
+    #[test]
+    fn test_error_context_message() {
+        let backend = SimulatedBackend::new();
+        let error = handle_error_with(&backend, -200077, || {
+            ErrorContext::task("ai")
+                .with_channel("ai0")
+                .with_operation("DAQmxGetChanAttribute")
+        })
+        .unwrap_err();
+        assert!(error.to_string().starts_with(
+            "DAQmx Generated Error in DAQmxGetChanAttribute (task \"ai\", channel \"ai0\"): "
+        ));
+        assert_eq!(error.context().unwrap().task.as_deref(), Some("ai"));
+    }
+
+    #[test]
+    fn test_task_warning_policy() {
+        let backend = SimulatedBackend::new();
+        let warnings = TaskWarnings::default();
+        warnings.set_policy(Some(crate::warnings::WarningPolicy::collect()));
+
+        handle_status(&backend, 200010, || ErrorContext::task("ai"), &warnings).unwrap();
+
+        let collected = warnings.take();
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].code, 200010);
+        assert_eq!(collected[0].context, ErrorContext::task("ai"));
+    }
+
+    #[test]
+    fn test_event_status_error() {
+        let backend = SimulatedBackend::new();
+        let warnings = TaskWarnings::default();
+
+        let error = handle_event_status(&backend, -200279, || ErrorContext::task("ai"), &warnings)
+            .unwrap_err();
+
+        assert_eq!(error.kind(), Some(ErrorKind::SamplesNoLongerAvailable));
+        assert_eq!(error.context(), Some(&ErrorContext::task("ai")));
+        assert!(error.to_string().contains("not able to keep up"));
+    }
 }
@@ -27,25 +27,61 @@
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 
-pub mod daqmx {
-    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
-}
-
+pub mod backend;
 pub mod channels;
+pub mod daqmx;
 pub mod error;
 pub mod scales;
 pub mod tasks;
 pub mod types;
 pub mod utils;
+pub mod warnings;
+pub mod waveforms;
 
 pub use channels::*;
+pub use error::Error;
 pub use tasks::*;
 pub use types::*;
 pub use utils::*;
 
+/// Calls a [`backend::Backend`] method and converts the returned status into a [`Result`](error::Result).
+///
+/// `daqmx_call!(backend, start_task(handle))` where `backend` is a `&dyn Backend`.
+/// Errors name the NI-DAQmx function called. An [`error::ErrorContext`] can be passed after the call,
+/// `daqmx_call!(backend, start_task(handle), context)`; it is only evaluated if the call fails.
+/// Warnings follow the global [`warnings::WarningPolicy`], or that of the
+/// [`warnings::TaskWarnings`] passed after the context.
 #[macro_export]
 macro_rules! daqmx_call {
-    ($l:expr) => {
-        $crate::error::handle_error(unsafe { $l })
+    ($backend:expr, $method:ident($($arg:expr),* $(,)?) $(, $context:expr $(, $warnings:expr)?)?) => {
+        // `match` keeps a temporary backend (e.g. from `default_backend()`) alive for the call.
+        match $backend {
+            backend => {
+                let backend: &dyn $crate::backend::Backend = backend;
+                #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
+                let return_code = unsafe { backend.$method($($arg),*) };
+                $crate::error::handle_status(
+                    backend,
+                    return_code,
+                    || {
+                        $crate::daqmx_call!(@context $($context)?)
+                            .with_operation($crate::backend::function_name(stringify!($method)))
+                    },
+                    $crate::daqmx_call!(@warnings $($($warnings)?)?),
+                )
+            }
+        }
+    };
+    (@context) => {
+        $crate::error::ErrorContext::default()
+    };
+    (@context $context:expr) => {
+        $context
+    };
+    (@warnings) => {
+        None::<&$crate::warnings::TaskWarnings>
+    };
+    (@warnings $warnings:expr) => {
+        $warnings
     };
 }
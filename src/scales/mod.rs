@@ -10,8 +10,9 @@ use crate::daqmx::*;
 use num::FromPrimitive;
 use num_derive::FromPrimitive;
 
+use crate::backend::default_backend;
+use crate::error::Result;
 use crate::{daqmx_call, error::DaqmxError};
-use anyhow::Result;
 
 /// The custom scale type encapsulates common custom scale functions used by all scale types.
 ///
@@ -34,6 +35,7 @@ pub struct LinearScale {
 }
 
 impl LinearScale {
+    /// Creates the scale on the [`crate::backend::default_backend`].
     pub fn new(
         name: &str,
         slope: f64,
@@ -42,13 +44,17 @@ impl LinearScale {
         scaled_units: &str,
     ) -> Result<Self> {
         let name = CString::new(name)?;
-        daqmx_call!(DAQmxCreateLinScale(
-            name.as_ptr(),
-            slope,
-            y_intercept,
-            pre_scaled_units as i32,
-            CString::new(scaled_units)?.as_ptr()
-        ))?;
+        let scaled_units = CString::new(scaled_units)?;
+        daqmx_call!(
+            default_backend().as_ref(),
+            create_lin_scale(
+                &name,
+                slope,
+                y_intercept,
+                pre_scaled_units as i32,
+                &scaled_units
+            )
+        )?;
         Ok(Self {
             inner: CustomScale::new(name),
         })
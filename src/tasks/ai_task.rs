@@ -1,13 +1,19 @@
 // This file contains code derived from the daqmx-rs project:
 // https://github.com/WiresmithTech/daqmx-rs
 
-use crate::channels::{AnalogChannelBuilderTrait, AnalogChannelTrait, ChannelBuilderInput};
-use crate::types::Timeout;
+use crate::backend::Backend;
+use crate::channels::{
+    AnalogChannelBuilderTrait, AnalogChannelTrait, ChannelBuilderInput, VoltageChannelBase,
+};
+use crate::error::{ErrorContext, Result};
+use crate::types::{DataFillMode, Timeout};
+use crate::warnings::TaskWarnings;
 use crate::{daqmx, daqmx_call};
-use anyhow::Result;
-use std::ptr;
 
-use super::input::{DAQmxInput, InputTask};
+use super::frame::Frame;
+use super::input::{DAQmxInput, InputTask, ReadConfig};
+use super::stream::SampleStream;
+use super::waveform::Waveform;
 use super::{task::AnalogInput, Task};
 
 impl Task<AnalogInput> {
@@ -15,7 +21,9 @@ impl Task<AnalogInput> {
         &mut self,
         builder: B,
     ) -> Result<()> {
-        unsafe { <B as ChannelBuilderInput>::add_to_task(builder, self.raw_handle())? };
+        unsafe {
+            <B as ChannelBuilderInput>::add_to_task(builder, self.backend(), self.raw_handle())?
+        };
         self.channel_count += 1;
         Ok(())
     }
@@ -23,22 +31,105 @@ impl Task<AnalogInput> {
     pub fn get_channel<C: AnalogChannelTrait<AnalogInput>>(&self, name: &str) -> Result<C> {
         C::new(self.clone(), name)
     }
+
+    /// Reads the running task in chunks of `samples_per_chunk` samples per channel.
+    /// See [`SampleStream`].
+    pub fn stream(&self, samples_per_chunk: u32) -> Result<SampleStream<AnalogInput, f64>> {
+        SampleStream::new(self, samples_per_chunk, self.num_channels()?)
+    }
+
+    /// Sets the input buffer size, overwrite behaviour and read position. See [`ReadConfig`].
+    pub fn configure_read(&mut self, config: &ReadConfig) -> Result<()> {
+        self.apply_read_config(config)
+    }
+
+    /// The current input buffer size, overwrite behaviour and read position.
+    pub fn read_config(&self) -> Result<ReadConfig> {
+        self.current_read_config()
+    }
+
+    /// Reads `samples_per_channel` samples per channel into a [`Frame`], which knows the channel
+    /// names and the layout of the data.
+    pub fn read_frame(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: u32,
+    ) -> Result<Frame<f64>> {
+        self.read_into_frame(timeout, fill_mode, samples_per_channel)
+    }
+
+    /// Reads `samples_per_channel` samples per channel as a [`Waveform`] for each channel, timed
+    /// by the sample clock of the task.
+    ///
+    /// `t0` is set if the first sample was timestamped, see
+    /// [`Task::set_first_sample_timestamp_enabled`].
+    pub fn read_waveforms(
+        &mut self,
+        timeout: Timeout,
+        samples_per_channel: u32,
+    ) -> Result<Vec<Waveform<f64>>> {
+        let dt = self.sample_interval()?;
+        let frame = self.read_into_frame::<f64>(
+            timeout,
+            DataFillMode::GroupByChannel,
+            samples_per_channel,
+        )?;
+        // Found after the read, as the read may have started the task and may not start at the
+        // previous read position, see [`ReadConfig`].
+        let position = self
+            .read_position()?
+            .saturating_sub(frame.samples_per_channel() as u64);
+        let t0 = self.sample_timestamp(position, dt)?;
+
+        let mut waveforms = Vec::with_capacity(frame.num_channels());
+        for (index, channel) in frame.channel_names().iter().enumerate() {
+            let units = self
+                .get_channel::<VoltageChannelBase<AnalogInput>>(channel)?
+                .units()?;
+            waveforms.push(Waveform {
+                channel: channel.clone(),
+                units,
+                t0,
+                dt,
+                samples: frame.channel_slice(index).unwrap_or_default().to_vec(),
+            });
+        }
+        Ok(waveforms)
+    }
 }
 
 impl InputTask<f64> for Task<AnalogInput> {
     fn read_scalar(&mut self, timeout: Timeout) -> Result<f64> {
         let mut value = 0.0;
-        daqmx_call!(daqmx::DAQmxReadAnalogScalarF64(
-            self.raw_handle(),
-            timeout.into(),
-            &mut value,
-            ptr::null_mut(),
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            read_analog_scalar_f64(self.raw_handle(), timeout.into(), &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(value)
     }
 }
 
 impl DAQmxInput<f64> for Task<AnalogInput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn read_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("read_analog_f64"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
     unsafe fn daqmx_read(
         &mut self,
         samples_per_channel: i32,
@@ -48,15 +139,13 @@ impl DAQmxInput<f64> for Task<AnalogInput> {
         buffer_size: u32,
         actual_samples_per_channel: *mut i32,
     ) -> i32 {
-        daqmx::DAQmxReadAnalogF64(
+        Task::backend(self).read_analog_f64(
             self.raw_handle(),
             samples_per_channel,
             timeout,
             fill_mode,
-            buffer.as_mut_ptr(),
-            buffer_size,
-            actual_samples_per_channel,
-            ptr::null_mut(),
+            &mut buffer[..buffer_size as usize],
+            &mut *actual_samples_per_channel,
         )
     }
 }
@@ -1,10 +1,13 @@
+use crate::backend::Backend;
 use crate::channels::{AnalogChannelBuilderTrait, AnalogChannelTrait, ChannelBuilderOutput};
-use crate::types::Timeout;
+use crate::error::{DaqmxError, ErrorContext, Result};
+use crate::types::{DataFillMode, Timeout};
+use crate::warnings::TaskWarnings;
+use crate::waveforms::{combine, Render};
 use crate::{daqmx, daqmx_call};
-use anyhow::Result;
-use std::ptr;
 
-use super::output::{DAQmxOutput, OutputTask};
+use super::output::{DAQmxOutput, OutputTask, WriteConfig};
+use super::output_stream::OutputStreamer;
 use super::{task::AnalogOutput, Task};
 
 impl Task<AnalogOutput> {
@@ -12,7 +15,9 @@ impl Task<AnalogOutput> {
         &mut self,
         builder: B,
     ) -> Result<()> {
-        unsafe { <B as ChannelBuilderOutput>::add_to_task(builder, self.raw_handle())? };
+        unsafe {
+            <B as ChannelBuilderOutput>::add_to_task(builder, self.backend(), self.raw_handle())?
+        };
         self.channel_count += 1;
         Ok(())
     }
@@ -20,41 +25,100 @@ impl Task<AnalogOutput> {
     pub fn get_channel<C: AnalogChannelTrait<AnalogOutput>>(&self, name: &str) -> Result<C> {
         C::new(self.clone(), name)
     }
+
+    /// Sets the output buffer size, regeneration and write position. See [`WriteConfig`].
+    pub fn configure_write(&mut self, config: &WriteConfig) -> Result<()> {
+        self.apply_write_config(config)
+    }
+
+    /// The current output buffer size, regeneration and write position.
+    pub fn write_config(&self) -> Result<WriteConfig> {
+        self.current_write_config()
+    }
+
+    /// Streams the chunks of `producer` to the task without regeneration. See [`OutputStreamer`].
+    pub fn streamer<P: IntoIterator<Item = Vec<f64>>>(
+        &self,
+        producer: P,
+    ) -> Result<OutputStreamer<AnalogOutput, f64, P::IntoIter>> {
+        OutputStreamer::new(self, producer.into_iter(), self.num_channels()?)
+    }
+
+    /// Renders one signal per channel of the task at its sample clock rate, laid out as
+    /// `fill_mode` for a write. See [`combine`].
+    pub fn render_signals(
+        &self,
+        signals: &[&dyn Render],
+        fill_mode: DataFillMode,
+    ) -> Result<Vec<f64>> {
+        let channels = self.num_channels()?;
+        if signals.len() != channels {
+            return Err(DaqmxError::Validation(format!(
+                "{} signals can't be rendered for {channels} channels.",
+                signals.len()
+            )));
+        }
+        let sample_rate = self.sample_clock_rate()?;
+        let buffers = signals
+            .iter()
+            .map(|signal| signal.render(sample_rate))
+            .collect::<Result<Vec<_>>>()?;
+        combine(&buffers, fill_mode)
+    }
 }
 
 impl OutputTask<f64> for Task<AnalogOutput> {
     /// Autostart is always true for analog output tasks.
     fn write_scalar(&mut self, value: f64, timeout: Timeout) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxWriteAnalogScalarF64(
-            self.raw_handle(),
-            1,
-            timeout.into(),
-            value,
-            ptr::null_mut()
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            write_analog_scalar_f64(self.raw_handle(), true, timeout.into(), value),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(())
     }
 }
 
 impl DAQmxOutput<f64> for Task<AnalogOutput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn write_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("write_analog_f64"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
+    fn elements_per_sample(&self) -> Result<usize> {
+        self.num_channels()
+    }
+
     unsafe fn daqmx_write(
         &mut self,
         samples_per_channel: i32,
         auto_start: daqmx::bool32,
         timeout: f64,
         fill_mode: daqmx::bool32,
-        buffer: *const f64,
+        buffer: &[f64],
         actual_samples_per_channel: *mut i32,
     ) -> i32 {
-        daqmx::DAQmxWriteAnalogF64(
+        Task::backend(self).write_analog_f64(
             self.raw_handle(),
             samples_per_channel,
-            auto_start,
+            auto_start != 0,
             timeout,
             fill_mode,
             buffer,
-            actual_samples_per_channel,
-            ptr::null_mut(),
+            &mut *actual_samples_per_channel,
         )
     }
 }
@@ -0,0 +1,538 @@
+//! Futures for reading and writing without blocking an async executor, enabled by the `async`
+//! feature.
+//!
+//! The futures don't depend on a runtime. They share one background thread, which holds a clone
+//! of the task for each pending read or write and wakes the executor once it completes.
+//!
+//! A read waits for its samples to be available, then reads them without waiting in the driver.
+//! A write waits for space in the output buffer, then writes without waiting in the driver. The
+//! thread checks again about when the missing samples are due at the sample clock rate, and
+//! sleeps while nothing is pending. Dropping the future stops the wait whatever the timeout, so
+//! the thread releases its clone of the task. The task is cleared when the last clone is dropped,
+//! whether that is yours or the thread's.
+//!
+//! [`SampleStream::into_async`] reads a [`SampleStream`] the same way, as a [`Stream`].
+//!
+//! ```no_run
+//! use daqmx::channels::VoltageChannel;
+//! use daqmx::tasks::{AnalogInput, Task};
+//! use daqmx::types::{ClockEdge, DataFillMode, SampleMode, Timeout};
+//!
+//! # async fn acquire() -> daqmx::error::Result<()> {
+//! let mut task = Task::<AnalogInput>::new("async")?;
+//! task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+//! task.configure_sample_clock_timing(
+//!     None,
+//!     1000.0,
+//!     ClockEdge::Rising,
+//!     SampleMode::ContinuousSamples,
+//!     1000,
+//! )?;
+//! task.start()?;
+//!
+//! let (buffer, samples) = task
+//!     .read_async(
+//!         Timeout::WaitForever,
+//!         DataFillMode::GroupByChannel,
+//!         Some(100),
+//!         vec![0.0; 100],
+//!     )
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Once};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+
+use super::output::{DAQmxOutput, OutputTask};
+use super::{InputTask, SampleStream, Task};
+use crate::backend::TaskProperty;
+use crate::error::{DaqmxError, Result};
+use crate::types::{DataFillMode, SampleMode, Timeout};
+
+/// The shortest time between checks of a pending read or write.
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The longest time between checks, also used when the sample clock rate is unknown.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A read or write pending on the background thread. Returned by [`Task::read_async`] and
+/// [`Task::write_async`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TaskFuture<R> {
+    shared: Arc<Shared<R>>,
+}
+
+struct Shared<R> {
+    state: Mutex<FutureState<R>>,
+    /// Set when the future is dropped.
+    cancelled: AtomicBool,
+}
+
+struct FutureState<R> {
+    result: Option<R>,
+    /// Set when the call panicked, so the future never completes.
+    abandoned: bool,
+    waker: Option<Waker>,
+}
+
+impl<R> Shared<R> {
+    fn complete(&self, result: Option<R>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match result {
+            Some(result) => state.result = Some(result),
+            None => state.abandoned = true,
+        }
+        let waker = state.waker.take();
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<R: Send + 'static> TaskFuture<R> {
+    /// Schedules `check` on the background thread, which calls it until it is done or the
+    /// future is dropped.
+    fn schedule(check: impl FnMut() -> Step<R> + Send + 'static) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(FutureState {
+                result: None,
+                abandoned: false,
+                waker: None,
+            }),
+            cancelled: AtomicBool::new(false),
+        });
+        REACTOR.schedule(Box::new(Pending {
+            shared: shared.clone(),
+            check,
+            done: false,
+        }));
+        Self { shared }
+    }
+}
+
+impl<R> Future for TaskFuture<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(result) = state.result.take() {
+            return Poll::Ready(result);
+        }
+        if state.abandoned {
+            panic!("an async DAQmx read or write panicked");
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<R> Drop for TaskFuture<R> {
+    fn drop(&mut self) {
+        self.shared.cancelled.store(true, Ordering::Relaxed);
+        // Release the task right away instead of at the next check.
+        REACTOR.wake();
+    }
+}
+
+/// What a check of a pending read or write found.
+enum Step<R> {
+    /// Check again after this long.
+    Wait(Duration),
+    Done(R),
+}
+
+/// A read or write checked by the background thread.
+trait Job: Send {
+    fn cancelled(&self) -> bool;
+
+    /// Checks once, completing the future when done. Returns how long to wait before checking
+    /// again, or [`None`] once done.
+    fn check(&mut self) -> Option<Duration>;
+}
+
+struct Pending<R, F> {
+    shared: Arc<Shared<R>>,
+    check: F,
+    done: bool,
+}
+
+impl<R: Send, F: FnMut() -> Step<R> + Send> Job for Pending<R, F> {
+    fn cancelled(&self) -> bool {
+        self.shared.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn check(&mut self) -> Option<Duration> {
+        match (self.check)() {
+            Step::Wait(delay) => Some(delay),
+            Step::Done(result) => {
+                self.done = true;
+                self.shared.complete(Some(result));
+                None
+            }
+        }
+    }
+}
+
+impl<R, F> Drop for Pending<R, F> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.shared.complete(None);
+        }
+    }
+}
+
+/// The background thread and the reads and writes it checks.
+struct Reactor {
+    jobs: Mutex<Vec<Scheduled>>,
+    wakeup: Condvar,
+}
+
+struct Scheduled {
+    at: Instant,
+    job: Box<dyn Job>,
+}
+
+static REACTOR: Reactor = Reactor {
+    jobs: Mutex::new(Vec::new()),
+    wakeup: Condvar::new(),
+};
+
+impl Reactor {
+    /// Checks `job` right away, starting the thread on first use.
+    fn schedule(&'static self, job: Box<dyn Job>) {
+        static STARTED: Once = Once::new();
+        STARTED.call_once(|| {
+            thread::Builder::new()
+                .name("daqmx-async".to_string())
+                .spawn(|| self.run())
+                .expect("failed to start the thread of async DAQmx reads and writes");
+        });
+        self.lock().push(Scheduled {
+            at: Instant::now(),
+            job,
+        });
+        self.wakeup.notify_one();
+    }
+
+    /// Makes the thread look at its jobs, e.g. to drop a cancelled one.
+    fn wake(&self) {
+        // Taking the lock makes sure the thread isn't between checking and waiting.
+        let _jobs = self.lock();
+        self.wakeup.notify_one();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Scheduled>> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn run(&self) -> ! {
+        let mut jobs = self.lock();
+        loop {
+            let now = Instant::now();
+            let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut *jobs)
+                .into_iter()
+                .partition(|scheduled| scheduled.at <= now || scheduled.job.cancelled());
+            *jobs = waiting;
+            if ready.is_empty() {
+                jobs = match jobs.iter().map(|scheduled| scheduled.at).min() {
+                    Some(at) => {
+                        self.wakeup
+                            .wait_timeout(jobs, at - now)
+                            .unwrap_or_else(|e| e.into_inner())
+                            .0
+                    }
+                    None => self.wakeup.wait(jobs).unwrap_or_else(|e| e.into_inner()),
+                };
+                continue;
+            }
+
+            // Check and drop jobs without the lock, so futures can be created and dropped
+            // meanwhile.
+            drop(jobs);
+            let mut rescheduled = Vec::new();
+            for Scheduled { mut job, .. } in ready {
+                if job.cancelled() {
+                    continue;
+                }
+                // A job which panicked is dropped, which makes its future panic too.
+                if let Ok(Some(delay)) = panic::catch_unwind(AssertUnwindSafe(|| job.check())) {
+                    rescheduled.push(Scheduled {
+                        at: Instant::now() + delay,
+                        job,
+                    });
+                }
+            }
+            jobs = self.lock();
+            jobs.extend(rescheduled);
+        }
+    }
+}
+
+impl<TYPE: Send + 'static> Task<TYPE> {
+    /// Reads like [`InputTask::read`] without blocking the caller, returning the buffer along
+    /// with the number of samples read per channel.
+    ///
+    /// Dropping the future before it completes cancels the read. Without
+    /// `samples_per_channel`, a finite task waits until it is done and a continuous one reads the
+    /// samples available.
+    pub fn read_async<T>(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: Option<u32>,
+        mut buffer: Vec<T>,
+    ) -> TaskFuture<Result<(Vec<T>, i32)>>
+    where
+        Self: InputTask<T> + Clone,
+        T: Send + 'static,
+    {
+        let mut task = self.clone();
+        let mut wait = SampleWait::new(samples_per_channel, timeout);
+        TaskFuture::schedule(move || {
+            if let Some(delay) = wait.check::<TYPE, T>(&mut task) {
+                return Step::Wait(delay);
+            }
+            // Everything the read can get is available by now.
+            let read = task.read(Timeout::NoWait, fill_mode, samples_per_channel, &mut buffer);
+            Step::Done(read.map(|samples| (std::mem::take(&mut buffer), samples)))
+        })
+    }
+
+    /// Writes like [`OutputTask::write`] without blocking the caller.
+    ///
+    /// Dropping the future before it completes cancels the write. While the task runs, the
+    /// samples must fit in its output buffer.
+    pub fn write_async<T>(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: Option<u32>,
+        buffer: Vec<T>,
+    ) -> TaskFuture<Result<i32>>
+    where
+        Self: OutputTask<T> + Clone,
+        T: Send + 'static,
+    {
+        let mut task = self.clone();
+        let deadline = deadline(timeout);
+        let mut rate = None;
+        TaskFuture::schedule(move || {
+            let samples = match samples_per_channel {
+                Some(samples) => Ok(samples as usize),
+                None => DAQmxOutput::<T>::elements_per_sample(&task)
+                    .map(|elements| buffer.len() / elements.max(1)),
+            };
+            match samples
+                .and_then(|samples| task.write_space_wait::<T>(samples as u64, deadline, &mut rate))
+            {
+                Ok(Some(delay)) => return Step::Wait(delay),
+                Ok(None) => {}
+                Err(error) => return Step::Done(Err(error)),
+            }
+            // The samples fit in the buffer by now, or the timeout has passed.
+            Step::Done(task.write(Timeout::NoWait, fill_mode, samples_per_channel, &buffer))
+        })
+    }
+
+    /// How long to wait before checking again for space to write `samples` samples per
+    /// channel, or [`None`] once they fit, the task isn't running or the deadline passed.
+    ///
+    /// Fails if the samples can never fit in the buffer of the running task. Other errors
+    /// checking the task stop the wait, leaving the write to report them.
+    fn write_space_wait<T>(
+        &mut self,
+        samples: u64,
+        deadline: Option<Instant>,
+        rate: &mut Option<Option<f64>>,
+    ) -> Result<Option<Duration>>
+    where
+        Self: OutputTask<T>,
+    {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            || self.is_done().unwrap_or(true)
+        {
+            return Ok(None);
+        }
+        let Ok(space) = OutputTask::<T>::write_space_available(self) else {
+            return Ok(None);
+        };
+        if u64::from(space) >= samples {
+            return Ok(None);
+        }
+        let buffer_size = self
+            .read_task_property::<u32>(TaskProperty::OutputBufferSize)
+            .map_or(0, u64::from);
+        if samples > buffer_size {
+            return Err(DaqmxError::Validation(format!(
+                "A write of {samples} samples per channel doesn't fit in the output buffer of \
+                 {buffer_size} samples per channel."
+            )));
+        }
+        let rate = *rate.get_or_insert_with(|| self.sample_clock_rate().ok());
+        Ok(Some(check_interval(
+            Some(samples - u64::from(space)),
+            rate,
+            deadline,
+        )))
+    }
+}
+
+/// A wait for samples to read, checked without blocking.
+pub(crate) struct SampleWait {
+    samples: Option<u32>,
+    deadline: Option<Instant>,
+    /// The sample clock rate, once read.
+    rate: Option<Option<f64>>,
+}
+
+impl SampleWait {
+    /// Waits for `samples` samples per channel until `timeout`, which starts now.
+    pub(crate) fn new(samples: Option<u32>, timeout: Timeout) -> Self {
+        Self {
+            samples,
+            deadline: deadline(timeout),
+            rate: None,
+        }
+    }
+
+    /// How long to wait before checking again, or [`None`] once the samples are available, the
+    /// task is done or the deadline passed.
+    ///
+    /// Without `samples`, only a finite task waits, for the rest of its samples. Errors checking
+    /// the task stop the wait, leaving the read to report them.
+    pub(crate) fn check<TYPE, T>(&mut self, task: &mut Task<TYPE>) -> Option<Duration>
+    where
+        Task<TYPE>: InputTask<T>,
+    {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return None;
+        }
+        let missing = match self.samples {
+            Some(samples) => match InputTask::<T>::available_samples_per_channel(task) {
+                Ok(available) if available < samples => Some(u64::from(samples - available)),
+                _ => return None,
+            },
+            None if matches!(task.sample_mode(), Ok(SampleMode::FiniteSamples)) => None,
+            None => return None,
+        };
+        if task.is_done().unwrap_or(true) {
+            return None;
+        }
+        let rate = *self
+            .rate
+            .get_or_insert_with(|| task.sample_clock_rate().ok());
+        Some(check_interval(missing, rate, self.deadline))
+    }
+}
+
+/// About when `missing` samples are due at `rate`, no later than the deadline.
+fn check_interval(missing: Option<u64>, rate: Option<f64>, deadline: Option<Instant>) -> Duration {
+    let due = match (missing, rate) {
+        (Some(missing), Some(rate)) => Duration::try_from_secs_f64(missing as f64 / rate).ok(),
+        _ => None,
+    };
+    let interval = due
+        .unwrap_or(MAX_CHECK_INTERVAL)
+        .clamp(MIN_CHECK_INTERVAL, MAX_CHECK_INTERVAL);
+    match deadline {
+        Some(deadline) => interval.min(deadline.saturating_duration_since(Instant::now())),
+        None => interval,
+    }
+}
+
+/// A [`SampleStream`] read on the background thread as a [`Stream`]. Created by
+/// [`SampleStream::into_async`].
+///
+/// Each chunk is read like [`Task::read_async`], so dropping the stream while it waits for a
+/// chunk releases the task.
+pub struct AsyncSampleStream<TYPE, T> {
+    /// The stream between reads, or [`None`] while a read is pending or once it ended.
+    stream: Option<SampleStream<TYPE, T>>,
+    pending: Option<TaskFuture<NextChunk<TYPE, T>>>,
+}
+
+/// The stream handed back by the background thread with the chunk it read.
+type NextChunk<TYPE, T> = (SampleStream<TYPE, T>, Option<Result<Vec<T>>>);
+// The fields are never pinned.
+impl<TYPE, T> Unpin for AsyncSampleStream<TYPE, T> {}
+
+impl<TYPE, T> SampleStream<TYPE, T>
+where
+    TYPE: Send + 'static,
+    T: Clone + Default + Send + 'static,
+    Task<TYPE>: InputTask<T> + Clone,
+{
+    /// Reads the stream without blocking the executor.
+    pub fn into_async(self) -> AsyncSampleStream<TYPE, T> {
+        AsyncSampleStream {
+            stream: Some(self),
+            pending: None,
+        }
+    }
+}
+
+impl<TYPE, T> Stream for AsyncSampleStream<TYPE, T>
+where
+    TYPE: Send + 'static,
+    T: Clone + Default + Send + 'static,
+    Task<TYPE>: InputTask<T> + Clone,
+{
+    type Item = Result<Vec<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let pending = match &mut this.pending {
+            Some(pending) => pending,
+            None => {
+                let Some(stream) = this.stream.take() else {
+                    return Poll::Ready(None);
+                };
+                let mut wait = stream.chunk_wait();
+                let mut stream = Some(stream);
+                this.pending.insert(TaskFuture::schedule(move || {
+                    let mut current = stream.take().expect("a chunk is read only once");
+                    if let Some(delay) = current.check_chunk(&mut wait) {
+                        stream = Some(current);
+                        return Step::Wait(delay);
+                    }
+                    let chunk = current.next_without_waiting();
+                    Step::Done((current, chunk))
+                }))
+            }
+        };
+        let Poll::Ready((stream, chunk)) = Pin::new(pending).poll(cx) else {
+            return Poll::Pending;
+        };
+        this.pending = None;
+        if chunk.is_some() {
+            this.stream = Some(stream);
+        }
+        Poll::Ready(chunk)
+    }
+}
+
+/// When a read with `timeout` started now times out, or [`None`] to wait forever. Timeouts too
+/// long to represent, or not a number, never time out.
+pub(super) fn deadline(timeout: Timeout) -> Option<Instant> {
+    match timeout {
+        Timeout::WaitForever => None,
+        Timeout::NoWait => Some(Instant::now()),
+        Timeout::Seconds(seconds) if seconds <= 0.0 => Some(Instant::now()),
+        Timeout::Seconds(seconds) => Duration::try_from_secs_f64(seconds)
+            .ok()
+            .and_then(|timeout| Instant::now().checked_add(timeout)),
+    }
+}
@@ -1,12 +1,16 @@
-use std::{ffi::CString, ptr};
+use std::ffi::CString;
 
-use anyhow::bail;
-use anyhow::Result;
+use crate::error::{DaqmxError, ErrorContext, Result};
 
+use crate::backend::{Backend, ChannelProperty, PropertyValue};
 use crate::channels::{ChannelBuilderInput, ChannelBuilderOutput, CounterChannelTrait};
-use crate::types::{ExportSignal, IdleState, SampleMode, Timeout};
+use crate::types::{DataFillMode, ExportSignal, IdleState, SampleMode, Timeout};
+use crate::warnings::TaskWarnings;
 use crate::{daqmx, daqmx_call};
 
+use super::frame::Frame;
+use super::input::{DAQmxInput, InputTask, ReadConfig};
+use super::stream::SampleStream;
 use super::{CounterInput, CounterOutput, Task};
 
 /// Counter output task operations.
@@ -36,7 +40,7 @@ pub trait CounterInputTask {
 
 impl Task<CounterOutput> {
     pub fn create_channel<B: ChannelBuilderOutput>(&mut self, builder: B) -> Result<()> {
-        unsafe { builder.add_to_task(self.raw_handle())? };
+        unsafe { builder.add_to_task(self.backend(), self.raw_handle())? };
         self.channel_count += 1;
         Ok(())
     }
@@ -56,27 +60,36 @@ impl Task<CounterOutput> {
         idle_state: IdleState,
     ) -> Result<()> {
         if self.channel_count != 0 {
-            bail!("counter task already has channels; create a new Task<CounterOutput> for another pulse config");
+            return Err(DaqmxError::Validation("counter task already has channels; create a new Task<CounterOutput> for another pulse config".to_string()));
         }
 
         if low_s <= 0.0 {
-            bail!("low_s must be > 0.0 seconds");
+            return Err(DaqmxError::Validation(
+                "low_s must be > 0.0 seconds".to_string(),
+            ));
         }
         if high_s <= 0.0 {
-            bail!("high_s must be > 0.0 seconds");
+            return Err(DaqmxError::Validation(
+                "high_s must be > 0.0 seconds".to_string(),
+            ));
         }
 
         let counter_c = CString::new(counter)?;
-        daqmx_call!(daqmx::DAQmxCreateCOPulseChanTime(
-            self.raw_handle(),
-            counter_c.as_ptr(),
-            ptr::null(),
-            daqmx::DAQmx_Val_Seconds,
-            idle_state.into(),
-            0.0,
-            low_s,
-            high_s
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            create_co_pulse_chan_time(
+                self.raw_handle(),
+                &counter_c,
+                c"",
+                daqmx::DAQmx_Val_Seconds,
+                idle_state.into(),
+                0.0,
+                low_s,
+                high_s
+            ),
+            self.error_context(),
+            self.warning_state()
+        )?;
         self.channel_count += 1;
         self.configure_implicit_timing(SampleMode::FiniteSamples, 1)
     }
@@ -98,13 +111,7 @@ impl Task<CounterOutput> {
     /// This is often more direct than exporting a signal when you're routing only the
     /// counter pulse itself.
     pub fn set_counter_output_terminal(&mut self, terminal: &str) -> Result<()> {
-        let empty_channel = CString::new("")?;
-        let terminal_c = CString::new(terminal)?;
-        daqmx_call!(daqmx::DAQmxSetCOPulseTerm(
-            self.raw_handle(),
-            empty_channel.as_ptr(),
-            terminal_c.as_ptr()
-        ))
+        self.set_counter_output_terminal_for_channel("", terminal)
     }
 
     /// Set the pulse output terminal for a specific counter output channel in this task.
@@ -114,12 +121,18 @@ impl Task<CounterOutput> {
         terminal: &str,
     ) -> Result<()> {
         let channel_c = CString::new(channel_name)?;
-        let terminal_c = CString::new(terminal)?;
-        daqmx_call!(daqmx::DAQmxSetCOPulseTerm(
-            self.raw_handle(),
-            channel_c.as_ptr(),
-            terminal_c.as_ptr()
-        ))
+        let terminal = PropertyValue::String(terminal.to_string());
+        daqmx_call!(
+            self.backend(),
+            set_channel_property(
+                self.raw_handle(),
+                &channel_c,
+                ChannelProperty::COPulseTerminal,
+                &terminal
+            ),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 }
 
@@ -133,11 +146,12 @@ impl CounterOutputTask for Task<CounterOutput> {
         mode: SampleMode,
         samples_per_channel: u64,
     ) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxCfgImplicitTiming(
-            self.raw_handle(),
-            mode.into(),
-            samples_per_channel
-        ))
+        daqmx_call!(
+            self.backend(),
+            cfg_implicit_timing(self.raw_handle(), mode.into(), samples_per_channel),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     fn start_pulse(&mut self) -> Result<()> {
@@ -147,7 +161,7 @@ impl CounterOutputTask for Task<CounterOutput> {
 
 impl Task<CounterInput> {
     pub fn create_channel<B: ChannelBuilderInput>(&mut self, builder: B) -> Result<()> {
-        unsafe { builder.add_to_task(self.raw_handle())? };
+        unsafe { builder.add_to_task(self.backend(), self.raw_handle())? };
         self.channel_count += 1;
         Ok(())
     }
@@ -155,6 +169,42 @@ impl Task<CounterInput> {
     pub fn get_channel<C: CounterChannelTrait<CounterInput>>(&self, name: &str) -> Result<C> {
         C::new(self.clone(), name)
     }
+
+    /// Reads the running task in chunks of `samples_per_chunk` samples, as counts (`u32`) or
+    /// scaled measurements (`f64`). See [`SampleStream`].
+    pub fn stream<T: Clone + Default>(
+        &self,
+        samples_per_chunk: u32,
+    ) -> Result<SampleStream<CounterInput, T>>
+    where
+        Self: InputTask<T>,
+    {
+        SampleStream::new(self, samples_per_chunk, 1)
+    }
+
+    /// Sets the input buffer size, overwrite behaviour and read position. See [`ReadConfig`].
+    pub fn configure_read(&mut self, config: &ReadConfig) -> Result<()> {
+        self.apply_read_config(config)
+    }
+
+    /// The current input buffer size, overwrite behaviour and read position.
+    pub fn read_config(&self) -> Result<ReadConfig> {
+        self.current_read_config()
+    }
+
+    /// Reads `samples_per_channel` samples into a [`Frame`], as counts (`u32`) or scaled
+    /// measurements (`f64`).
+    pub fn read_frame<T: Clone + Default>(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: u32,
+    ) -> Result<Frame<T>>
+    where
+        Self: InputTask<T>,
+    {
+        self.read_into_frame(timeout, fill_mode, samples_per_channel)
+    }
 }
 
 impl CounterInputTask for Task<CounterInput> {
@@ -163,12 +213,12 @@ impl CounterInputTask for Task<CounterInput> {
     /// such as for edge counting.
     fn read_count_scalar(&mut self, timeout: Timeout) -> Result<u32> {
         let mut value = 0u32;
-        daqmx_call!(daqmx::DAQmxReadCounterScalarU32(
-            self.raw_handle(),
-            timeout.into(),
-            &mut value,
-            ptr::null_mut()
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            read_counter_scalar_u32(self.raw_handle(), timeout.into(), &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(value)
     }
 
@@ -177,12 +227,102 @@ impl CounterInputTask for Task<CounterInput> {
     /// such as for frequency and period measurement.
     fn read_period_scalar(&mut self, timeout: Timeout) -> Result<f64> {
         let mut value = 0.0f64;
-        daqmx_call!(daqmx::DAQmxReadCounterScalarF64(
-            self.raw_handle(),
-            timeout.into(),
-            &mut value,
-            ptr::null_mut()
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            read_counter_scalar_f64(self.raw_handle(), timeout.into(), &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(value)
     }
 }
+
+/// Buffered reads of unscaled counter samples, such as edge counts latched by a sample clock.
+impl InputTask<u32> for Task<CounterInput> {
+    fn read_scalar(&mut self, timeout: Timeout) -> Result<u32> {
+        self.read_count_scalar(timeout)
+    }
+}
+
+/// Buffered reads of scaled counter samples, such as periods or frequencies.
+impl InputTask<f64> for Task<CounterInput> {
+    fn read_scalar(&mut self, timeout: Timeout) -> Result<f64> {
+        self.read_period_scalar(timeout)
+    }
+}
+
+impl DAQmxInput<u32> for Task<CounterInput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn read_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("read_counter_u32"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
+    /// Counter tasks have a single channel, so `fill_mode` doesn't apply.
+    unsafe fn daqmx_read(
+        &mut self,
+        samples_per_channel: i32,
+        timeout: f64,
+        _fill_mode: daqmx::bool32,
+        buffer: &mut [u32],
+        buffer_size: u32,
+        actual_samples_per_channel: *mut i32,
+    ) -> i32 {
+        Task::backend(self).read_counter_u32(
+            self.raw_handle(),
+            samples_per_channel,
+            timeout,
+            &mut buffer[..buffer_size as usize],
+            &mut *actual_samples_per_channel,
+        )
+    }
+}
+
+impl DAQmxInput<f64> for Task<CounterInput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn read_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("read_counter_f64"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
+    /// Counter tasks have a single channel, so `fill_mode` doesn't apply.
+    unsafe fn daqmx_read(
+        &mut self,
+        samples_per_channel: i32,
+        timeout: f64,
+        _fill_mode: daqmx::bool32,
+        buffer: &mut [f64],
+        buffer_size: u32,
+        actual_samples_per_channel: *mut i32,
+    ) -> i32 {
+        Task::backend(self).read_counter_f64(
+            self.raw_handle(),
+            samples_per_channel,
+            timeout,
+            &mut buffer[..buffer_size as usize],
+            &mut *actual_samples_per_channel,
+        )
+    }
+}
@@ -1,15 +1,18 @@
+use crate::backend::{Backend, TaskProperty};
 use crate::channels::{ChannelBuilderInput, DigitalChannelTrait};
-use crate::types::Timeout;
+use crate::error::{DaqmxError, ErrorContext, Result};
+use crate::types::{DataFillMode, Timeout};
+use crate::warnings::TaskWarnings;
 use crate::{daqmx, daqmx_call};
-use anyhow::Result;
-use std::ptr;
 
-use super::input::{DAQmxInput, InputTask};
+use super::frame::Frame;
+use super::input::{DAQmxInput, InputTask, ReadConfig};
+use super::stream::SampleStream;
 use super::{task::DigitalInput, Task};
 
 impl Task<DigitalInput> {
     pub fn create_channel<B: ChannelBuilderInput>(&mut self, builder: B) -> Result<()> {
-        unsafe { builder.add_to_task(self.raw_handle())? };
+        unsafe { builder.add_to_task(self.backend(), self.raw_handle())? };
         self.channel_count += 1;
         Ok(())
     }
@@ -17,17 +20,69 @@ impl Task<DigitalInput> {
     pub fn get_channel<C: DigitalChannelTrait<DigitalInput>>(&self, name: &str) -> Result<C> {
         C::new(self.clone(), name)
     }
+
+    /// Reads the running task in chunks of `samples_per_chunk` samples per channel, with one
+    /// element per line like [`InputTask::read`]. See [`SampleStream`].
+    pub fn stream<T: Clone + Default>(
+        &self,
+        samples_per_chunk: u32,
+    ) -> Result<SampleStream<DigitalInput, T>>
+    where
+        Self: InputTask<T>,
+    {
+        let lines =
+            self.read_task_property::<u32>(TaskProperty::ReadDigitalLinesBytesPerChannel)?;
+        SampleStream::new(
+            self,
+            samples_per_chunk,
+            self.num_channels()? * lines as usize,
+        )
+    }
+
+    /// Sets the input buffer size, overwrite behaviour and read position. See [`ReadConfig`].
+    pub fn configure_read(&mut self, config: &ReadConfig) -> Result<()> {
+        self.apply_read_config(config)
+    }
+
+    /// The current input buffer size, overwrite behaviour and read position.
+    pub fn read_config(&self) -> Result<ReadConfig> {
+        self.current_read_config()
+    }
+
+    /// Reads `samples_per_channel` samples per channel into a [`Frame`], which knows the channel
+    /// names and the layout of the data.
+    ///
+    /// Each channel must be a single line, so there is one element per sample of each channel.
+    /// Read channels of several lines with [`InputTask::read`].
+    pub fn read_frame<T: Clone + Default>(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: u32,
+    ) -> Result<Frame<T>>
+    where
+        Self: InputTask<T>,
+    {
+        let lines =
+            self.read_task_property::<u32>(TaskProperty::ReadDigitalLinesBytesPerChannel)?;
+        if lines != 1 {
+            return Err(DaqmxError::Validation(format!(
+                "Frames need one line per channel, but the channels have up to {lines} lines."
+            )));
+        }
+        self.read_into_frame(timeout, fill_mode, samples_per_channel)
+    }
 }
 
 impl InputTask<u8> for Task<DigitalInput> {
     fn read_scalar(&mut self, timeout: Timeout) -> Result<u8> {
         let mut value = 0;
-        daqmx_call!(daqmx::DAQmxReadDigitalScalarU32(
-            self.raw_handle(),
-            timeout.into(),
-            &mut value,
-            ptr::null_mut(),
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            read_digital_scalar_u32(self.raw_handle(), timeout.into(), &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(value as u8)
     }
 }
@@ -35,17 +90,34 @@ impl InputTask<u8> for Task<DigitalInput> {
 impl InputTask<bool> for Task<DigitalInput> {
     fn read_scalar(&mut self, timeout: Timeout) -> Result<bool> {
         let mut value = 0;
-        daqmx_call!(daqmx::DAQmxReadDigitalScalarU32(
-            self.raw_handle(),
-            timeout.into(),
-            &mut value,
-            ptr::null_mut(),
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            read_digital_scalar_u32(self.raw_handle(), timeout.into(), &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(value != 0)
     }
 }
 
 impl DAQmxInput<u8> for Task<DigitalInput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn read_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("read_digital_lines"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
     unsafe fn daqmx_read(
         &mut self,
         samples_per_channel: i32,
@@ -55,21 +127,37 @@ impl DAQmxInput<u8> for Task<DigitalInput> {
         buffer_size: u32,
         actual_samples_per_channel: *mut i32,
     ) -> i32 {
-        daqmx::DAQmxReadDigitalLines(
+        let mut bytes_per_sample = 0;
+        Task::backend(self).read_digital_lines(
             self.raw_handle(),
             samples_per_channel,
             timeout,
             fill_mode,
-            buffer.as_mut_ptr(),
-            buffer_size,
-            actual_samples_per_channel,
-            ptr::null_mut(),
-            ptr::null_mut(),
+            &mut buffer[..buffer_size as usize],
+            &mut *actual_samples_per_channel,
+            &mut bytes_per_sample,
         )
     }
 }
 
 impl DAQmxInput<bool> for Task<DigitalInput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn read_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("read_digital_lines"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
     unsafe fn daqmx_read(
         &mut self,
         samples_per_channel: i32,
@@ -85,23 +173,34 @@ impl DAQmxInput<bool> for Task<DigitalInput> {
         let max_elems = temp_buffer.len().min(u32::MAX as usize) as u32;
         let safe_size = buffer_size.min(max_elems);
 
-        let res = daqmx::DAQmxReadDigitalLines(
+        // Queried before the read, so a failed read leaves its own extended error info for the
+        // caller. The count is a driver `u32`, so any other error means a broken backend.
+        let channels = match self.num_channels() {
+            Ok(channels) => channels,
+            Err(error) => return error.code().unwrap_or(-1),
+        };
+
+        let mut bytes_per_sample = 0;
+        let res = Task::backend(self).read_digital_lines(
             self.raw_handle(),
             samples_per_channel,
             timeout,
             fill_mode,
-            temp_buffer.as_mut_ptr(),
-            safe_size,
-            actual_samples_per_channel,
-            ptr::null_mut(),
-            ptr::null_mut(),
+            &mut temp_buffer[..safe_size as usize],
+            &mut *actual_samples_per_channel,
+            &mut bytes_per_sample,
         );
 
-        if res >= 0 {
-            let n = safe_size as usize;
-            for i in 0..n {
-                buffer[i] = temp_buffer[i] != 0;
-            }
+        // A timed out or overwritten read still returns the samples read before the error, so
+        // they are copied whatever the status.
+        let samples_read = (*actual_samples_per_channel).max(0) as usize;
+        let elements = samples_read * channels * bytes_per_sample.max(0) as usize;
+        for (value, &line) in buffer
+            .iter_mut()
+            .zip(&temp_buffer[..safe_size as usize])
+            .take(elements)
+        {
+            *value = line != 0;
         }
 
         res
@@ -1,15 +1,18 @@
-use crate::channels::{ChannelBuilderOutput, DigitalChannelTrait};
-use crate::types::Timeout;
+use crate::backend::{Backend, TaskProperty};
+use crate::channels::{ChannelBuilderOutput, DigitalChannelBase, DigitalChannelTrait};
+use crate::error::{ErrorContext, Result};
+use crate::types::{DataFillMode, Timeout};
+use crate::warnings::TaskWarnings;
+use crate::waveforms::DigitalPattern;
 use crate::{daqmx, daqmx_call};
-use anyhow::Result;
-use std::ptr;
 
-use super::output::{DAQmxOutput, OutputTask};
+use super::output::{DAQmxOutput, OutputTask, WriteConfig};
+use super::output_stream::OutputStreamer;
 use super::{task::DigitalOutput, Task};
 
 impl Task<DigitalOutput> {
     pub fn create_channel<B: ChannelBuilderOutput>(&mut self, builder: B) -> Result<()> {
-        unsafe { builder.add_to_task(self.raw_handle())? };
+        unsafe { builder.add_to_task(self.backend(), self.raw_handle())? };
         self.channel_count += 1;
         Ok(())
     }
@@ -17,76 +20,164 @@ impl Task<DigitalOutput> {
     pub fn get_channel<C: DigitalChannelTrait<DigitalOutput>>(&self, name: &str) -> Result<C> {
         C::new(self.clone(), name)
     }
+
+    /// Sets the output buffer size, regeneration and write position. See [`WriteConfig`].
+    pub fn configure_write(&mut self, config: &WriteConfig) -> Result<()> {
+        self.apply_write_config(config)
+    }
+
+    /// The current output buffer size, regeneration and write position.
+    pub fn write_config(&self) -> Result<WriteConfig> {
+        self.current_write_config()
+    }
+
+    /// Streams the chunks of `producer` to the task without regeneration, as line states (`u8`
+    /// or `bool`). See [`OutputStreamer`].
+    pub fn streamer<T, P>(
+        &self,
+        producer: P,
+    ) -> Result<OutputStreamer<DigitalOutput, T, P::IntoIter>>
+    where
+        Self: OutputTask<T>,
+        P: IntoIterator<Item = Vec<T>>,
+    {
+        OutputStreamer::new(self, producer.into_iter(), self.lines_per_sample()?)
+    }
+
+    /// Line states per sample across all channels, as written by `write_digital_lines`.
+    fn lines_per_sample(&self) -> Result<usize> {
+        let lines =
+            self.read_task_property::<u32>(TaskProperty::WriteDigitalLinesBytesPerChannel)?;
+        Ok(self.num_channels()? * lines as usize)
+    }
+
+    /// Renders `pattern` for the channels and sample clock rate of the task, laid out as
+    /// `fill_mode` for a write of `u8` line states. See [`DigitalPattern::render`].
+    pub fn render_pattern(
+        &self,
+        pattern: &DigitalPattern,
+        fill_mode: DataFillMode,
+    ) -> Result<Vec<u8>> {
+        let lines_per_channel = self
+            .channel_names()?
+            .iter()
+            .map(|name| {
+                self.get_channel::<DigitalChannelBase<DigitalOutput>>(name)?
+                    .num_lines()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        pattern.render(self.sample_clock_rate()?, &lines_per_channel, fill_mode)
+    }
 }
 
 impl OutputTask<u8> for Task<DigitalOutput> {
     fn write_scalar(&mut self, value: u8, timeout: Timeout) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxWriteDigitalScalarU32(
-            self.raw_handle(),
-            1,
-            timeout.into(),
-            value as u32,
-            ptr::null_mut()
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            write_digital_scalar_u32(self.raw_handle(), true, timeout.into(), value as u32),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(())
     }
 }
 
 impl OutputTask<bool> for Task<DigitalOutput> {
     fn write_scalar(&mut self, value: bool, timeout: Timeout) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxWriteDigitalScalarU32(
-            self.raw_handle(),
-            1,
-            timeout.into(),
-            value.into(),
-            ptr::null_mut()
-        ))?;
+        daqmx_call!(
+            self.backend(),
+            write_digital_scalar_u32(self.raw_handle(), true, timeout.into(), value.into()),
+            self.error_context(),
+            self.warning_state()
+        )?;
         Ok(())
     }
 }
 
 impl DAQmxOutput<u8> for Task<DigitalOutput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn write_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("write_digital_lines"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
+    fn elements_per_sample(&self) -> Result<usize> {
+        self.lines_per_sample()
+    }
+
     unsafe fn daqmx_write(
         &mut self,
         samples_per_channel: i32,
         auto_start: daqmx::bool32,
         timeout: f64,
         fill_mode: daqmx::bool32,
-        buffer: *const u8,
+        buffer: &[u8],
         actual_samples_per_channel: *mut i32,
     ) -> i32 {
-        daqmx::DAQmxWriteDigitalLines(
+        Task::backend(self).write_digital_lines(
             self.raw_handle(),
             samples_per_channel,
-            auto_start,
+            auto_start != 0,
             timeout,
             fill_mode,
             buffer,
-            actual_samples_per_channel,
-            ptr::null_mut(),
+            &mut *actual_samples_per_channel,
         )
     }
 }
 
 impl DAQmxOutput<bool> for Task<DigitalOutput> {
+    fn backend(&self) -> &dyn Backend {
+        Task::backend(self)
+    }
+
+    fn write_error_context(&self) -> ErrorContext {
+        self.error_context()
+            .with_operation(crate::backend::function_name("write_digital_lines"))
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        self.warning_state()
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.raw_handle()
+    }
+
+    fn elements_per_sample(&self) -> Result<usize> {
+        self.lines_per_sample()
+    }
+
     unsafe fn daqmx_write(
         &mut self,
         samples_per_channel: i32,
         auto_start: daqmx::bool32,
         timeout: f64,
         fill_mode: daqmx::bool32,
-        buffer: *const bool,
+        buffer: &[bool],
         actual_samples_per_channel: *mut i32,
     ) -> i32 {
-        daqmx::DAQmxWriteDigitalLines(
+        // bool has the same layout as a u8 holding 0 or 1, which is what DAQmx expects.
+        let buffer = std::slice::from_raw_parts(buffer.as_ptr() as *const u8, buffer.len());
+        Task::backend(self).write_digital_lines(
             self.raw_handle(),
             samples_per_channel,
-            auto_start,
+            auto_start != 0,
             timeout,
             fill_mode,
-            buffer as *const u8, // check if this works
-            actual_samples_per_channel,
-            ptr::null_mut(),
+            buffer,
+            &mut *actual_samples_per_channel,
         )
     }
 }
@@ -0,0 +1,326 @@
+//! Multi-channel blocks of samples which keep track of their channels and layout.
+use std::iter::{StepBy, Take};
+use std::slice;
+
+use super::input::InputTask;
+use super::Task;
+use crate::error::{DaqmxError, Result};
+use crate::types::{DataFillMode, Timeout};
+
+/// The samples of one channel or one row of a [`Frame`], in order.
+pub type Samples<'a, T> = Take<StepBy<slice::Iter<'a, T>>>;
+
+/// A block of samples read from several channels, along with the channel names and the layout
+/// of the data.
+///
+/// Created by `read_frame` on input tasks, such as
+/// [`Task::<AnalogInput>::read_frame`](Task::read_frame), or from your own buffer with
+/// [`Frame::new`]. Each channel holds the same number of samples. A row is one sample from
+/// every channel, also known as a scan.
+///
+/// ```
+/// use daqmx::tasks::Frame;
+/// use daqmx::types::DataFillMode;
+///
+/// # fn main() -> daqmx::error::Result<()> {
+/// let frame = Frame::new(
+///     vec!["ai0".to_string(), "ai1".to_string()],
+///     DataFillMode::GroupByChannel,
+///     vec![1.0, 2.0, 3.0, 10.0, 20.0, 30.0],
+/// )?;
+///
+/// assert_eq!(frame.samples_per_channel(), 3);
+/// assert_eq!(frame.channel_slice(1), Some(&[10.0, 20.0, 30.0][..]));
+/// assert!(frame.rows().next().unwrap().eq(&[1.0, 10.0]));
+///
+/// let interleaved = frame.into_interleaved();
+/// assert_eq!(interleaved.data(), &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame<T> {
+    channels: Vec<String>,
+    samples_per_channel: usize,
+    layout: DataFillMode,
+    data: Vec<T>,
+}
+
+impl<T> Frame<T> {
+    /// Wraps `data` laid out as `layout` across `channels`.
+    ///
+    /// Fails if there are no channels or the data doesn't split evenly between them.
+    pub fn new(channels: Vec<String>, layout: DataFillMode, data: Vec<T>) -> Result<Self> {
+        if channels.is_empty() {
+            return Err(DaqmxError::Validation(
+                "A frame needs at least one channel.".to_string(),
+            ));
+        }
+        if data.len() % channels.len() != 0 {
+            return Err(DaqmxError::Validation(format!(
+                "{} values can't be split evenly between {} channels.",
+                data.len(),
+                channels.len()
+            )));
+        }
+        Ok(Self {
+            samples_per_channel: data.len() / channels.len(),
+            channels,
+            layout,
+            data,
+        })
+    }
+
+    /// The names of the channels, in the order of the data.
+    pub fn channel_names(&self) -> &[String] {
+        &self.channels
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    pub fn samples_per_channel(&self) -> usize {
+        self.samples_per_channel
+    }
+
+    /// The layout of [`Frame::data`].
+    pub fn layout(&self) -> DataFillMode {
+        self.layout
+    }
+
+    /// The data of all channels, laid out as [`Frame::layout`].
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+
+    /// The position of the channel called `name`. Like DAQmx, names are case insensitive.
+    pub fn channel_index(&self, name: &str) -> Option<usize> {
+        self.channels
+            .iter()
+            .position(|channel| channel.eq_ignore_ascii_case(name))
+    }
+
+    /// The samples of channel `index`, whatever the layout.
+    pub fn channel(&self, index: usize) -> Option<Samples<'_, T>> {
+        (index < self.num_channels()).then(|| self.channel_at(index))
+    }
+
+    /// The samples of the channel called `name`, whatever the layout.
+    pub fn channel_by_name(&self, name: &str) -> Option<Samples<'_, T>> {
+        self.channel(self.channel_index(name)?)
+    }
+
+    /// The samples of channel `index` as a slice, if they are next to each other. That is when
+    /// grouped by channel, or if there is only one channel or sample.
+    pub fn channel_slice(&self, index: usize) -> Option<&[T]> {
+        if index >= self.num_channels() {
+            return None;
+        }
+        match self.layout {
+            DataFillMode::GroupByChannel => {
+                let start = index * self.samples_per_channel;
+                Some(&self.data[start..start + self.samples_per_channel])
+            }
+            DataFillMode::GroupByScanNumber if self.num_channels() == 1 => Some(&self.data),
+            DataFillMode::GroupByScanNumber if self.samples_per_channel == 1 => {
+                Some(&self.data[index..=index])
+            }
+            DataFillMode::GroupByScanNumber => None,
+        }
+    }
+
+    /// The sample `sample` of channel `channel`.
+    pub fn get(&self, channel: usize, sample: usize) -> Option<&T> {
+        if channel >= self.num_channels() || sample >= self.samples_per_channel {
+            return None;
+        }
+        self.data.get(self.position(channel, sample))
+    }
+
+    /// Sample `index` of every channel, in channel order.
+    pub fn row(&self, index: usize) -> Option<Samples<'_, T>> {
+        (index < self.samples_per_channel).then(|| self.row_at(index))
+    }
+
+    /// Iterates over the rows of the frame, each holding one sample from every channel.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = Samples<'_, T>> {
+        (0..self.samples_per_channel).map(move |index| self.row_at(index))
+    }
+
+    fn channel_at(&self, index: usize) -> Samples<'_, T> {
+        match self.layout {
+            DataFillMode::GroupByChannel => self.samples(
+                index * self.samples_per_channel,
+                1,
+                self.samples_per_channel,
+            ),
+            DataFillMode::GroupByScanNumber => {
+                self.samples(index, self.num_channels(), self.samples_per_channel)
+            }
+        }
+    }
+
+    fn row_at(&self, index: usize) -> Samples<'_, T> {
+        match self.layout {
+            DataFillMode::GroupByChannel => {
+                self.samples(index, self.samples_per_channel, self.num_channels())
+            }
+            DataFillMode::GroupByScanNumber => {
+                self.samples(index * self.num_channels(), 1, self.num_channels())
+            }
+        }
+    }
+
+    /// Where `sample` of `channel` is in the data.
+    fn position(&self, channel: usize, sample: usize) -> usize {
+        match self.layout {
+            DataFillMode::GroupByChannel => channel * self.samples_per_channel + sample,
+            DataFillMode::GroupByScanNumber => sample * self.num_channels() + channel,
+        }
+    }
+
+    /// `count` values of the data, `step` apart from `start`.
+    fn samples(&self, start: usize, step: usize, count: usize) -> Samples<'_, T> {
+        self.data
+            .get(start..)
+            .unwrap_or_default()
+            .iter()
+            .step_by(step)
+            .take(count)
+    }
+}
+
+impl<T: Clone> Frame<T> {
+    /// Rearranges the data as `layout`.
+    pub fn into_layout(self, layout: DataFillMode) -> Self {
+        if layout == self.layout || self.num_channels() == 1 || self.samples_per_channel <= 1 {
+            return Self { layout, ..self };
+        }
+        let data = match layout {
+            DataFillMode::GroupByChannel => (0..self.num_channels())
+                .flat_map(|index| self.channel_at(index))
+                .cloned()
+                .collect(),
+            DataFillMode::GroupByScanNumber => self.rows().flatten().cloned().collect(),
+        };
+        Self {
+            data,
+            layout,
+            ..self
+        }
+    }
+
+    /// Rearranges the data so each row is contiguous, as [`DataFillMode::GroupByScanNumber`].
+    pub fn into_interleaved(self) -> Self {
+        self.into_layout(DataFillMode::GroupByScanNumber)
+    }
+
+    /// Rearranges the data so each channel is contiguous, as [`DataFillMode::GroupByChannel`].
+    pub fn into_grouped_by_channel(self) -> Self {
+        self.into_layout(DataFillMode::GroupByChannel)
+    }
+}
+
+impl<TYPE> Task<TYPE> {
+    /// Reads `samples_per_channel` samples per channel into a new frame with one element per
+    /// sample of each channel.
+    pub(crate) fn read_into_frame<T: Clone + Default>(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: u32,
+    ) -> Result<Frame<T>>
+    where
+        Self: InputTask<T>,
+    {
+        let channels = self.channel_names()?;
+        let mut data = vec![T::default(); channels.len() * samples_per_channel as usize];
+        let read = self.read(timeout, fill_mode, Some(samples_per_channel), &mut data)?;
+        // A finite acquisition may end early, in which case the samples read are packed at the
+        // start of the buffer.
+        data.truncate(channels.len() * read.max(0) as usize);
+        Frame::new(channels, fill_mode, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn grouped() -> Frame<i32> {
+        Frame::new(
+            names(&["a", "b", "c"]),
+            DataFillMode::GroupByChannel,
+            vec![1, 2, 10, 20, 100, 200],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_frame_shape() {
+        let frame = grouped();
+        assert_eq!(frame.num_channels(), 3);
+        assert_eq!(frame.samples_per_channel(), 2);
+        assert_eq!(frame.channel_index("B"), Some(1));
+        assert_eq!(frame.channel_index("d"), None);
+    }
+
+    #[test]
+    fn test_frame_rejects_uneven_data() {
+        assert!(Frame::new(
+            names(&["a", "b"]),
+            DataFillMode::GroupByChannel,
+            vec![1, 2, 3]
+        )
+        .is_err());
+        assert!(Frame::<i32>::new(vec![], DataFillMode::GroupByChannel, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_channels_in_both_layouts() {
+        let grouped = grouped();
+        let interleaved = grouped.clone().into_interleaved();
+        assert_eq!(interleaved.data(), &[1, 10, 100, 2, 20, 200]);
+
+        for frame in [&grouped, &interleaved] {
+            assert!(frame.channel(1).unwrap().eq(&[10, 20]));
+            assert!(frame.channel_by_name("c").unwrap().eq(&[100, 200]));
+            assert!(frame.channel(3).is_none());
+            assert!(frame.row(1).unwrap().eq(&[2, 20, 200]));
+            assert!(frame.row(2).is_none());
+            assert_eq!(frame.get(2, 0), Some(&100));
+            assert_eq!(frame.get(0, 2), None);
+            assert_eq!(frame.rows().len(), 2);
+        }
+        assert_eq!(grouped.channel_slice(1), Some(&[10, 20][..]));
+        assert_eq!(interleaved.channel_slice(1), None);
+    }
+
+    #[test]
+    fn test_layout_round_trip() {
+        let grouped = grouped();
+        let round_trip = grouped.clone().into_interleaved().into_grouped_by_channel();
+        assert_eq!(round_trip, grouped);
+    }
+
+    #[test]
+    fn test_single_channel_slices() {
+        let frame = Frame::new(
+            names(&["a"]),
+            DataFillMode::GroupByScanNumber,
+            vec![1, 2, 3],
+        )
+        .unwrap();
+        assert_eq!(frame.channel_slice(0), Some(&[1, 2, 3][..]));
+        assert_eq!(frame.into_grouped_by_channel().data(), &[1, 2, 3]);
+    }
+}
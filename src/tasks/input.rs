@@ -2,12 +2,17 @@
 ///
 /// In future it may expose a reader struct for managing the buffers and providing
 /// the different data representations for us.
+use crate::backend::{Backend, PropertyValue, TaskProperty};
 use crate::daqmx;
-use daqmx::bool32;
-
 use crate::daqmx_call;
-use crate::types::{DataFillMode, Timeout};
-use anyhow::{bail, Result};
+use daqmx::{bool32, TaskHandle};
+
+use crate::error::{handle_status, ErrorContext};
+use crate::error::{DaqmxError, ErrorKind, Result};
+use crate::types::{DataFillMode, OverwriteMode, ReadRelativeTo, Timeout};
+use crate::warnings::TaskWarnings;
+
+use super::Task;
 
 pub trait InputTask<T>: DAQmxInput<T> {
     /// Read a single value from the task with the given timeout.
@@ -28,7 +33,8 @@ pub trait InputTask<T>: DAQmxInput<T> {
     /// # Return
     /// The number of samples read per channel.
     ///
-    /// TODO: If we timeout we may still read samples - that needs to be expressed with a more complicated return type.
+    /// If the read times out, samples read before the timeout are in the buffer but the count is
+    /// lost. Use [`InputTask::read_partial`] to keep it.
     fn read(
         &mut self,
         timeout: Timeout,
@@ -36,6 +42,23 @@ pub trait InputTask<T>: DAQmxInput<T> {
         samples_per_channel: Option<u32>,
         buffer: &mut [T],
     ) -> Result<i32> {
+        self.read_partial(timeout, fill_mode, samples_per_channel, buffer)?
+            .into_result()
+    }
+
+    /// Reads like [`InputTask::read`], but reports the samples read before a timeout or buffer
+    /// overflow together with the error instead of discarding them.
+    ///
+    /// Errors which end the read early with data in the buffer ([`ErrorKind::Timeout`],
+    /// [`ErrorKind::BufferOverwritten`] and [`ErrorKind::SamplesNoLongerAvailable`]) are returned in
+    /// the [`ReadOutcome`]. Any other error is returned as [`Err`].
+    fn read_partial(
+        &mut self,
+        timeout: Timeout,
+        fill_mode: DataFillMode,
+        samples_per_channel: Option<u32>,
+        buffer: &mut [T],
+    ) -> Result<ReadOutcome> {
         let mut actual_samples_per_channel = 0;
         let requested_samples_per_channel = match samples_per_channel {
             Some(val) => val as i32,
@@ -43,30 +66,143 @@ pub trait InputTask<T>: DAQmxInput<T> {
         };
 
         if buffer.is_empty() {
-            bail!("Read buffer is empty, nothing to read into.");
+            return Err(DaqmxError::Validation(
+                "Read buffer is empty, nothing to read into.".to_string(),
+            ));
         }
 
         // Just saturate the buffer size at u32 boundary.
         // If it is larger, this will still be memory safe.
         let buffer_length = buffer.len().try_into().unwrap_or(u32::MAX);
 
-        daqmx_call!(self.daqmx_read(
-            requested_samples_per_channel,
-            timeout.into(),
-            fill_mode.into(),
-            buffer,
-            buffer_length,
-            &mut actual_samples_per_channel as *mut i32
-        ))?;
+        let return_code = unsafe {
+            self.daqmx_read(
+                requested_samples_per_channel,
+                timeout.into(),
+                fill_mode.into(),
+                buffer,
+                buffer_length,
+                &mut actual_samples_per_channel as *mut i32,
+            )
+        };
+        let status = handle_status(
+            self.backend(),
+            return_code,
+            || self.read_error_context(),
+            self.task_warnings(),
+        );
+
+        match status {
+            Ok(()) => Ok(ReadOutcome {
+                samples_per_channel: actual_samples_per_channel,
+                error: None,
+            }),
+            Err(error) if ReadOutcome::is_partial_read_error(&error) => Ok(ReadOutcome {
+                samples_per_channel: actual_samples_per_channel,
+                error: Some(error),
+            }),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Samples per channel acquired but not yet read.
+    fn available_samples_per_channel(&self) -> Result<u32> {
+        read_task_status(
+            self.backend(),
+            self.raw_task_handle(),
+            self.task_warnings(),
+            TaskProperty::ReadAvailableSamplesPerChannel,
+        )
+    }
+
+    /// Samples per channel acquired since the task started, including those already read.
+    ///
+    /// The difference to the samples read so far shows how far reads lag behind the acquisition,
+    /// and how close a continuous acquisition is to overwriting unread samples.
+    fn total_samples_acquired_per_channel(&self) -> Result<u64> {
+        read_task_status(
+            self.backend(),
+            self.raw_task_handle(),
+            self.task_warnings(),
+            TaskProperty::ReadTotalSamplesPerChannelAcquired,
+        )
+    }
+}
+
+/// Reads a status property of the buffer of `task` for [`InputTask`] and
+/// [`crate::tasks::output::OutputTask`].
+pub(crate) fn read_task_status<T: TryFrom<PropertyValue, Error = DaqmxError>>(
+    backend: &dyn Backend,
+    task: TaskHandle,
+    warnings: &TaskWarnings,
+    property: TaskProperty,
+) -> Result<T> {
+    let mut value = PropertyValue::U32(0);
+    daqmx_call!(
+        backend,
+        get_task_property(task, property, &mut value),
+        ErrorContext::for_task(backend, task),
+        warnings
+    )?;
+    T::try_from(value)
+}
+
+/// The result of [`InputTask::read_partial`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReadOutcome {
+    /// The number of samples read per channel, including those read before an error.
+    pub samples_per_channel: i32,
+    /// The timeout or overflow which ended the read early.
+    pub error: Option<DaqmxError>,
+}
+
+impl ReadOutcome {
+    /// Whether the read completed without an error.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Whether the read ended on a timeout.
+    pub fn timed_out(&self) -> bool {
+        self.error.as_ref().is_some_and(DaqmxError::is_timeout)
+    }
+
+    /// The samples read per channel, or the error if the read ended early.
+    pub fn into_result(self) -> Result<i32> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.samples_per_channel),
+        }
+    }
 
-        Ok(actual_samples_per_channel)
+    fn is_partial_read_error(error: &DaqmxError) -> bool {
+        matches!(
+            error.kind(),
+            Some(
+                ErrorKind::Timeout
+                    | ErrorKind::BufferOverwritten
+                    | ErrorKind::SamplesNoLongerAvailable
+            )
+        )
     }
 }
 
 pub trait DAQmxInput<T> {
+    /// The backend the task reads through, used to describe errors.
+    fn backend(&self) -> &dyn Backend;
+
+    /// Describes a failed read, including the DAQmx function called by [`Self::daqmx_read`].
+    fn read_error_context(&self) -> ErrorContext;
+
+    /// The warning state of the task, whose policy handles warnings from [`Self::daqmx_read`].
+    fn task_warnings(&self) -> &TaskWarnings;
+
+    /// The handle of the task, used to query the status of its buffer.
+    fn raw_task_handle(&self) -> TaskHandle;
+
     /// Low-level wrapper around the underlying NI-DAQmx read call.
     ///
-    /// This exists so implementers only need to provide the final FFI call, while
+    /// This exists so implementers only need to provide the final backend call, while
     /// [`InputTask::read`] handles argument normalization and common setup.
     ///
     /// # Safety
@@ -96,3 +232,119 @@ pub trait DAQmxInput<T> {
         actual_samples_per_channel: *mut i32,
     ) -> i32;
 }
+
+/// Input buffer and read position settings, applied with `configure_read` on input tasks such as
+/// [`Task::<AnalogInput>::configure_read`](Task::configure_read).
+///
+/// Settings left as [`None`] are not changed. Together the settings let a continuous task serve
+/// "latest N samples" reads without draining the buffer, see [`ReadConfig::latest`].
+///
+/// ```
+/// use daqmx::tasks::ReadConfig;
+/// use daqmx::types::{OverwriteMode, ReadRelativeTo};
+///
+/// let config = ReadConfig::default()
+///     .buffer_size(10_000)
+///     .overwrite(OverwriteMode::OverwriteUnreadSamples)
+///     .relative_to(ReadRelativeTo::MostRecentSample)
+///     .offset(-100);
+/// assert_eq!(config, ReadConfig::latest(100).buffer_size(10_000));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadConfig {
+    /// The size of the input buffer in samples per channel.
+    pub buffer_size: Option<u32>,
+    /// What a continuous acquisition does when the buffer is full of unread samples.
+    pub overwrite: Option<OverwriteMode>,
+    /// The point reads start from.
+    pub relative_to: Option<ReadRelativeTo>,
+    /// Samples per channel from [`ReadConfig::relative_to`] to the first sample read.
+    pub offset: Option<i32>,
+}
+
+impl ReadConfig {
+    /// Reads the most recent `samples` samples per channel, letting the acquisition overwrite
+    /// samples which aren't read.
+    ///
+    /// Each read should ask for `samples` samples per channel. Reading fewer returns the oldest of
+    /// them, and reading more waits for samples not yet acquired. `samples` above [`i32::MAX`] is
+    /// clamped to it, the furthest offset NI-DAQmx can express.
+    pub const fn latest(samples: u32) -> Self {
+        let samples = if samples > i32::MAX as u32 {
+            i32::MAX
+        } else {
+            samples as i32
+        };
+        Self {
+            buffer_size: None,
+            overwrite: Some(OverwriteMode::OverwriteUnreadSamples),
+            relative_to: Some(ReadRelativeTo::MostRecentSample),
+            offset: Some(-samples),
+        }
+    }
+
+    /// Sets the input buffer size in samples per channel. This can only be set while the task is
+    /// not running.
+    pub const fn buffer_size(mut self, samples_per_channel: u32) -> Self {
+        self.buffer_size = Some(samples_per_channel);
+        self
+    }
+
+    pub const fn overwrite(mut self, mode: OverwriteMode) -> Self {
+        self.overwrite = Some(mode);
+        self
+    }
+
+    pub const fn relative_to(mut self, relative_to: ReadRelativeTo) -> Self {
+        self.relative_to = Some(relative_to);
+        self
+    }
+
+    pub const fn offset(mut self, offset: i32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl<TYPE> Task<TYPE> {
+    /// Applies the settings of `config` which are set. Shared by the input tasks.
+    pub(crate) fn apply_read_config(&mut self, config: &ReadConfig) -> Result<()> {
+        if let Some(samples_per_channel) = config.buffer_size {
+            daqmx_call!(
+                self.backend(),
+                cfg_input_buffer(self.raw_handle(), samples_per_channel),
+                self.error_context(),
+                self.warning_state()
+            )?;
+        }
+        if let Some(mode) = config.overwrite {
+            self.write_task_property(TaskProperty::ReadOverwrite, PropertyValue::I32(mode.into()))?;
+        }
+        if let Some(relative_to) = config.relative_to {
+            self.write_task_property(
+                TaskProperty::ReadRelativeTo,
+                PropertyValue::I32(relative_to.into()),
+            )?;
+        }
+        if let Some(offset) = config.offset {
+            self.write_task_property(TaskProperty::ReadOffset, PropertyValue::I32(offset))?;
+        }
+        Ok(())
+    }
+
+    /// The current settings, all of them set.
+    pub(crate) fn current_read_config(&self) -> Result<ReadConfig> {
+        Ok(ReadConfig {
+            buffer_size: Some(self.read_task_property(TaskProperty::InputBufferSize)?),
+            overwrite: Some(
+                self.read_task_property::<i32>(TaskProperty::ReadOverwrite)?
+                    .try_into()?,
+            ),
+            relative_to: Some(
+                self.read_task_property::<i32>(TaskProperty::ReadRelativeTo)?
+                    .try_into()?,
+            ),
+            offset: Some(self.read_task_property(TaskProperty::ReadOffset)?),
+        })
+    }
+}
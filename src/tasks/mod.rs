@@ -1,12 +1,25 @@
 pub mod ai_task;
 pub mod ao_task;
+#[cfg(feature = "async")]
+pub mod async_io;
 pub mod counter;
 pub mod di_task;
 pub mod do_task;
+pub mod frame;
 pub mod input;
 pub mod output;
+pub mod output_stream;
+pub mod stream;
 pub mod task;
+pub mod typestate;
+pub mod waveform;
 
+#[cfg(feature = "async")]
+pub use async_io::{AsyncSampleStream, TaskFuture};
 pub use counter::*;
+pub use frame::*;
 pub use input::*;
+pub use output_stream::*;
+pub use stream::*;
 pub use task::*;
+pub use waveform::*;
@@ -1,10 +1,16 @@
 //! Provides traits around output task behaviours
+use crate::backend::{Backend, PropertyValue, TaskProperty};
 use crate::daqmx;
-use daqmx::bool32;
-
 use crate::daqmx_call;
-use crate::types::{DataFillMode, Timeout};
-use anyhow::{bail, Result};
+use daqmx::{bool32, TaskHandle};
+
+use crate::error::{handle_status, ErrorContext};
+use crate::error::{DaqmxError, Result};
+use crate::types::{DataFillMode, RegenerationMode, Timeout, WriteRelativeTo};
+use crate::warnings::TaskWarnings;
+
+use super::input::read_task_status;
+use super::Task;
 
 #[derive(Clone, Copy, Debug)]
 pub struct WriteOptions {
@@ -76,38 +82,77 @@ pub trait OutputTask<T>: DAQmxOutput<T> {
         };
 
         if buffer.is_empty() {
-            bail!("Buffer is empty, nothing to write.");
+            return Err(DaqmxError::Validation(
+                "Buffer is empty, nothing to write.".to_string(),
+            ));
         }
 
         if requested_samples_per_channel != -1
             && buffer.len() % requested_samples_per_channel as usize != 0
         {
-            bail!("Buffer length is not a multiple of the requested samples per channel.");
+            return Err(DaqmxError::Validation(
+                "Buffer length is not a multiple of the requested samples per channel.".to_string(),
+            ));
         }
 
-        daqmx_call!(self.daqmx_write(
-            requested_samples_per_channel,
-            daqmx::bool32::from(opts.auto_start),
-            timeout.into(),
-            fill_mode.into(),
-            buffer.as_ptr(),
-            &mut actual_samples_per_channel as *mut i32
-        ))?;
+        let return_code = unsafe {
+            self.daqmx_write(
+                requested_samples_per_channel,
+                daqmx::bool32::from(opts.auto_start),
+                timeout.into(),
+                fill_mode.into(),
+                buffer,
+                &mut actual_samples_per_channel as *mut i32,
+            )
+        };
+        handle_status(
+            self.backend(),
+            return_code,
+            || self.write_error_context(),
+            self.task_warnings(),
+        )?;
 
         Ok(actual_samples_per_channel)
     }
+
+    /// Samples per channel which can be written without overwriting samples not yet generated.
+    ///
+    /// Writing no more than this keeps a non-regenerating output from blocking.
+    fn write_space_available(&self) -> Result<u32> {
+        read_task_status(
+            self.backend(),
+            self.raw_task_handle(),
+            self.task_warnings(),
+            TaskProperty::WriteSpaceAvailable,
+        )
+    }
 }
 
 pub trait DAQmxOutput<T> {
+    /// The backend the task writes through, used to describe errors.
+    fn backend(&self) -> &dyn Backend;
+
+    /// Describes a failed write, including the DAQmx function called by [`Self::daqmx_write`].
+    fn write_error_context(&self) -> ErrorContext;
+
+    /// The warning state of the task, whose policy handles warnings from [`Self::daqmx_write`].
+    fn task_warnings(&self) -> &TaskWarnings;
+
+    /// The handle of the task, used to query the status of its buffer.
+    fn raw_task_handle(&self) -> TaskHandle;
+
+    /// Buffer elements per sample across all channels, which sizes a write of the whole buffer.
+    fn elements_per_sample(&self) -> Result<usize>;
+
     /// Low-level wrapper around the underlying NI-DAQmx write call.
     ///
-    /// This exists so implementers only need to provide the final FFI call, while
+    /// This exists so implementers only need to provide the final backend call, while
     /// [`OutputTask::write_with_options`] handles argument validation and common setup.
     ///
     /// # Safety
     /// Implementers must uphold the following:
     ///
-    /// - `buffer` must point to at least `N` contiguous elements of `T`, where:
+    /// - `buffer` must hold at least `N` elements of `T`, where:
     ///   - If `samples_per_channel == -1`, `N` is the full buffer length chosen by the caller
     ///     (DAQmx interprets this as "all available / full finite buffer", depending on task mode).
     ///   - Otherwise, `N >= samples_per_channel * num_channels` for the configured task.
@@ -124,7 +169,127 @@ pub trait DAQmxOutput<T> {
         auto_start: bool32,
         timeout: f64,
         fill_mode: bool32,
-        buffer: *const T,
+        buffer: &[T],
         actual_samples_per_channel: *mut i32,
     ) -> i32;
 }
+
+/// Output buffer and write position settings, applied with `configure_write` on output tasks such
+/// as [`Task::<AnalogOutput>::configure_write`](Task::configure_write).
+///
+/// Settings left as [`None`] are not changed. By default DAQmx regenerates the buffer, so a
+/// waveform written once loops until the task stops. [`WriteConfig::streaming`] turns that off so
+/// every sample is generated once, which needs the buffer topped up as the task runs.
+///
+/// ```
+/// use daqmx::tasks::output::WriteConfig;
+/// use daqmx::types::RegenerationMode;
+///
+/// let config = WriteConfig::streaming().buffer_size(10_000);
+/// assert_eq!(
+///     config.regeneration,
+///     Some(RegenerationMode::DoNotAllowRegeneration)
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WriteConfig {
+    /// The size of the output buffer in samples per channel.
+    pub buffer_size: Option<u32>,
+    /// Whether the buffer is generated again once every sample in it has been generated.
+    pub regeneration: Option<RegenerationMode>,
+    /// The point writes start from.
+    pub relative_to: Option<WriteRelativeTo>,
+    /// Samples per channel from [`WriteConfig::relative_to`] to the first sample written.
+    pub offset: Option<i32>,
+}
+
+impl WriteConfig {
+    /// Loops the buffer, for repeating a waveform written before the task starts. Writing to the
+    /// running task replaces the waveform from the start of the buffer.
+    pub const fn regenerating() -> Self {
+        Self {
+            buffer_size: None,
+            regeneration: Some(RegenerationMode::AllowRegeneration),
+            relative_to: Some(WriteRelativeTo::FirstSample),
+            offset: Some(0),
+        }
+    }
+
+    /// Generates each sample once, appending every write to the samples already written.
+    pub const fn streaming() -> Self {
+        Self {
+            buffer_size: None,
+            regeneration: Some(RegenerationMode::DoNotAllowRegeneration),
+            relative_to: Some(WriteRelativeTo::CurrentWritePosition),
+            offset: Some(0),
+        }
+    }
+
+    /// Sets the output buffer size in samples per channel. This can only be set while the task
+    /// is not running.
+    pub const fn buffer_size(mut self, samples_per_channel: u32) -> Self {
+        self.buffer_size = Some(samples_per_channel);
+        self
+    }
+
+    pub const fn regeneration(mut self, mode: RegenerationMode) -> Self {
+        self.regeneration = Some(mode);
+        self
+    }
+
+    pub const fn relative_to(mut self, relative_to: WriteRelativeTo) -> Self {
+        self.relative_to = Some(relative_to);
+        self
+    }
+
+    pub const fn offset(mut self, offset: i32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl<TYPE> Task<TYPE> {
+    /// Applies the settings of `config` which are set. Shared by the output tasks.
+    pub(crate) fn apply_write_config(&mut self, config: &WriteConfig) -> Result<()> {
+        if let Some(samples_per_channel) = config.buffer_size {
+            daqmx_call!(
+                self.backend(),
+                cfg_output_buffer(self.raw_handle(), samples_per_channel),
+                self.error_context(),
+                self.warning_state()
+            )?;
+        }
+        if let Some(mode) = config.regeneration {
+            self.write_task_property(
+                TaskProperty::WriteRegenMode,
+                PropertyValue::I32(mode.into()),
+            )?;
+        }
+        if let Some(relative_to) = config.relative_to {
+            self.write_task_property(
+                TaskProperty::WriteRelativeTo,
+                PropertyValue::I32(relative_to.into()),
+            )?;
+        }
+        if let Some(offset) = config.offset {
+            self.write_task_property(TaskProperty::WriteOffset, PropertyValue::I32(offset))?;
+        }
+        Ok(())
+    }
+
+    /// The current settings, all of them set.
+    pub(crate) fn current_write_config(&self) -> Result<WriteConfig> {
+        Ok(WriteConfig {
+            buffer_size: Some(self.read_task_property(TaskProperty::OutputBufferSize)?),
+            regeneration: Some(
+                self.read_task_property::<i32>(TaskProperty::WriteRegenMode)?
+                    .try_into()?,
+            ),
+            relative_to: Some(
+                self.read_task_property::<i32>(TaskProperty::WriteRelativeTo)?
+                    .try_into()?,
+            ),
+            offset: Some(self.read_task_property(TaskProperty::WriteOffset)?),
+        })
+    }
+}
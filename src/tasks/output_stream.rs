@@ -0,0 +1,271 @@
+//! Streaming a non-regenerating output from a producer of chunks.
+use std::time::Duration;
+
+use super::output::{OutputTask, WriteConfig, WriteOptions};
+use super::Task;
+use crate::backend::TaskProperty;
+use crate::error::{DaqmxError, ErrorKind, Result};
+use crate::types::{DataFillMode, Timeout};
+
+/// Keeps the buffer of a running output topped up with chunks pulled from a producer, generating
+/// each sample once.
+///
+/// Created by `streamer` on output tasks, such as
+/// [`Task::<AnalogOutput>::streamer`](Task::streamer), which turns off regeneration with
+/// [`WriteConfig::streaming`]. The producer is any iterator of chunks, so a closure can be used
+/// through [`std::iter::from_fn`]. Each chunk holds whole samples of every channel, laid out as
+/// [`OutputStreamer::fill_mode`], and the stream ends when the producer does. A chunk larger than
+/// the output buffer is a [`DaqmxError::Validation`] error.
+///
+/// [`OutputStreamer::run`] streams everything and stops the task. To drive the stream yourself,
+/// [`OutputStreamer::prime`] the buffer, start the task and call [`OutputStreamer::top_up`] often
+/// enough that the buffer never runs dry. If it does, the generation stops and the streamer
+/// returns [`DaqmxError::OutputUnderflow`].
+///
+/// ```no_run
+/// use daqmx::channels::VoltageChannel;
+/// use daqmx::tasks::{AnalogOutput, Task};
+/// use daqmx::tasks::output::WriteConfig;
+/// use daqmx::types::{ClockEdge, SampleMode};
+///
+/// # fn main() -> daqmx::error::Result<()> {
+/// let mut task = Task::<AnalogOutput>::new("stream_out")?;
+/// task.create_channel(VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?.max(1.0).build()?)?;
+/// task.configure_sample_clock_timing(
+///     None,
+///     1000.0,
+///     ClockEdge::Rising,
+///     SampleMode::ContinuousSamples,
+///     1000,
+/// )?;
+/// task.configure_write(&WriteConfig::default().buffer_size(1000))?;
+///
+/// // Ten seconds of a ramp, produced 100 samples at a time.
+/// let chunks = (0..100).map(|chunk| (0..100).map(|i| (chunk * 100 + i) as f64 / 1e4).collect());
+/// let written = task.streamer(chunks)?.run()?;
+/// assert_eq!(written, 10_000);
+/// # Ok(())
+/// # }
+/// ```
+pub struct OutputStreamer<TYPE, T, P> {
+    task: Task<TYPE>,
+    producer: P,
+    /// Buffer elements per sample, across all channels.
+    elements_per_sample: usize,
+    fill_mode: DataFillMode,
+    timeout: Timeout,
+    poll_interval: Duration,
+    /// A chunk taken from the producer which didn't fit in the buffer yet.
+    pending: Option<Vec<T>>,
+    samples_written: u64,
+    exhausted: bool,
+}
+
+impl<TYPE, T, P> OutputStreamer<TYPE, T, P>
+where
+    Task<TYPE>: OutputTask<T> + Clone,
+    P: Iterator<Item = Vec<T>>,
+{
+    pub(crate) fn new(task: &Task<TYPE>, producer: P, elements_per_sample: usize) -> Result<Self> {
+        let mut task = task.clone();
+        task.apply_write_config(&WriteConfig::streaming())?;
+        Ok(Self {
+            task,
+            producer,
+            elements_per_sample,
+            fill_mode: DataFillMode::GroupByChannel,
+            timeout: Timeout::Seconds(10.0),
+            poll_interval: Duration::from_millis(10),
+            pending: None,
+            samples_written: 0,
+            exhausted: false,
+        })
+    }
+
+    /// The layout of the chunks. Defaults to [`DataFillMode::GroupByChannel`].
+    pub fn fill_mode(mut self, fill_mode: DataFillMode) -> Self {
+        self.fill_mode = fill_mode;
+        self
+    }
+
+    /// The timeout of each write. Defaults to 10 seconds.
+    ///
+    /// Chunks are only written once they fit in the buffer, so writes don't normally wait.
+    pub fn timeout(mut self, timeout: Timeout) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long [`OutputStreamer::run`] sleeps while the buffer is full. Defaults to 10 ms, and
+    /// should be well under the time the task takes to generate a buffer.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Samples per channel written so far.
+    pub fn samples_written(&self) -> u64 {
+        self.samples_written
+    }
+
+    /// Whether the producer has ended and every chunk has been written.
+    pub fn is_finished(&self) -> bool {
+        self.exhausted && self.pending.is_none()
+    }
+
+    /// Fills the buffer before the task starts, stopping at the first chunk which doesn't fit in
+    /// the space left. The writes never start the task.
+    ///
+    /// Returns the samples per channel written.
+    pub fn prime(&mut self) -> Result<u64> {
+        self.top_up()
+    }
+
+    /// Writes chunks for as long as they fit in the buffer, without waiting for space.
+    ///
+    /// Returns the samples per channel written, which is 0 once the stream is finished.
+    pub fn top_up(&mut self) -> Result<u64> {
+        self.check_generation()?;
+        let mut written = 0;
+        while let Some(chunk) = self.next_chunk()? {
+            let samples = (chunk.len() / self.elements_per_sample) as u64;
+            let fits = self.fits(samples);
+            if !matches!(fits, Ok(true)) {
+                self.pending = Some(chunk);
+                fits?;
+                break;
+            }
+            self.task
+                .write_with_options(
+                    self.timeout,
+                    self.fill_mode,
+                    Some(samples as u32),
+                    &chunk,
+                    WriteOptions::default().auto_start(false),
+                )
+                .map_err(|error| self.underflow(error))?;
+            written += samples;
+            self.samples_written += samples;
+        }
+        Ok(written)
+    }
+
+    /// Primes the buffer, starts the task and keeps the buffer topped up until the producer ends,
+    /// then stops the task once everything written has been generated.
+    ///
+    /// Returns the samples per channel written. The task is stopped on errors too.
+    pub fn run(&mut self) -> Result<u64> {
+        let result = self.stream_all();
+        if result.is_err() {
+            let _ = self.task.stop();
+        }
+        result.map(|()| self.samples_written)
+    }
+
+    /// Waits for the samples written to be generated, then stops the task.
+    pub fn finish(&mut self) -> Result<()> {
+        while !self.drained()? {
+            match self.task.is_done() {
+                // The buffer ran dry since it was checked, which is expected now.
+                Err(error) if error.kind() == Some(ErrorKind::OutputUnderflow) => {
+                    if self.drained()? {
+                        break;
+                    }
+                    return Err(self.underflow(error));
+                }
+                Err(error) => return Err(error),
+                Ok(_) => std::thread::sleep(self.poll_interval),
+            }
+        }
+        match self.task.stop() {
+            Err(error) if error.kind() == Some(ErrorKind::OutputUnderflow) => Ok(()),
+            result => result,
+        }
+    }
+
+    fn stream_all(&mut self) -> Result<()> {
+        self.prime()?;
+        self.task.start()?;
+        while !self.is_finished() {
+            if self.top_up()? == 0 {
+                std::thread::sleep(self.poll_interval);
+            }
+        }
+        self.finish()
+    }
+
+    /// The pending chunk or the next non-empty chunk from the producer.
+    fn next_chunk(&mut self) -> Result<Option<Vec<T>>> {
+        if let Some(chunk) = self.pending.take() {
+            return Ok(Some(chunk));
+        }
+        if self.exhausted {
+            return Ok(None);
+        }
+        for chunk in self.producer.by_ref() {
+            if chunk.is_empty() {
+                continue;
+            }
+            if chunk.len() % self.elements_per_sample != 0 {
+                return Err(DaqmxError::Validation(format!(
+                    "A chunk of {} values doesn't hold whole samples of {} values.",
+                    chunk.len(),
+                    self.elements_per_sample
+                )));
+            }
+            return Ok(Some(chunk));
+        }
+        self.exhausted = true;
+        Ok(None)
+    }
+
+    /// Whether `samples` samples per channel can be written without waiting.
+    ///
+    /// Fails if they are more than the buffer holds, as writing them before the task starts would
+    /// wait until the write times out.
+    fn fits(&self, samples: u64) -> Result<bool> {
+        let buffer_size = self.buffer_size()?;
+        // Without a configured size, the first write sets the size of the buffer.
+        if buffer_size == 0 && self.samples_written == 0 {
+            return Ok(true);
+        }
+        if samples > buffer_size {
+            return Err(DaqmxError::Validation(format!(
+                "A chunk of {samples} samples per channel doesn't fit in the output buffer of \
+                 {buffer_size} samples per channel."
+            )));
+        }
+        Ok(u64::from(self.task.write_space_available()?) >= samples)
+    }
+
+    /// Whether every sample written has been generated.
+    fn drained(&self) -> Result<bool> {
+        Ok(u64::from(self.task.write_space_available()?) >= self.buffer_size()?)
+    }
+
+    fn buffer_size(&self) -> Result<u64> {
+        self.task
+            .read_task_property::<u32>(TaskProperty::OutputBufferSize)
+            .map(u64::from)
+    }
+
+    /// Fails if the generation has stopped.
+    fn check_generation(&mut self) -> Result<()> {
+        match self.task.is_done() {
+            Ok(_) => Ok(()),
+            Err(error) => Err(self.underflow(error)),
+        }
+    }
+
+    /// Reports an underflow as [`DaqmxError::OutputUnderflow`], leaving other errors as they are.
+    fn underflow(&self, error: DaqmxError) -> DaqmxError {
+        if error.kind() == Some(ErrorKind::OutputUnderflow) {
+            DaqmxError::OutputUnderflow {
+                samples_written: self.samples_written,
+                source: Box::new(error),
+            }
+        } else {
+            error
+        }
+    }
+}
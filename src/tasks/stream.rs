@@ -0,0 +1,178 @@
+//! Reading a running acquisition as a sequence of chunks.
+use super::input::InputTask;
+use super::Task;
+use crate::error::{DaqmxError, Result};
+use crate::types::{DataFillMode, Timeout};
+
+/// Reads a running acquisition in chunks of a fixed number of samples per channel.
+///
+/// Created by `stream` on input tasks, such as [`Task::<AnalogInput>::stream`](Task::stream).
+/// It reads through its own clone of the task and ends once the task is stopped from any clone,
+/// or once a finite acquisition has been read completely. Start the task before reading the
+/// stream, as a stream of a stopped task is empty. Errors, such as an overflowing buffer, are
+/// yielded and the stream carries on.
+///
+/// Iterating yields each chunk as an owned [`Vec`]. [`SampleStream::next_chunk`] borrows it
+/// from the buffer of the stream instead.
+///
+/// ```no_run
+/// use daqmx::channels::VoltageChannel;
+/// use daqmx::tasks::{AnalogInput, Task};
+/// use daqmx::types::{ClockEdge, SampleMode};
+///
+/// # fn main() -> daqmx::error::Result<()> {
+/// let mut task = Task::<AnalogInput>::new("stream")?;
+/// task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+/// task.configure_sample_clock_timing(
+///     None,
+///     1000.0,
+///     ClockEdge::Rising,
+///     SampleMode::ContinuousSamples,
+///     1000,
+/// )?;
+/// task.start()?;
+///
+/// // Stop `task` from another thread to end the loop.
+/// let mut stream = task.stream(100)?;
+/// while let Some(chunk) = stream.next_chunk() {
+///     println!("{:?}", chunk?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SampleStream<TYPE, T> {
+    task: Task<TYPE>,
+    samples_per_chunk: u32,
+    /// Buffer elements per sample, across all channels.
+    elements_per_sample: usize,
+    timeout: Timeout,
+    fill_mode: DataFillMode,
+    buffer: Vec<T>,
+    finished: bool,
+}
+
+impl<TYPE, T> SampleStream<TYPE, T>
+where
+    Task<TYPE>: InputTask<T> + Clone,
+    T: Clone + Default,
+{
+    pub(crate) fn new(
+        task: &Task<TYPE>,
+        samples_per_chunk: u32,
+        elements_per_sample: usize,
+    ) -> Result<Self> {
+        if samples_per_chunk == 0 {
+            return Err(DaqmxError::Validation(
+                "A stream needs at least one sample per chunk.".to_string(),
+            ));
+        }
+        Ok(Self {
+            task: task.clone(),
+            samples_per_chunk,
+            elements_per_sample,
+            timeout: Timeout::WaitForever,
+            fill_mode: DataFillMode::GroupByChannel,
+            buffer: vec![T::default(); samples_per_chunk as usize * elements_per_sample],
+            finished: false,
+        })
+    }
+
+    /// The timeout of each read. Defaults to [`Timeout::WaitForever`].
+    pub fn timeout(mut self, timeout: Timeout) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The layout of the chunks. Defaults to [`DataFillMode::GroupByChannel`].
+    pub fn fill_mode(mut self, fill_mode: DataFillMode) -> Self {
+        self.fill_mode = fill_mode;
+        self
+    }
+
+    /// Reads the next chunk into the buffer of the stream, or returns [`None`] once the stream
+    /// has ended.
+    ///
+    /// The last chunk of a finite acquisition may be shorter.
+    pub fn next_chunk(&mut self) -> Option<Result<&[T]>> {
+        if self.finished {
+            return None;
+        }
+        let samples = match self.samples_left() {
+            Some(0) => {
+                self.finished = true;
+                return None;
+            }
+            Some(left) => left.min(self.samples_per_chunk),
+            None => self.samples_per_chunk,
+        };
+
+        let len = samples as usize * self.elements_per_sample;
+        let read = self.task.read(
+            self.timeout,
+            self.fill_mode,
+            Some(samples),
+            &mut self.buffer[..len],
+        );
+        match read {
+            Ok(read) => Some(Ok(&self.buffer[..read as usize * self.elements_per_sample])),
+            // The task was stopped while the read waited.
+            Err(_) if self.samples_left() == Some(0) => {
+                self.finished = true;
+                None
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+
+    /// The samples per channel still to be read once the task is done, because it was stopped
+    /// or finished acquiring, or [`None`] while it is running.
+    ///
+    /// Errors checking the task count as running, leaving the read to report them.
+    fn samples_left(&mut self) -> Option<u32> {
+        if !self.task.is_done().ok()? {
+            return None;
+        }
+        Some(self.task.available_samples_per_channel().unwrap_or(0))
+    }
+}
+
+impl<TYPE, T> Iterator for SampleStream<TYPE, T>
+where
+    Task<TYPE>: InputTask<T> + Clone,
+    T: Clone + Default,
+{
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk().map(|chunk| chunk.map(<[T]>::to_vec))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TYPE, T> SampleStream<TYPE, T>
+where
+    Task<TYPE>: InputTask<T> + Clone,
+    T: Clone + Default,
+{
+    /// Starts waiting for the next chunk, until the timeout of a read.
+    pub(crate) fn chunk_wait(&self) -> super::async_io::SampleWait {
+        super::async_io::SampleWait::new(Some(self.samples_per_chunk), self.timeout)
+    }
+
+    /// Checks `wait` once. See [`SampleWait::check`](super::async_io::SampleWait::check).
+    pub(crate) fn check_chunk(
+        &mut self,
+        wait: &mut super::async_io::SampleWait,
+    ) -> Option<std::time::Duration> {
+        wait.check::<TYPE, T>(&mut self.task)
+    }
+
+    /// Reads the next chunk without waiting in the driver, once [`Self::check_chunk`] found it
+    /// available or timed out.
+    pub(crate) fn next_without_waiting(&mut self) -> Option<Result<Vec<T>>> {
+        let timeout = std::mem::replace(&mut self.timeout, Timeout::NoWait);
+        let chunk = self.next();
+        self.timeout = timeout;
+        chunk
+    }
+}
@@ -1,22 +1,33 @@
 // This file contains code derived from the daqmx-rs project:
 // https://github.com/WiresmithTech/daqmx-rs
 
+use crate::backend::{self, Backend, EventHandler, PropertyValue, TaskProperty};
 use crate::{daqmx, daqmx_call, types};
-use std::{ffi::CString, marker::PhantomData, ptr, sync::Arc};
+use log::error;
+use std::ops::{Deref, DerefMut};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, Weak};
+use std::{ffi::CString, marker::PhantomData, ptr};
 
-use crate::error::handle_error;
-use crate::types::{buffer_to_string, ExportSignal, Timeout};
-use anyhow::Result;
-use daqmx::bool32;
+use crate::error::{handle_event_status, ErrorContext, Result};
+use crate::types::{EveryNSamplesEvent, ExportSignal, SignalEvent, Timeout};
+use crate::warnings::{TaskWarnings, Warning, WarningPolicy};
 
-struct TaskHandle(daqmx::TaskHandle);
+struct TaskHandle {
+    handle: daqmx::TaskHandle,
+    backend: Arc<dyn Backend>,
+    warnings: TaskWarnings,
+    /// Event callbacks registered with the driver. Dropped after the task is cleared, which
+    /// unregisters them.
+    events: Mutex<Vec<Arc<EventHandler>>>,
+}
 
 unsafe impl Send for TaskHandle {}
 unsafe impl Sync for TaskHandle {}
 
 impl Drop for TaskHandle {
     fn drop(&mut self) {
-        unsafe { daqmx::DAQmxClearTask(self.0) };
+        unsafe { self.backend.clear_task(self.handle) };
     }
 }
 
@@ -58,26 +69,239 @@ impl<TYPE> Task<TYPE> {
     /// You should not hold this raw handle yourself as you
     /// lose the memory safety given by the wrapped task.
     pub(crate) fn raw_handle(&self) -> daqmx::TaskHandle {
-        self.handle.0
+        self.handle.handle
+    }
+
+    /// The backend the task was created on.
+    pub(crate) fn backend(&self) -> &dyn Backend {
+        self.handle.backend.as_ref()
+    }
+
+    /// Identifies the task in errors. Looks up the task name, so only use it once a call failed.
+    pub(crate) fn error_context(&self) -> ErrorContext {
+        ErrorContext::for_task(self.backend(), self.raw_handle())
+    }
+
+    /// The warning policy and collected warnings of the task.
+    pub(crate) fn warning_state(&self) -> &TaskWarnings {
+        &self.handle.warnings
+    }
+
+    /// Sets how warnings raised by this task are handled. [`None`] uses the
+    /// [global policy](crate::warnings::global_warning_policy).
+    ///
+    /// The policy is shared with clones of the task.
+    pub fn set_warning_policy(&mut self, policy: Option<WarningPolicy>) {
+        self.warning_state().set_policy(policy);
+    }
+
+    /// The warnings collected by a [`WarningPolicy::collect`] policy so far.
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.warning_state().collected()
+    }
+
+    /// Returns the collected warnings and clears them.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        self.warning_state().take()
     }
 
     /// Create a new task handle from a name. For use in specific task types.
+    ///
+    /// The task is created on the [`backend::default_backend`].
+    /// With the `runtime-loading` feature this returns [`crate::error::DaqmxError::DriverNotInstalled`]
+    /// if the NI-DAQmx library is not available.
     pub fn new(name: &str) -> Result<Self> {
+        Self::with_backend(name, backend::default_backend())
+    }
+
+    /// Create a new task on a specific backend, such as a [`backend::SimulatedBackend`].
+    pub fn with_backend(name: &str, backend: Arc<dyn Backend>) -> Result<Self> {
         let c_name = CString::new(name)?;
 
         let handle = {
             let mut tmp_handle: daqmx::TaskHandle = ptr::null_mut();
-            daqmx_call!(daqmx::DAQmxCreateTask(c_name.as_ptr(), &mut tmp_handle))?;
+            daqmx_call!(
+                backend.as_ref(),
+                create_task(&c_name, &mut tmp_handle),
+                ErrorContext::task(name)
+            )?;
             tmp_handle
         };
 
         Ok(Self {
-            handle: Arc::new(TaskHandle(handle)),
+            handle: Arc::new(TaskHandle {
+                handle,
+                backend,
+                warnings: TaskWarnings::default(),
+                events: Mutex::default(),
+            }),
             channel_type: PhantomData,
             channel_count: 0,
         })
     }
 
+    /// Calls `callback` each time `samples` samples per channel have been acquired into, or
+    /// transferred from, the buffer of the task.
+    ///
+    /// The callback runs on a driver thread and gets a handle to the task along with the number
+    /// of samples, so it can read or write them. Register it before starting the task. It stays
+    /// registered until the last clone of the task is dropped; a panic in the callback is caught
+    /// and logged. If the last clone is dropped while the callback runs, the task is cleared on
+    /// another thread once it returns, as the driver can't clear a task inside its callbacks.
+    ///
+    /// ```no_run
+    /// use daqmx::channels::VoltageChannel;
+    /// use daqmx::tasks::{AnalogInput, InputTask, Task};
+    /// use daqmx::types::{ClockEdge, DataFillMode, EveryNSamplesEvent, SampleMode, Timeout};
+    ///
+    /// # fn main() -> daqmx::error::Result<()> {
+    /// let mut task = Task::<AnalogInput>::new("events")?;
+    /// task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    /// task.configure_sample_clock_timing(
+    ///     None,
+    ///     1000.0,
+    ///     ClockEdge::Rising,
+    ///     SampleMode::ContinuousSamples,
+    ///     1000,
+    /// )?;
+    ///
+    /// task.on_every_n_samples(100, EveryNSamplesEvent::AcquiredIntoBuffer, |task, samples| {
+    ///     let mut buffer = vec![0.0; samples as usize];
+    ///     let read = task.read(
+    ///         Timeout::Seconds(0.0),
+    ///         DataFillMode::GroupByChannel,
+    ///         Some(samples),
+    ///         &mut buffer,
+    ///     );
+    ///     println!("{read:?}");
+    /// })?;
+    /// task.start()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_every_n_samples<F>(
+        &mut self,
+        samples: u32,
+        event: EveryNSamplesEvent,
+        mut callback: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&mut Task<TYPE>, u32) + Send + 'static,
+        TYPE: 'static,
+    {
+        let handler = self.event_handler(move |task, _, samples| callback(task, samples));
+        daqmx_call!(
+            self.backend(),
+            register_every_n_samples_event(
+                self.raw_handle(),
+                event.into(),
+                samples,
+                Some(&handler)
+            ),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        self.keep_event_handler(handler);
+        Ok(())
+    }
+
+    /// Calls `callback` when the task finishes, such as the end of a finite acquisition, pulse
+    /// train or waveform, or when it stops on an error. The callback gets the final status of the
+    /// task: the error the task stopped on, or `Ok` if it completed.
+    ///
+    /// Stopping the task with [`Task::stop`] doesn't call it. Like
+    /// [`Task::on_every_n_samples`], register it before starting the task.
+    pub fn on_done<F>(&mut self, mut callback: F) -> Result<()>
+    where
+        F: FnMut(&mut Task<TYPE>, Result<()>) + Send + 'static,
+        TYPE: 'static,
+    {
+        let handler = self.event_handler(move |task, status, _| {
+            let status = handle_event_status(
+                task.backend(),
+                status,
+                || task.error_context(),
+                task.warning_state(),
+            );
+            callback(task, status)
+        });
+        daqmx_call!(
+            self.backend(),
+            register_done_event(self.raw_handle(), Some(&handler)),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        self.keep_event_handler(handler);
+        Ok(())
+    }
+
+    /// Calls `callback` each time `signal` occurs on the running task, such as every tick of its
+    /// sample clock or every counter output pulse.
+    ///
+    /// Like [`Task::on_every_n_samples`], register it before starting the task.
+    pub fn on_signal<F>(&mut self, signal: SignalEvent, mut callback: F) -> Result<()>
+    where
+        F: FnMut(&mut Task<TYPE>) + Send + 'static,
+        TYPE: 'static,
+    {
+        let handler = self.event_handler(move |task, _, _| callback(task));
+        daqmx_call!(
+            self.backend(),
+            register_signal_event(self.raw_handle(), signal.into(), Some(&handler)),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        self.keep_event_handler(handler);
+        Ok(())
+    }
+
+    /// Wraps an event callback so it gets the task. The handler only holds a weak reference, so
+    /// it doesn't keep the task alive.
+    ///
+    /// The driver doesn't support clearing a task inside its own callbacks, so if every other
+    /// handle was dropped while the callback ran, the task is cleared on another thread.
+    fn event_handler(
+        &self,
+        mut callback: impl FnMut(&mut Task<TYPE>, i32, u32) + Send + 'static,
+    ) -> Arc<EventHandler>
+    where
+        TYPE: 'static,
+    {
+        let task = Arc::downgrade(&self.handle);
+        let channel_count = self.channel_count;
+        EventHandler::new(move |value, samples| {
+            if let Some(mut task) = Self::from_weak(&task, channel_count) {
+                // The task is released before a panic carries on unwinding, so it isn't dropped
+                // on the way out either.
+                let result = catch_unwind(AssertUnwindSafe(|| callback(&mut task, value, samples)));
+                if let Some(handle) = Arc::into_inner(task.handle) {
+                    std::thread::spawn(move || drop(handle));
+                }
+                if let Err(panic) = result {
+                    resume_unwind(panic);
+                }
+            }
+        })
+    }
+
+    /// Keeps a registered handler alive until the task is cleared.
+    fn keep_event_handler(&self, handler: Arc<EventHandler>) {
+        self.handle
+            .events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(handler);
+    }
+
+    /// Recreates a task from the handle given to an event callback, if it hasn't been dropped.
+    fn from_weak(handle: &Weak<TaskHandle>, channel_count: usize) -> Option<Self> {
+        Some(Self {
+            handle: handle.upgrade()?,
+            channel_type: PhantomData,
+            channel_count,
+        })
+    }
+
     /// Gets the name assigned to the task in DAQmx.
     ///
     /// Useful if no name is specified.
@@ -93,22 +317,32 @@ impl<TYPE> Task<TYPE> {
     /// assert_ne!(&name, "");
     /// ```
     pub fn name(&mut self) -> Result<String> {
-        //first call to get size.
-        let return_code =
-            unsafe { daqmx::DAQmxGetTaskName(self.raw_handle(), std::ptr::null_mut(), 0) };
-        if return_code < 0 {
-            handle_error(return_code)?;
-        }
-
-        let buffer_size = return_code as u32;
-        let mut buffer: Vec<i8> = vec![0i8; buffer_size as usize];
-        daqmx_call!(daqmx::DAQmxGetTaskName(
-            self.raw_handle(),
-            buffer.as_mut_ptr(),
-            buffer_size
-        ))?;
+        let mut name = String::new();
+        daqmx_call!(
+            self.backend(),
+            task_name(self.raw_handle(), &mut name),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        Ok(name)
+    }
 
-        Ok(buffer_to_string(buffer))
+    /// Gets the names of the virtual channels in the task, in the order they are read or
+    /// written.
+    pub fn channel_names(&self) -> Result<Vec<String>> {
+        let mut channels = String::new();
+        daqmx_call!(
+            self.backend(),
+            task_channels(self.raw_handle(), &mut channels),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        Ok(channels
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
     }
 
     /// Configure a hardware timed task with the provided parameters.
@@ -127,22 +361,21 @@ impl<TYPE> Task<TYPE> {
         mode: types::SampleMode,
         samples_per_channel: u64,
     ) -> Result<()> {
-        let source_c;
-        let source_c_ptr = if let Some(name) = source {
-            source_c = CString::new(name)?;
-            source_c.as_ptr()
-        } else {
-            ptr::null()
-        };
+        let source_c = source.map(CString::new).transpose()?;
 
-        daqmx_call!(daqmx::DAQmxCfgSampClkTiming(
-            self.raw_handle(),
-            source_c_ptr,
-            rate,
-            edge.into(),
-            mode.into(),
-            samples_per_channel
-        ))
+        daqmx_call!(
+            self.backend(),
+            cfg_samp_clk_timing(
+                self.raw_handle(),
+                source_c.as_deref(),
+                rate,
+                edge.into(),
+                mode.into(),
+                samples_per_channel
+            ),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     /// Transitions the task from the committed state to the running state, which begins measurement or generation.
@@ -154,7 +387,41 @@ impl<TYPE> Task<TYPE> {
     ///If you do not call [`Task::start`] and [`Task::stop`] when you call NI-DAQmx Read functions or NI-DAQmx Write functions multiple times, such as in a loop, the task starts and stops repeatedly.
     /// Starting and stopping a task repeatedly reduces the performance of the application.
     pub fn start(&mut self) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxStartTask(self.raw_handle()))
+        daqmx_call!(
+            self.backend(),
+            start_task(self.raw_handle()),
+            self.error_context(),
+            self.warning_state()
+        )
+    }
+
+    /// Starts the task and returns a guard which stops it when dropped, also when unwinding from
+    /// a panic.
+    ///
+    /// The task can be read and written through the guard. Use [`RunningTask::finish`] to stop
+    /// the task and get the error if stopping fails; errors stopping on drop are only logged.
+    ///
+    /// ```no_run
+    /// use daqmx::channels::VoltageChannel;
+    /// use daqmx::tasks::{AnalogInput, InputTask, Task};
+    /// use daqmx::types::Timeout;
+    ///
+    /// # fn main() -> daqmx::error::Result<()> {
+    /// let mut task = Task::<AnalogInput>::new("run")?;
+    /// task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    ///
+    /// let mut running = task.run()?;
+    /// let value = running.read_scalar(Timeout::Seconds(1.0))?;
+    /// running.finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run(&mut self) -> Result<RunningTask<'_, TYPE>> {
+        self.start()?;
+        Ok(RunningTask {
+            task: self,
+            finished: false,
+        })
     }
 
     /// Stops the task and returns it to the state it was in before it was started.
@@ -162,39 +429,121 @@ impl<TYPE> Task<TYPE> {
     ///If you do not call [`Task::start`] and [`Task::stop`] when you call NI-DAQmx Read functions or NI-DAQmx Write functions multiple times, such as in a loop, the task starts and stops repeatedly.
     /// Starting and stopping a task repeatedly reduces the performance of the application.
     pub fn stop(&mut self) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxStopTask(self.raw_handle()))
+        daqmx_call!(
+            self.backend(),
+            stop_task(self.raw_handle()),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     /// Waits for the measurement or generation to complete. Use this function to ensure that the specified operation is complete before you stop the task.
     pub fn wait_until_done(&mut self, timeout: Timeout) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxWaitUntilTaskDone(
-            self.raw_handle(),
-            timeout.into()
-        ))
+        daqmx_call!(
+            self.backend(),
+            wait_until_task_done(self.raw_handle(), timeout.into()),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     /// Check if task is done
     pub fn is_done(&mut self) -> Result<bool> {
-        let mut done: bool32 = 0;
-        daqmx_call!(daqmx::DAQmxIsTaskDone(self.raw_handle(), &mut done))?;
-        Ok(done != 0)
+        let mut done = false;
+        daqmx_call!(
+            self.backend(),
+            is_task_done(self.raw_handle(), &mut done),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        Ok(done)
+    }
+
+    /// Requests a state transition through `DAQmxTaskControl`.
+    fn control(&mut self, action: types::TaskAction) -> Result<()> {
+        daqmx_call!(
+            self.backend(),
+            task_control(self.raw_handle(), action.into()),
+            self.error_context(),
+            self.warning_state()
+        )
+    }
+
+    /// Checks the task configuration is valid without reserving or programming the hardware.
+    pub fn verify(&mut self) -> Result<()> {
+        self.control(types::TaskAction::Verify)
+    }
+
+    /// Reserves the hardware and programs it with the task configuration, so that a following
+    /// [`Task::start`] is quick.
+    ///
+    /// Stopping a committed task returns it to the committed state, so committing once before a
+    /// start/stop loop avoids reprogramming the hardware on every start.
+    pub fn commit(&mut self) -> Result<()> {
+        self.control(types::TaskAction::Commit)
+    }
+
+    /// Reserves the hardware resources the task needs, failing if another task holds them.
+    pub fn reserve(&mut self) -> Result<()> {
+        self.control(types::TaskAction::Reserve)
+    }
+
+    /// Releases the hardware resources reserved or committed by the task. A running task is
+    /// aborted.
+    pub fn unreserve(&mut self) -> Result<()> {
+        self.control(types::TaskAction::Unreserve)
+    }
+
+    /// Stops the task immediately. Reads and writes waiting on the task return an error.
+    ///
+    /// Unlike [`Task::stop`] this doesn't wait for the current operation, so it can recover a task
+    /// hung in a read from another thread through a clone of the task.
+    pub fn abort(&mut self) -> Result<()> {
+        self.control(types::TaskAction::Abort)
     }
 
     ///Gets whether DAQmx read automatically starts the task.
     pub fn read_auto_start(&mut self) -> Result<bool> {
-        let mut value: bool32 = 0;
-        daqmx_call!(daqmx::DAQmxGetReadAutoStart(self.raw_handle(), &mut value))?;
-        Ok(value != 0)
+        self.read_task_property(TaskProperty::ReadAutoStart)
     }
 
     ///Sets whether DAQmx read automatically starts the task.
     pub fn set_read_auto_start(&mut self, value: bool) -> Result<()> {
-        let value: bool32 = match value {
-            true => 1,
-            false => 0,
-        };
-        daqmx_call!(daqmx::DAQmxSetReadAutoStart(self.raw_handle(), value))?;
-        Ok(())
+        self.write_task_property(TaskProperty::ReadAutoStart, PropertyValue::Bool(value))
+    }
+
+    /// The rate of the sample clock in samples per second. The device may coerce the rate
+    /// given to [`Task::configure_sample_clock_timing`] to one it can generate.
+    pub fn sample_clock_rate(&self) -> Result<f64> {
+        self.read_task_property(TaskProperty::SampleClockRate)
+    }
+
+    /// Whether the task acquires or generates a finite number of samples or runs continuously,
+    /// as set by [`Task::configure_sample_clock_timing`].
+    pub fn sample_mode(&self) -> Result<types::SampleMode> {
+        self.read_task_property::<i32>(TaskProperty::SampleMode)?
+            .try_into()
+    }
+
+    /// Gets whether the device timestamps the first sample of the task.
+    pub fn first_sample_timestamp_enabled(&self) -> Result<bool> {
+        self.read_task_property(TaskProperty::FirstSampleTimestampEnable)
+    }
+
+    /// Sets whether the device timestamps the first sample of the task, which gives waveforms
+    /// their start time. Only some devices support timestamps, others return an error.
+    pub fn set_first_sample_timestamp_enabled(&mut self, value: bool) -> Result<()> {
+        self.write_task_property(
+            TaskProperty::FirstSampleTimestampEnable,
+            PropertyValue::Bool(value),
+        )
+    }
+
+    /// The number of virtual channels in the task, which [`Task::channel_count`] undercounts
+    /// when a builder adds a range of channels.
+    pub(crate) fn num_channels(&self) -> Result<usize> {
+        self.read_task_property::<u32>(TaskProperty::NumChannels)
+            .map(|channels| channels as usize)
     }
 
     /// Configures a digital edge start trigger source for the task.
@@ -205,16 +554,22 @@ impl<TYPE> Task<TYPE> {
     /// - `"/DevX/Ctr0InternalOutput"`
     pub fn configure_trigger(&mut self, source: &str, edge: types::ClockEdge) -> Result<()> {
         let source_c = CString::new(source)?;
-        daqmx_call!(daqmx::DAQmxCfgDigEdgeStartTrig(
-            self.raw_handle(),
-            source_c.as_ptr(),
-            edge.into()
-        ))
+        daqmx_call!(
+            self.backend(),
+            cfg_dig_edge_start_trig(self.raw_handle(), &source_c, edge.into()),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     /// Disable the task's configured start trigger.
     pub fn disable_start_trigger(&mut self) -> Result<()> {
-        daqmx_call!(daqmx::DAQmxDisableStartTrig(self.raw_handle()))
+        daqmx_call!(
+            self.backend(),
+            disable_start_trig(self.raw_handle()),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     /// Configure a digital edge reference trigger.
@@ -225,21 +580,96 @@ impl<TYPE> Task<TYPE> {
         pretrigger_samples: u32,
     ) -> Result<()> {
         let source_c = CString::new(source)?;
-        daqmx_call!(daqmx::DAQmxCfgDigEdgeRefTrig(
-            self.raw_handle(),
-            source_c.as_ptr(),
-            edge.into(),
-            pretrigger_samples
-        ))
+        daqmx_call!(
+            self.backend(),
+            cfg_dig_edge_ref_trig(
+                self.raw_handle(),
+                &source_c,
+                edge.into(),
+                pretrigger_samples
+            ),
+            self.error_context(),
+            self.warning_state()
+        )
     }
 
     /// Export an internal signal to a terminal (PFI/RTSI).
     pub fn export_signal(&mut self, signal: ExportSignal, terminal: &str) -> Result<()> {
         let terminal_c = CString::new(terminal)?;
-        daqmx_call!(daqmx::DAQmxExportSignal(
-            self.raw_handle(),
-            signal.into(),
-            terminal_c.as_ptr()
-        ))
+        daqmx_call!(
+            self.backend(),
+            export_signal(self.raw_handle(), signal.into(), &terminal_c),
+            self.error_context(),
+            self.warning_state()
+        )
+    }
+
+    pub(crate) fn read_task_property<
+        T: TryFrom<PropertyValue, Error = crate::error::DaqmxError>,
+    >(
+        &self,
+        property: TaskProperty,
+    ) -> Result<T> {
+        let mut value = PropertyValue::Bool(false);
+        daqmx_call!(
+            self.backend(),
+            get_task_property(self.raw_handle(), property, &mut value),
+            self.error_context(),
+            self.warning_state()
+        )?;
+        T::try_from(value)
+    }
+
+    pub(crate) fn write_task_property(
+        &mut self,
+        property: TaskProperty,
+        value: PropertyValue,
+    ) -> Result<()> {
+        daqmx_call!(
+            self.backend(),
+            set_task_property(self.raw_handle(), property, &value),
+            self.error_context(),
+            self.warning_state()
+        )
+    }
+}
+
+/// A started task which is stopped when the guard is dropped. Returned by [`Task::run`].
+///
+/// Dereferences to the [`Task`] for reading and writing.
+pub struct RunningTask<'a, TYPE> {
+    task: &'a mut Task<TYPE>,
+    finished: bool,
+}
+
+impl<TYPE> RunningTask<'_, TYPE> {
+    /// Stops the task, returning any error from stopping it.
+    pub fn finish(mut self) -> Result<()> {
+        self.finished = true;
+        self.task.stop()
+    }
+}
+
+impl<TYPE> Deref for RunningTask<'_, TYPE> {
+    type Target = Task<TYPE>;
+
+    fn deref(&self) -> &Task<TYPE> {
+        self.task
+    }
+}
+
+impl<TYPE> DerefMut for RunningTask<'_, TYPE> {
+    fn deref_mut(&mut self) -> &mut Task<TYPE> {
+        self.task
+    }
+}
+
+impl<TYPE> Drop for RunningTask<'_, TYPE> {
+    fn drop(&mut self) {
+        if !self.finished {
+            if let Err(error) = self.task.stop() {
+                error!("Failed to stop task when dropping its running guard: {error}");
+            }
+        }
     }
 }
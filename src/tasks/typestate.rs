@@ -0,0 +1,492 @@
+//! An opt-in task API which tracks the task state in its type.
+//!
+//! A [`TypedTask`] starts out [`Configuring`], where channels, timing and triggers can be set.
+//! [`TypedTask::commit`] and [`TypedTask::start`] consume it and return a [`Committed`] or
+//! [`Running`] task, which can be read and written but no longer configured, and
+//! [`TypedTask::stop`] returns it to [`Configuring`]. Calling these in the wrong order is a compile
+//! error rather than a DAQmx error at runtime:
+//!
+//! ```compile_fail
+//! use daqmx::tasks::typestate::TypedTask;
+//! use daqmx::tasks::{AnalogInput, InputTask};
+//! use daqmx::types::Timeout;
+//!
+//! let mut task = TypedTask::<AnalogInput>::new("ai")?;
+//! // Reading needs a committed or running task.
+//! task.read_scalar(Timeout::Seconds(1.0))?;
+//! # Ok::<(), daqmx::Error>(())
+//! ```
+//!
+//! ```no_run
+//! use daqmx::channels::VoltageChannel;
+//! use daqmx::tasks::typestate::TypedTask;
+//! use daqmx::tasks::{AnalogInput, InputTask};
+//! use daqmx::types::Timeout;
+//!
+//! # fn main() -> daqmx::error::Result<()> {
+//! let mut task = TypedTask::<AnalogInput>::new("typed")?;
+//! task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+//!
+//! let mut task = task.start()?;
+//! let value = task.read_scalar(Timeout::Seconds(1.0))?;
+//! let task = task.stop()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A transition which fails returns a [`TransitionError`] holding the task in the state it was
+//! in, so nothing configured is lost and the transition can be retried. `?` converts it into the
+//! [`DaqmxError`], dropping the task.
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::backend::Backend;
+use crate::channels::{AnalogChannelBuilderTrait, ChannelBuilderInput, ChannelBuilderOutput};
+use crate::daqmx;
+use crate::error::{DaqmxError, ErrorContext, Result};
+use crate::types::{ClockEdge, ExportSignal, SampleMode, Timeout};
+use crate::warnings::{TaskWarnings, Warning, WarningPolicy};
+
+use super::input::{DAQmxInput, InputTask};
+use super::output::{DAQmxOutput, OutputTask};
+use super::{
+    AnalogInput, AnalogOutput, CounterInput, CounterInputTask, CounterOutput, CounterOutputTask,
+    DigitalInput, DigitalOutput, Task,
+};
+
+/// The task is being configured and can't read or write yet.
+pub struct Configuring;
+
+/// The task is committed to the hardware, so it starts quickly. Reads and writes are allowed,
+/// for example to preload an output buffer.
+pub struct Committed;
+
+/// The task is running.
+pub struct Running;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Configuring {}
+    impl Sealed for super::Committed {}
+    impl Sealed for super::Running {}
+}
+
+/// A state of a [`TypedTask`].
+pub trait TaskState: sealed::Sealed {}
+impl TaskState for Configuring {}
+impl TaskState for Committed {}
+impl TaskState for Running {}
+
+/// The states in which a [`TypedTask`] can read and write.
+pub trait Active: TaskState {}
+impl Active for Committed {}
+impl Active for Running {}
+
+/// A transition of a [`TypedTask`] which failed, with the task still in the state `STATE` it was
+/// in before.
+pub struct TransitionError<TYPE, STATE: TaskState> {
+    pub task: TypedTask<TYPE, STATE>,
+    pub error: DaqmxError,
+}
+
+/// The result of a transition of a [`TypedTask`] from `STATE` to `NEXT`.
+pub type TransitionResult<TYPE, STATE, NEXT> =
+    std::result::Result<TypedTask<TYPE, NEXT>, TransitionError<TYPE, STATE>>;
+
+impl<TYPE, STATE: TaskState> std::fmt::Debug for TransitionError<TYPE, STATE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransitionError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<TYPE, STATE: TaskState> std::fmt::Display for TransitionError<TYPE, STATE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<TYPE, STATE: TaskState> std::error::Error for TransitionError<TYPE, STATE> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+impl<TYPE, STATE: TaskState> From<TransitionError<TYPE, STATE>> for DaqmxError {
+    fn from(error: TransitionError<TYPE, STATE>) -> Self {
+        error.error
+    }
+}
+
+/// A [`Task`] which couldn't be converted into a [`TypedTask`], handed back with the reason.
+pub struct TryFromTaskError<TYPE> {
+    pub task: Task<TYPE>,
+    pub error: DaqmxError,
+}
+
+impl<TYPE> std::fmt::Debug for TryFromTaskError<TYPE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TryFromTaskError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<TYPE> std::fmt::Display for TryFromTaskError<TYPE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<TYPE> std::error::Error for TryFromTaskError<TYPE> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+impl<TYPE> From<TryFromTaskError<TYPE>> for DaqmxError {
+    fn from(error: TryFromTaskError<TYPE>) -> Self {
+        error.error
+    }
+}
+
+/// A [`Task`] whose state is tracked by the type parameter `STATE`.
+pub struct TypedTask<TYPE, STATE: TaskState = Configuring> {
+    task: Task<TYPE>,
+    state: PhantomData<STATE>,
+}
+
+impl<TYPE, STATE: TaskState> TypedTask<TYPE, STATE> {
+    fn into_state<NEXT: TaskState>(self) -> TypedTask<TYPE, NEXT> {
+        TypedTask {
+            task: self.task,
+            state: PhantomData,
+        }
+    }
+
+    /// Runs `change` on the task and moves to the state `NEXT` if it succeeds, or hands the task
+    /// back in this state if it fails.
+    fn transition<NEXT: TaskState>(
+        mut self,
+        change: impl FnOnce(&mut Task<TYPE>) -> Result<()>,
+    ) -> TransitionResult<TYPE, STATE, NEXT> {
+        match change(&mut self.task) {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err(TransitionError { task: self, error }),
+        }
+    }
+
+    /// Gets the name assigned to the task in DAQmx.
+    pub fn name(&mut self) -> Result<String> {
+        self.task.name()
+    }
+
+    /// See [`Task::set_warning_policy`].
+    pub fn set_warning_policy(&mut self, policy: Option<WarningPolicy>) {
+        self.task.set_warning_policy(policy);
+    }
+
+    /// See [`Task::take_warnings`].
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        self.task.take_warnings()
+    }
+
+    /// Returns the untyped task, for functions this API doesn't cover.
+    pub fn into_inner(self) -> Task<TYPE> {
+        self.task
+    }
+}
+
+impl<TYPE> TypedTask<TYPE, Configuring> {
+    /// Creates a task on the default backend. See [`Task::new`].
+    pub fn new(name: &str) -> Result<Self> {
+        Ok(Self {
+            task: Task::new(name)?,
+            state: PhantomData,
+        })
+    }
+
+    /// Creates a task on `backend`. See [`Task::with_backend`].
+    pub fn with_backend(name: &str, backend: Arc<dyn Backend>) -> Result<Self> {
+        Ok(Self {
+            task: Task::with_backend(name, backend)?,
+            state: PhantomData,
+        })
+    }
+
+    /// See [`Task::configure_sample_clock_timing`].
+    pub fn configure_sample_clock_timing(
+        &mut self,
+        source: Option<&str>,
+        rate: f64,
+        active_edge: ClockEdge,
+        sample_mode: SampleMode,
+        samples_per_channel: u64,
+    ) -> Result<()> {
+        self.task.configure_sample_clock_timing(
+            source,
+            rate,
+            active_edge,
+            sample_mode,
+            samples_per_channel,
+        )
+    }
+
+    /// See [`Task::configure_trigger`].
+    pub fn configure_trigger(&mut self, source: &str, edge: ClockEdge) -> Result<()> {
+        self.task.configure_trigger(source, edge)
+    }
+
+    /// See [`Task::configure_reference_trigger`].
+    pub fn configure_reference_trigger(
+        &mut self,
+        source: &str,
+        edge: ClockEdge,
+        pretrigger_samples: u32,
+    ) -> Result<()> {
+        self.task
+            .configure_reference_trigger(source, edge, pretrigger_samples)
+    }
+
+    /// See [`Task::disable_start_trigger`].
+    pub fn disable_start_trigger(&mut self) -> Result<()> {
+        self.task.disable_start_trigger()
+    }
+
+    /// See [`Task::export_signal`].
+    pub fn export_signal(&mut self, signal: ExportSignal, terminal: &str) -> Result<()> {
+        self.task.export_signal(signal, terminal)
+    }
+
+    /// See [`Task::set_read_auto_start`].
+    pub fn set_read_auto_start(&mut self, value: bool) -> Result<()> {
+        self.task.set_read_auto_start(value)
+    }
+
+    /// Programs the hardware with the configuration, so starting the task is quick.
+    pub fn commit(self) -> TransitionResult<TYPE, Configuring, Committed> {
+        self.transition(Task::commit)
+    }
+
+    /// Starts the task.
+    pub fn start(self) -> TransitionResult<TYPE, Configuring, Running> {
+        self.transition(Task::start)
+    }
+}
+
+/// Wraps a task which is not running. A committed task is unreserved, so it can be configured
+/// again. A running task, or one which fails to unreserve, is handed back in the error.
+impl<TYPE> TryFrom<Task<TYPE>> for TypedTask<TYPE, Configuring> {
+    type Error = TryFromTaskError<TYPE>;
+
+    fn try_from(mut task: Task<TYPE>) -> std::result::Result<Self, Self::Error> {
+        let checked = match task.is_done() {
+            Ok(true) => task.stop().and_then(|()| task.unreserve()),
+            Ok(false) => Err(DaqmxError::Validation(
+                "A running task can't be configured, stop it first.".to_string(),
+            )),
+            Err(error) => Err(error),
+        };
+        match checked {
+            Ok(()) => Ok(Self {
+                task,
+                state: PhantomData,
+            }),
+            Err(error) => Err(TryFromTaskError { task, error }),
+        }
+    }
+}
+
+impl<TYPE> TypedTask<TYPE, Committed> {
+    /// Starts the task.
+    pub fn start(self) -> TransitionResult<TYPE, Committed, Running> {
+        self.transition(Task::start)
+    }
+
+    /// Releases the hardware so the task can be configured again.
+    pub fn unreserve(self) -> TransitionResult<TYPE, Committed, Configuring> {
+        self.transition(Task::unreserve)
+    }
+}
+
+impl<TYPE> TypedTask<TYPE, Running> {
+    /// Stops the task so it can be configured again.
+    pub fn stop(self) -> TransitionResult<TYPE, Running, Configuring> {
+        self.transition(Task::stop)
+    }
+
+    /// See [`Task::wait_until_done`].
+    pub fn wait_until_done(&mut self, timeout: Timeout) -> Result<()> {
+        self.task.wait_until_done(timeout)
+    }
+
+    /// See [`Task::is_done`].
+    pub fn is_done(&mut self) -> Result<bool> {
+        self.task.is_done()
+    }
+}
+
+impl TypedTask<AnalogInput, Configuring> {
+    pub fn create_channel<B: AnalogChannelBuilderTrait + ChannelBuilderInput>(
+        &mut self,
+        builder: B,
+    ) -> Result<()> {
+        self.task.create_channel(builder)
+    }
+}
+
+impl TypedTask<AnalogOutput, Configuring> {
+    pub fn create_channel<B: AnalogChannelBuilderTrait + ChannelBuilderOutput>(
+        &mut self,
+        builder: B,
+    ) -> Result<()> {
+        self.task.create_channel(builder)
+    }
+}
+
+impl TypedTask<DigitalInput, Configuring> {
+    pub fn create_channel<B: ChannelBuilderInput>(&mut self, builder: B) -> Result<()> {
+        self.task.create_channel(builder)
+    }
+}
+
+impl TypedTask<DigitalOutput, Configuring> {
+    pub fn create_channel<B: ChannelBuilderOutput>(&mut self, builder: B) -> Result<()> {
+        self.task.create_channel(builder)
+    }
+}
+
+impl TypedTask<CounterInput, Configuring> {
+    pub fn create_channel<B: ChannelBuilderInput>(&mut self, builder: B) -> Result<()> {
+        self.task.create_channel(builder)
+    }
+}
+
+impl TypedTask<CounterOutput, Configuring> {
+    pub fn create_channel<B: ChannelBuilderOutput>(&mut self, builder: B) -> Result<()> {
+        self.task.create_channel(builder)
+    }
+
+    /// See [`CounterOutputTask::configure_implicit_timing`].
+    pub fn configure_implicit_timing(
+        &mut self,
+        mode: SampleMode,
+        samples_per_channel: u64,
+    ) -> Result<()> {
+        self.task
+            .configure_implicit_timing(mode, samples_per_channel)
+    }
+}
+
+impl<T, TYPE, STATE: Active> InputTask<T> for TypedTask<TYPE, STATE>
+where
+    Task<TYPE>: InputTask<T>,
+{
+    fn read_scalar(&mut self, timeout: Timeout) -> Result<T> {
+        self.task.read_scalar(timeout)
+    }
+}
+
+impl<T, TYPE, STATE: Active> DAQmxInput<T> for TypedTask<TYPE, STATE>
+where
+    Task<TYPE>: DAQmxInput<T>,
+{
+    fn backend(&self) -> &dyn Backend {
+        DAQmxInput::<T>::backend(&self.task)
+    }
+
+    fn read_error_context(&self) -> ErrorContext {
+        self.task.read_error_context()
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        DAQmxInput::<T>::task_warnings(&self.task)
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.task.raw_handle()
+    }
+
+    unsafe fn daqmx_read(
+        &mut self,
+        samples_per_channel: i32,
+        timeout: f64,
+        fill_mode: daqmx::bool32,
+        buffer: &mut [T],
+        buffer_size: u32,
+        actual_samples_per_channel: *mut i32,
+    ) -> i32 {
+        self.task.daqmx_read(
+            samples_per_channel,
+            timeout,
+            fill_mode,
+            buffer,
+            buffer_size,
+            actual_samples_per_channel,
+        )
+    }
+}
+
+impl<T, TYPE, STATE: Active> OutputTask<T> for TypedTask<TYPE, STATE>
+where
+    Task<TYPE>: OutputTask<T>,
+{
+    fn write_scalar(&mut self, value: T, timeout: Timeout) -> Result<()> {
+        self.task.write_scalar(value, timeout)
+    }
+}
+
+impl<T, TYPE, STATE: Active> DAQmxOutput<T> for TypedTask<TYPE, STATE>
+where
+    Task<TYPE>: DAQmxOutput<T>,
+{
+    fn backend(&self) -> &dyn Backend {
+        DAQmxOutput::<T>::backend(&self.task)
+    }
+
+    fn write_error_context(&self) -> ErrorContext {
+        self.task.write_error_context()
+    }
+
+    fn task_warnings(&self) -> &TaskWarnings {
+        DAQmxOutput::<T>::task_warnings(&self.task)
+    }
+
+    fn raw_task_handle(&self) -> daqmx::TaskHandle {
+        self.task.raw_handle()
+    }
+
+    fn elements_per_sample(&self) -> Result<usize> {
+        DAQmxOutput::<T>::elements_per_sample(&self.task)
+    }
+
+    unsafe fn daqmx_write(
+        &mut self,
+        samples_per_channel: i32,
+        auto_start: daqmx::bool32,
+        timeout: f64,
+        fill_mode: daqmx::bool32,
+        buffer: &[T],
+        actual_samples_per_channel: *mut i32,
+    ) -> i32 {
+        self.task.daqmx_write(
+            samples_per_channel,
+            auto_start,
+            timeout,
+            fill_mode,
+            buffer,
+            actual_samples_per_channel,
+        )
+    }
+}
+
+impl<STATE: Active> CounterInputTask for TypedTask<CounterInput, STATE> {
+    fn read_count_scalar(&mut self, timeout: Timeout) -> Result<u32> {
+        self.task.read_count_scalar(timeout)
+    }
+
+    fn read_period_scalar(&mut self, timeout: Timeout) -> Result<f64> {
+        self.task.read_period_scalar(timeout)
+    }
+}
@@ -0,0 +1,129 @@
+//! Samples of a channel together with their timing.
+use std::time::{Duration, SystemTime};
+
+use super::Task;
+use crate::backend::TaskProperty;
+use crate::error::{DaqmxError, ErrorKind, Result};
+
+/// The samples of one channel with the time of the first sample and the interval between
+/// samples, like the waveforms of NI's other APIs.
+///
+/// Returned by [`Task::<AnalogInput>::read_waveforms`](Task::read_waveforms), which times it
+/// by the sample clock rate of the task. `t0` is only known if the device timestamps the first
+/// sample, see [`Task::set_first_sample_timestamp_enabled`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Waveform<T> {
+    /// The virtual channel the samples were read from.
+    pub channel: String,
+    /// The units of the samples, such as `Volts`.
+    pub units: String,
+    /// When the first sample was acquired, if known.
+    pub t0: Option<SystemTime>,
+    /// The interval between samples in seconds.
+    pub dt: f64,
+    pub samples: Vec<T>,
+}
+
+impl<T> Waveform<T> {
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Samples per second.
+    pub fn sample_rate(&self) -> f64 {
+        1.0 / self.dt
+    }
+
+    /// The time covered by the samples in seconds, from the first sample to the end of the last
+    /// interval.
+    pub fn duration(&self) -> f64 {
+        self.len() as f64 * self.dt
+    }
+
+    /// The time of each sample in seconds from the first sample.
+    pub fn times(&self) -> impl ExactSizeIterator<Item = f64> + '_ {
+        (0..self.len()).map(|index| index as f64 * self.dt)
+    }
+
+    /// When sample `index` was acquired, if `t0` is known and `dt` is a valid interval.
+    pub fn timestamp(&self, index: usize) -> Option<SystemTime> {
+        offset_time(self.t0?, index as f64 * self.dt)
+    }
+}
+
+impl<TYPE> Task<TYPE> {
+    /// The interval between samples in seconds, from the sample clock rate.
+    pub(crate) fn sample_interval(&self) -> Result<f64> {
+        let rate = self.sample_clock_rate()?;
+        if rate > 0.0 && rate.is_finite() {
+            Ok(1.0 / rate)
+        } else {
+            Err(DaqmxError::Validation(format!(
+                "The sample clock rate of {rate} S/s can't time samples."
+            )))
+        }
+    }
+
+    /// The position in the acquisition of the next sample to be read.
+    pub(crate) fn read_position(&self) -> Result<u64> {
+        self.read_task_property(TaskProperty::ReadCurrentReadPosition)
+    }
+
+    /// When the sample at `position` was acquired, or [`None`] if the first sample wasn't
+    /// timestamped because the device doesn't support it or it isn't enabled.
+    pub(crate) fn sample_timestamp(&self, position: u64, dt: f64) -> Result<Option<SystemTime>> {
+        match self.read_task_property::<SystemTime>(TaskProperty::FirstSampleTimestamp) {
+            Ok(first) => Ok(offset_time(first, position as f64 * dt)),
+            Err(error) if error.kind() == Some(ErrorKind::PropertyNotSupported) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// `time` plus `seconds`, or [`None`] if `seconds` isn't a valid duration.
+fn offset_time(time: SystemTime, seconds: f64) -> Option<SystemTime> {
+    time.checked_add(Duration::try_from_secs_f64(seconds).ok()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_waveform_timing() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let waveform = Waveform {
+            channel: "ai0".to_string(),
+            units: "Volts".to_string(),
+            t0: Some(t0),
+            dt: 0.001,
+            samples: vec![0.0; 4],
+        };
+        assert_eq!(waveform.sample_rate(), 1000.0);
+        assert!((waveform.duration() - 0.004).abs() < 1e-12);
+        assert_eq!(waveform.times().nth(2), Some(0.002));
+        assert_eq!(waveform.timestamp(3), Some(t0 + Duration::from_millis(3)));
+        for dt in [f64::NAN, f64::INFINITY, -0.001] {
+            assert_eq!(
+                Waveform {
+                    dt,
+                    ..waveform.clone()
+                }
+                .timestamp(3),
+                None
+            );
+        }
+        assert_eq!(
+            Waveform {
+                t0: None,
+                ..waveform
+            }
+            .timestamp(3),
+            None
+        );
+    }
+}
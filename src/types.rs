@@ -2,6 +2,7 @@
 // https://github.com/WiresmithTech/daqmx-rs
 
 use crate::daqmx;
+use crate::error::DaqmxError;
 
 /// Module for handling FFI interface types and general DAQmx Types.
 /// The FFI exposes the char* interface as i8 and requires preallocation in a way
@@ -24,6 +25,7 @@ pub(crate) fn buffer_to_string(buffer: Vec<i8>) -> String {
 /// Describes the memory layout of a 1D buffer that represents 2D data.
 ///
 /// This will impact the access patterns when you read the data which can impact performance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DataFillMode {
     /// The layout groups data by channel. i.e. [Channel 0 Sample 0-2, Channel 1 Sample 0-2]
     /// Also known as noninterleaved.
@@ -43,6 +45,7 @@ impl From<DataFillMode> for daqmx::bool32 {
 }
 
 /// Enum representing the timeout options in the read and write APIs.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Timeout {
     /// Wait forever for the samples to become available.
     WaitForever,
@@ -208,6 +211,243 @@ impl From<SampleMode> for i32 {
     }
 }
 
+impl TryFrom<i32> for SampleMode {
+    type Error = DaqmxError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            daqmx::DAQmx_Val_FiniteSamps => Ok(Self::FiniteSamples),
+            daqmx::DAQmx_Val_ContSamps => Ok(Self::ContinuousSamples),
+            daqmx::DAQmx_Val_HWTimedSinglePoint => Ok(Self::HardwareTimedSinglePoint),
+            _ => Err(DaqmxError::UnexpectedValue("SampleMode".to_string(), value)),
+        }
+    }
+}
+
+/// The task state transitions of `DAQmxTaskControl`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskAction {
+    /// Start the task.
+    Start,
+    /// Stop the task.
+    Stop,
+    /// Check the task configuration is valid.
+    Verify,
+    /// Program the hardware with the task configuration so it starts faster.
+    Commit,
+    /// Reserve the hardware resources the task needs.
+    Reserve,
+    /// Release the hardware resources, aborting the task if it is running.
+    Unreserve,
+    /// Stop the task immediately, without completing the current operation.
+    Abort,
+}
+
+impl From<TaskAction> for i32 {
+    fn from(action: TaskAction) -> Self {
+        match action {
+            TaskAction::Start => daqmx::DAQmx_Val_Task_Start,
+            TaskAction::Stop => daqmx::DAQmx_Val_Task_Stop,
+            TaskAction::Verify => daqmx::DAQmx_Val_Task_Verify,
+            TaskAction::Commit => daqmx::DAQmx_Val_Task_Commit,
+            TaskAction::Reserve => daqmx::DAQmx_Val_Task_Reserve,
+            TaskAction::Unreserve => daqmx::DAQmx_Val_Task_Unreserve,
+            TaskAction::Abort => daqmx::DAQmx_Val_Task_Abort,
+        }
+    }
+}
+
+/// When an Every N Samples event fires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EveryNSamplesEvent {
+    /// After N samples per channel are acquired into the buffer of an input task.
+    AcquiredIntoBuffer,
+    /// After N samples per channel are transferred from the buffer of an output task to the device.
+    TransferredFromBuffer,
+}
+
+impl From<EveryNSamplesEvent> for i32 {
+    fn from(event: EveryNSamplesEvent) -> Self {
+        match event {
+            EveryNSamplesEvent::AcquiredIntoBuffer => daqmx::DAQmx_Val_Acquired_Into_Buffer,
+            EveryNSamplesEvent::TransferredFromBuffer => daqmx::DAQmx_Val_Transferred_From_Buffer,
+        }
+    }
+}
+
+/// Signals which can call a closure registered with
+/// [`Task::on_signal`](crate::tasks::Task::on_signal).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignalEvent {
+    /// The sample clock of the task.
+    SampleClock,
+    /// A sample of a hardware timed single point task completing.
+    SampleComplete,
+    /// A change detected on the lines of a change detection digital input task.
+    ChangeDetection,
+    /// The output event of a counter output task.
+    CounterOutput,
+}
+
+impl From<SignalEvent> for i32 {
+    fn from(signal: SignalEvent) -> Self {
+        match signal {
+            SignalEvent::SampleClock => daqmx::DAQmx_Val_SampleClock,
+            SignalEvent::SampleComplete => daqmx::DAQmx_Val_SampleCompleteEvent,
+            SignalEvent::ChangeDetection => daqmx::DAQmx_Val_ChangeDetectionEvent,
+            SignalEvent::CounterOutput => daqmx::DAQmx_Val_CounterOutputEvent,
+        }
+    }
+}
+
+/// What a continuous acquisition does when the input buffer is full of unread samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverwriteMode {
+    /// Keep acquiring, overwriting the oldest unread samples.
+    OverwriteUnreadSamples,
+    /// Stop with an error on the next read. This is the default.
+    DoNotOverwriteUnreadSamples,
+}
+
+impl From<OverwriteMode> for i32 {
+    fn from(mode: OverwriteMode) -> Self {
+        match mode {
+            OverwriteMode::OverwriteUnreadSamples => daqmx::DAQmx_Val_OverwriteUnreadSamps,
+            OverwriteMode::DoNotOverwriteUnreadSamples => {
+                daqmx::DAQmx_Val_DoNotOverwriteUnreadSamps
+            }
+        }
+    }
+}
+
+impl TryFrom<i32> for OverwriteMode {
+    type Error = DaqmxError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            daqmx::DAQmx_Val_OverwriteUnreadSamps => Ok(Self::OverwriteUnreadSamples),
+            daqmx::DAQmx_Val_DoNotOverwriteUnreadSamps => Ok(Self::DoNotOverwriteUnreadSamples),
+            _ => Err(DaqmxError::UnexpectedValue(
+                "OverwriteMode".to_string(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The point in the input buffer which reads start from, before applying the read offset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadRelativeTo {
+    /// The first sample acquired.
+    FirstSample,
+    /// The sample after the last one read. This is the default.
+    CurrentReadPosition,
+    /// The first sample after the reference trigger.
+    ReferenceTrigger,
+    /// The first pretrigger sample of a reference triggered acquisition.
+    FirstPretriggerSample,
+    /// The sample after the most recent one acquired, so an offset of `-n` reads the latest `n`
+    /// samples.
+    MostRecentSample,
+}
+
+impl From<ReadRelativeTo> for i32 {
+    fn from(relative_to: ReadRelativeTo) -> Self {
+        match relative_to {
+            ReadRelativeTo::FirstSample => daqmx::DAQmx_Val_FirstSample,
+            ReadRelativeTo::CurrentReadPosition => daqmx::DAQmx_Val_CurrReadPos,
+            ReadRelativeTo::ReferenceTrigger => daqmx::DAQmx_Val_RefTrig,
+            ReadRelativeTo::FirstPretriggerSample => daqmx::DAQmx_Val_FirstPretrigSamp,
+            ReadRelativeTo::MostRecentSample => daqmx::DAQmx_Val_MostRecentSamp,
+        }
+    }
+}
+
+impl TryFrom<i32> for ReadRelativeTo {
+    type Error = DaqmxError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            daqmx::DAQmx_Val_FirstSample => Ok(Self::FirstSample),
+            daqmx::DAQmx_Val_CurrReadPos => Ok(Self::CurrentReadPosition),
+            daqmx::DAQmx_Val_RefTrig => Ok(Self::ReferenceTrigger),
+            daqmx::DAQmx_Val_FirstPretrigSamp => Ok(Self::FirstPretriggerSample),
+            daqmx::DAQmx_Val_MostRecentSamp => Ok(Self::MostRecentSample),
+            _ => Err(DaqmxError::UnexpectedValue(
+                "ReadRelativeTo".to_string(),
+                value,
+            )),
+        }
+    }
+}
+
+/// Whether an output generates the samples in its buffer again once it has generated them all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegenerationMode {
+    /// Loop over the buffer until the task stops. This is the default.
+    AllowRegeneration,
+    /// Generate each sample once. The generation stops with an error if the buffer isn't
+    /// written fast enough.
+    DoNotAllowRegeneration,
+}
+
+impl From<RegenerationMode> for i32 {
+    fn from(mode: RegenerationMode) -> Self {
+        match mode {
+            RegenerationMode::AllowRegeneration => daqmx::DAQmx_Val_AllowRegen,
+            RegenerationMode::DoNotAllowRegeneration => daqmx::DAQmx_Val_DoNotAllowRegen,
+        }
+    }
+}
+
+impl TryFrom<i32> for RegenerationMode {
+    type Error = DaqmxError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            daqmx::DAQmx_Val_AllowRegen => Ok(Self::AllowRegeneration),
+            daqmx::DAQmx_Val_DoNotAllowRegen => Ok(Self::DoNotAllowRegeneration),
+            _ => Err(DaqmxError::UnexpectedValue(
+                "RegenerationMode".to_string(),
+                value,
+            )),
+        }
+    }
+}
+
+/// The point in the output buffer which writes start from, before applying the write offset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteRelativeTo {
+    /// The start of the buffer, to replace a regenerated waveform.
+    FirstSample,
+    /// The sample after the last one written. This is the default.
+    CurrentWritePosition,
+}
+
+impl From<WriteRelativeTo> for i32 {
+    fn from(relative_to: WriteRelativeTo) -> Self {
+        match relative_to {
+            WriteRelativeTo::FirstSample => daqmx::DAQmx_Val_FirstSample,
+            WriteRelativeTo::CurrentWritePosition => daqmx::DAQmx_Val_CurrWritePos,
+        }
+    }
+}
+
+impl TryFrom<i32> for WriteRelativeTo {
+    type Error = DaqmxError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            daqmx::DAQmx_Val_FirstSample => Ok(Self::FirstSample),
+            daqmx::DAQmx_Val_CurrWritePos => Ok(Self::CurrentWritePosition),
+            _ => Err(DaqmxError::UnexpectedValue(
+                "WriteRelativeTo".to_string(),
+                value,
+            )),
+        }
+    }
+}
+
 //Used quite a bit so lets re-export here with conversion.
 pub use daqmx::bool32;
 
@@ -1,7 +1,8 @@
 use std::ffi::CString;
 
+use crate::backend::default_backend;
 use crate::daqmx_call;
-use anyhow::Result;
+use crate::error::Result;
 /// # Purpose
 /// Immediately aborts all tasks associated with a device and returns the device to an initialized state.
 /// Aborting a task stops and releases any resources the task reserved.
@@ -11,31 +12,24 @@ use anyhow::Result;
 /// If you attempt to reset an attached module during this time, you will receive an error.
 pub fn reset_device(device: &str) -> Result<()> {
     let c_device = CString::new(device)?;
-    let d_ptr = c_device.as_ptr();
 
-    daqmx_call!(crate::daqmx::DAQmxResetDevice(d_ptr))?;
+    daqmx_call!(default_backend().as_ref(), reset_device(&c_device))?;
 
     Ok(())
 }
 
 pub mod info {
-    use anyhow::Result;
+    use crate::error::Result;
     use std::ffi::CString;
 
-    use crate::{
-        daqmx::{
-            DAQmxGetDevAIPhysicalChans, DAQmxGetDevAOPhysicalChans, DAQmxGetDevCIPhysicalChans,
-            DAQmxGetDevCOPhysicalChans, DAQmxGetDevDILines, DAQmxGetDevDOLines,
-        },
-        daqmx_call,
-        types::buffer_to_string,
-    };
+    use crate::{backend::default_backend, daqmx_call};
 
     #[cfg_attr(
         feature = "serde_support",
         derive(serde::Serialize, serde::Deserialize)
     )]
     #[cfg_attr(feature = "specta_support", derive(specta::Type))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub enum ChannelType {
         AI, // Analog Input
         AO, // Analog Output
@@ -50,22 +44,13 @@ pub mod info {
         channel_type: ChannelType,
         trim_device_name: bool,
     ) -> Result<Vec<String>> {
-        let mut buf = vec![0i8; 5120];
         let c_device = CString::new(device)?;
 
-        let b_ptr = buf.as_mut_ptr();
-        let d_ptr = c_device.as_ptr();
-
-        match channel_type {
-            ChannelType::DI => daqmx_call!(DAQmxGetDevDILines(d_ptr, b_ptr, 5120))?,
-            ChannelType::DO => daqmx_call!(DAQmxGetDevDOLines(d_ptr, b_ptr, 5120))?,
-            ChannelType::AI => daqmx_call!(DAQmxGetDevAIPhysicalChans(d_ptr, b_ptr, 5120))?,
-            ChannelType::AO => daqmx_call!(DAQmxGetDevAOPhysicalChans(d_ptr, b_ptr, 5120))?,
-            ChannelType::CI => daqmx_call!(DAQmxGetDevCIPhysicalChans(d_ptr, b_ptr, 5120))?,
-            ChannelType::CO => daqmx_call!(DAQmxGetDevCOPhysicalChans(d_ptr, b_ptr, 5120))?,
-        };
-
-        let buffer = buffer_to_string(buf);
+        let mut buffer = String::new();
+        daqmx_call!(
+            default_backend().as_ref(),
+            device_physical_channels(&c_device, channel_type, &mut buffer)
+        )?;
 
         let channels: Vec<String> = buffer
             .lines()
@@ -106,23 +91,23 @@ pub mod info {
 
     /// Query the system's device names and returns them as a vector of strings.
     ///
+    /// With the `runtime-loading` feature this returns [`crate::error::DaqmxError::DriverNotInstalled`]
+    /// if the NI-DAQmx library is not available.
+    ///
     /// # Example
     ///
     /// ```rust
     /// use daqmx::info::get_device_names;
-    /// use anyhow::Result;
+    /// use daqmx::error::Result;
     ///
     /// let res: Result<Vec<String>> = get_device_names();
     /// assert!(res.is_ok());
     ///
     /// ```
     pub fn get_device_names() -> Result<Vec<String>> {
-        let mut buf = vec![0i8; 2048];
-        let buf_ptr = buf.as_mut_ptr();
-
-        daqmx_call!(crate::daqmx::DAQmxGetSysDevNames(buf_ptr, 2048))?;
+        let mut buffer = String::new();
+        daqmx_call!(default_backend().as_ref(), device_names(&mut buffer))?;
 
-        let buffer = buffer_to_string(buf);
         let names: Vec<String> = buffer.split(", ").map(String::from).collect();
 
         Ok(names)
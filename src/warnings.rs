@@ -0,0 +1,241 @@
+//! Handling of NI-DAQmx warnings.
+//!
+//! DAQmx reports warnings, such as a coerced sample rate, as positive status codes while the call
+//! itself succeeds. What happens to them is decided by a [`WarningPolicy`]: the global policy set
+//! with [`set_global_warning_policy`], unless the task has its own from
+//! [`Task::set_warning_policy`](crate::tasks::Task::set_warning_policy).
+//!
+//! ```
+//! use daqmx::warnings::{set_global_warning_policy, WarningPolicy};
+//!
+//! // Keep warnings on each task for `Task::take_warnings`, but fail calls which report
+//! // that a finite task stopped before it was done.
+//! set_global_warning_policy(WarningPolicy::collect().promote(200010));
+//! ```
+use log::warn;
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::error::{DaqmxError, ErrorContext, ErrorKind, Result};
+
+/// A DAQmx warning raised by a successful call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// The positive DAQmx status code.
+    pub code: i32,
+    /// The description of the code from the driver.
+    pub message: String,
+    /// Where the warning was raised.
+    pub context: ErrorContext,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DAQmx Warning{}: {}", self.context, self.message)
+    }
+}
+
+/// Called with each warning by [`WarningAction::Callback`].
+pub type WarningCallback = Arc<dyn Fn(&Warning) + Send + Sync>;
+
+/// What to do with a warning which isn't promoted to an error.
+#[derive(Clone, Default)]
+pub enum WarningAction {
+    /// Report it through [`log::warn!`].
+    #[default]
+    Log,
+    /// Keep it on the task, to be read with [`Task::warnings`](crate::tasks::Task::warnings).
+    /// Warnings from calls which don't belong to a task are logged.
+    Collect,
+    /// Pass it to the callback.
+    Callback(WarningCallback),
+}
+
+impl fmt::Debug for WarningAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningAction::Log => write!(f, "Log"),
+            WarningAction::Collect => write!(f, "Collect"),
+            WarningAction::Callback(_) => write!(f, "Callback(..)"),
+        }
+    }
+}
+
+/// How DAQmx warnings are handled.
+///
+/// The default logs every warning, as earlier versions did.
+#[derive(Clone, Debug, Default)]
+pub struct WarningPolicy {
+    pub action: WarningAction,
+    /// Warning codes which fail the call with a [`DaqmxError`] carrying the positive code.
+    pub promoted: Vec<i32>,
+}
+
+impl WarningPolicy {
+    /// Log warnings.
+    pub fn log() -> Self {
+        Self::default()
+    }
+
+    /// Collect warnings on the task.
+    pub fn collect() -> Self {
+        Self {
+            action: WarningAction::Collect,
+            ..Self::default()
+        }
+    }
+
+    /// Pass warnings to `callback`.
+    pub fn callback(callback: impl Fn(&Warning) + Send + Sync + 'static) -> Self {
+        Self {
+            action: WarningAction::Callback(Arc::new(callback)),
+            ..Self::default()
+        }
+    }
+
+    /// Fail calls which raise the warning `code` instead.
+    pub fn promote(mut self, code: i32) -> Self {
+        self.promoted.push(code);
+        self
+    }
+
+    /// Whether the warning `code` is turned into an error.
+    pub fn is_promoted(&self, code: i32) -> bool {
+        self.promoted.contains(&code)
+    }
+}
+
+static GLOBAL_POLICY: RwLock<WarningPolicy> = RwLock::new(WarningPolicy {
+    action: WarningAction::Log,
+    promoted: Vec::new(),
+});
+
+/// The policy for tasks without their own and for calls outside of a task.
+pub fn global_warning_policy() -> WarningPolicy {
+    GLOBAL_POLICY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Replaces the global warning policy.
+pub fn set_global_warning_policy(policy: WarningPolicy) {
+    *GLOBAL_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+/// The warning policy of a task and the warnings it collected. Shared by clones of the task.
+#[derive(Debug, Default)]
+pub struct TaskWarnings {
+    policy: RwLock<Option<WarningPolicy>>,
+    collected: Mutex<Vec<Warning>>,
+}
+
+impl TaskWarnings {
+    /// The task policy, or the global policy if it has none.
+    pub(crate) fn policy(&self) -> WarningPolicy {
+        self.policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_else(global_warning_policy)
+    }
+
+    pub(crate) fn set_policy(&self, policy: Option<WarningPolicy>) {
+        *self.policy.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    pub(crate) fn collected(&self) -> Vec<Warning> {
+        self.collected
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn take(&self) -> Vec<Warning> {
+        std::mem::take(&mut *self.collected.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Applies the policy of `task`, or the global policy, to a warning.
+pub(crate) fn handle_warning(warning: Warning, task: Option<&TaskWarnings>) -> Result<()> {
+    let policy = task.map_or_else(global_warning_policy, TaskWarnings::policy);
+    if policy.is_promoted(warning.code) {
+        return Err(DaqmxError::DaqmxError {
+            code: warning.code,
+            kind: ErrorKind::from_code(warning.code),
+            message: warning.message,
+            context: warning.context,
+        });
+    }
+
+    match (policy.action, task) {
+        (WarningAction::Collect, Some(task)) => task
+            .collected
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(warning),
+        (WarningAction::Callback(callback), _) => callback(&warning),
+        (WarningAction::Log | WarningAction::Collect, _) => warn!("{warning}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    fn warning(code: i32) -> Warning {
+        Warning {
+            code,
+            message: "Warning".to_string(),
+            context: ErrorContext::task("task").with_operation("DAQmxStartTask"),
+        }
+    }
+
+    #[test]
+    fn test_collect_warnings() {
+        let task = TaskWarnings::default();
+        task.set_policy(Some(WarningPolicy::collect()));
+
+        handle_warning(warning(200010), Some(&task)).unwrap();
+        handle_warning(warning(200011), Some(&task)).unwrap();
+
+        assert_eq!(task.collected().len(), 2);
+        let taken = task.take();
+        assert_eq!(taken[0], warning(200010));
+        assert_eq!(taken[1].code, 200011);
+        assert!(task.collected().is_empty());
+    }
+
+    #[test]
+    fn test_promote_warning() {
+        let task = TaskWarnings::default();
+        task.set_policy(Some(WarningPolicy::collect().promote(200011)));
+
+        handle_warning(warning(200010), Some(&task)).unwrap();
+        let error = handle_warning(warning(200011), Some(&task)).unwrap_err();
+
+        assert_eq!(error.code(), Some(200011));
+        assert_eq!(
+            error.to_string(),
+            "DAQmx Generated Error in DAQmxStartTask (task \"task\"): Warning"
+        );
+        assert_eq!(task.take(), vec![warning(200010)]);
+    }
+
+    #[test]
+    fn test_warning_callback() {
+        let last = Arc::new(AtomicI32::new(0));
+        let task = TaskWarnings::default();
+        let seen = last.clone();
+        task.set_policy(Some(WarningPolicy::callback(move |warning| {
+            seen.store(warning.code, Ordering::SeqCst)
+        })));
+
+        handle_warning(warning(200010), Some(&task)).unwrap();
+
+        assert_eq!(last.load(Ordering::SeqCst), 200010);
+        assert!(task.collected().is_empty());
+    }
+}
@@ -0,0 +1,799 @@
+//! Generates sample buffers for outputs: periodic signals, chirps, steps and piecewise linear
+//! signals for analog outputs, and [`DigitalPattern`] for digital outputs.
+//!
+//! The analog buffers hold one channel of samples, so they can be written straight to a single
+//! channel task with [`OutputTask::write`](crate::tasks::output::OutputTask::write). [`combine`]
+//! lays out several channels for one write.
+//! [`Task::<AnalogOutput>::render_signals`](crate::tasks::Task::render_signals) renders signals
+//! which implement [`Render`] at the sample clock rate of the task.
+//!
+//! ```
+//! use daqmx::waveforms::{Periodic, Signal};
+//!
+//! # fn main() -> daqmx::error::Result<()> {
+//! // 1 kHz at 10 kS/s, peak-to-peak 2 V around 0.5 V.
+//! let sine = Periodic::sine(1000.0).amplitude(1.0).offset(0.5);
+//! let buffer = sine.periods(10_000.0, 4)?;
+//! assert_eq!(buffer.len(), 40);
+//! assert!((buffer[0] - 0.5).abs() < 1e-12);
+//! assert!((sine.value_at(0.25e-3) - 1.5).abs() < 1e-12);
+//! # Ok(())
+//! # }
+//! ```
+use std::cmp::Ordering;
+use std::f64::consts::TAU;
+
+use crate::error::{DaqmxError, Result};
+use crate::types::DataFillMode;
+
+/// A signal which can be sampled at any time from its start.
+pub trait Signal {
+    /// The value `t` seconds from the start.
+    fn value_at(&self, t: f64) -> f64;
+
+    /// `count` samples taken at `sample_rate` samples per second from the start.
+    fn samples(&self, sample_rate: f64, count: usize) -> Vec<f64> {
+        (0..count)
+            .map(|index| self.value_at(index as f64 / sample_rate))
+            .collect()
+    }
+
+    /// The samples taken at `sample_rate` over `seconds`, rounded to a whole number of samples.
+    fn samples_for(&self, sample_rate: f64, seconds: f64) -> Result<Vec<f64>> {
+        check_rate(sample_rate)?;
+        if seconds.is_nan() || seconds < 0.0 {
+            return Err(DaqmxError::Validation(format!(
+                "A signal can't last {seconds} s."
+            )));
+        }
+        Ok(self.samples(sample_rate, (seconds * sample_rate).round() as usize))
+    }
+}
+
+/// A signal with a length of its own, which renders as a whole buffer at a sample rate.
+pub trait Render {
+    /// The whole signal sampled at `sample_rate`.
+    fn render(&self, sample_rate: f64) -> Result<Vec<f64>>;
+}
+
+/// The shape of one period of a [`Periodic`] signal, between -1 and 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Sine,
+    /// High for `duty_cycle`, a fraction of the period, then low.
+    Square {
+        duty_cycle: f64,
+    },
+    Triangle,
+    /// Rises linearly from 0 to 1 over the first half of the period, drops to -1 and rises back
+    /// to 0 over the second half.
+    Sawtooth,
+}
+
+impl Shape {
+    /// The value at `position` through the period, from 0 to 1. Every shape starts at the
+    /// midpoint and rises, like a sine, apart from the square which starts high.
+    fn value(self, position: f64) -> f64 {
+        match self {
+            Shape::Sine => (TAU * position).sin(),
+            Shape::Square { duty_cycle } if position < duty_cycle => 1.0,
+            Shape::Square { .. } => -1.0,
+            Shape::Triangle if position < 0.25 => 4.0 * position,
+            Shape::Triangle if position < 0.75 => 2.0 - 4.0 * position,
+            Shape::Triangle => 4.0 * position - 4.0,
+            Shape::Sawtooth if position < 0.5 => 2.0 * position,
+            Shape::Sawtooth => 2.0 * position - 2.0,
+        }
+    }
+}
+
+/// A repeating signal of a [`Shape`], scaled by the amplitude and shifted by the offset and
+/// phase.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Periodic {
+    pub shape: Shape,
+    /// Periods per second.
+    pub frequency: f64,
+    /// The peak value above the offset.
+    pub amplitude: f64,
+    pub offset: f64,
+    /// The phase at the start, in degrees.
+    pub phase: f64,
+}
+
+impl Periodic {
+    /// A signal of `shape` at `frequency` Hz with an amplitude of 1, no offset and no phase.
+    pub fn new(shape: Shape, frequency: f64) -> Self {
+        Self {
+            shape,
+            frequency,
+            amplitude: 1.0,
+            offset: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    pub fn sine(frequency: f64) -> Self {
+        Self::new(Shape::Sine, frequency)
+    }
+
+    /// A square wave with a 50% duty cycle.
+    pub fn square(frequency: f64) -> Self {
+        Self::new(Shape::Square { duty_cycle: 0.5 }, frequency)
+    }
+
+    pub fn triangle(frequency: f64) -> Self {
+        Self::new(Shape::Triangle, frequency)
+    }
+
+    pub fn sawtooth(frequency: f64) -> Self {
+        Self::new(Shape::Sawtooth, frequency)
+    }
+
+    pub fn amplitude(mut self, amplitude: f64) -> Self {
+        self.amplitude = amplitude;
+        self
+    }
+
+    pub fn offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the phase at the start in degrees.
+    pub fn phase(mut self, degrees: f64) -> Self {
+        self.phase = degrees;
+        self
+    }
+
+    /// The frequency used by [`Periodic::periods`], coerced so `periods` periods take a whole
+    /// number of samples at `sample_rate`.
+    pub fn coerced_frequency(&self, sample_rate: f64, periods: u32) -> Result<f64> {
+        Ok(periods as f64 * sample_rate / self.samples_for_periods(sample_rate, periods)? as f64)
+    }
+
+    /// The signal for `periods` periods, rendering as [`Periodic::periods`].
+    pub fn repeat(self, periods: u32) -> Periods {
+        Periods {
+            signal: self,
+            periods,
+        }
+    }
+
+    /// Exactly `periods` periods sampled at `sample_rate`, which loop without a jump when the
+    /// buffer is regenerated.
+    ///
+    /// The frequency is coerced to fit the periods into a whole number of samples, see
+    /// [`Periodic::coerced_frequency`]. More periods bring it closer to the requested frequency.
+    pub fn periods(&self, sample_rate: f64, periods: u32) -> Result<Vec<f64>> {
+        let count = self.samples_for_periods(sample_rate, periods)?;
+        let coerced = Self {
+            frequency: periods as f64 * sample_rate / count as f64,
+            ..*self
+        };
+        Ok(coerced.samples(sample_rate, count))
+    }
+
+    fn samples_for_periods(&self, sample_rate: f64, periods: u32) -> Result<usize> {
+        check_rate(sample_rate)?;
+        if self.frequency.is_nan() || self.frequency <= 0.0 || periods == 0 {
+            return Err(DaqmxError::Validation(format!(
+                "Can't generate {periods} periods at {} Hz.",
+                self.frequency
+            )));
+        }
+        let count = (periods as f64 * sample_rate / self.frequency).round();
+        if count < 2.0 * periods as f64 {
+            return Err(DaqmxError::Validation(format!(
+                "{} Hz needs a sample rate of at least {} S/s.",
+                self.frequency,
+                2.0 * self.frequency
+            )));
+        }
+        Ok(count as usize)
+    }
+}
+
+impl Signal for Periodic {
+    fn value_at(&self, t: f64) -> f64 {
+        let position = (self.frequency * t + self.phase / 360.0).rem_euclid(1.0);
+        self.offset + self.amplitude * self.shape.value(position)
+    }
+}
+
+/// A whole number of periods of a [`Periodic`] signal, from [`Periodic::repeat`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Periods {
+    pub signal: Periodic,
+    pub periods: u32,
+}
+
+impl Render for Periods {
+    fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        self.signal.periods(sample_rate, self.periods)
+    }
+}
+
+/// A sine whose frequency sweeps linearly from `start_frequency` to `end_frequency` over
+/// `duration` seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chirp {
+    pub start_frequency: f64,
+    pub end_frequency: f64,
+    pub duration: f64,
+    pub amplitude: f64,
+    pub offset: f64,
+    /// The phase at the start, in degrees.
+    pub phase: f64,
+}
+
+impl Chirp {
+    /// A sweep with an amplitude of 1, no offset and no phase.
+    pub fn new(start_frequency: f64, end_frequency: f64, duration: f64) -> Self {
+        Self {
+            start_frequency,
+            end_frequency,
+            duration,
+            amplitude: 1.0,
+            offset: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    pub fn amplitude(mut self, amplitude: f64) -> Self {
+        self.amplitude = amplitude;
+        self
+    }
+
+    pub fn offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the phase at the start in degrees.
+    pub fn phase(mut self, degrees: f64) -> Self {
+        self.phase = degrees;
+        self
+    }
+
+    /// The whole sweep sampled at `sample_rate`. The duration must be positive.
+    pub fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        if !(self.duration > 0.0 && self.duration.is_finite()) {
+            return Err(DaqmxError::Validation(format!(
+                "A chirp can't sweep over {} s.",
+                self.duration
+            )));
+        }
+        self.samples_for(sample_rate, self.duration)
+    }
+}
+
+impl Render for Chirp {
+    fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        Chirp::render(self, sample_rate)
+    }
+}
+
+impl Signal for Chirp {
+    fn value_at(&self, t: f64) -> f64 {
+        let sweep = (self.end_frequency - self.start_frequency) / self.duration;
+        let cycles = self.start_frequency * t + sweep * t * t / 2.0 + self.phase / 360.0;
+        self.offset + self.amplitude * (TAU * cycles).sin()
+    }
+}
+
+/// A DC level which steps from `initial` to `final_value` `at` seconds from the start, lasting
+/// `duration` seconds in all.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step {
+    pub initial: f64,
+    pub final_value: f64,
+    pub at: f64,
+    pub duration: f64,
+}
+
+impl Step {
+    pub fn new(initial: f64, final_value: f64, at: f64, duration: f64) -> Self {
+        Self {
+            initial,
+            final_value,
+            at,
+            duration,
+        }
+    }
+
+    /// A constant `value` for `duration` seconds.
+    pub fn dc(value: f64, duration: f64) -> Self {
+        Self::new(value, value, 0.0, duration)
+    }
+
+    /// The whole step sampled at `sample_rate`.
+    pub fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        self.samples_for(sample_rate, self.duration)
+    }
+}
+
+impl Render for Step {
+    fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        Step::render(self, sample_rate)
+    }
+}
+
+impl Signal for Step {
+    fn value_at(&self, t: f64) -> f64 {
+        if t < self.at {
+            self.initial
+        } else {
+            self.final_value
+        }
+    }
+}
+
+/// An arbitrary signal joining `(time, value)` points with straight lines. It holds the first
+/// value before the first point and lasts until the last point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseLinear {
+    points: Vec<(f64, f64)>,
+}
+
+impl PiecewiseLinear {
+    /// Fails if there are no points or the times aren't in increasing order.
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self> {
+        if points.is_empty() {
+            return Err(DaqmxError::Validation(
+                "A piecewise linear signal needs at least one point.".to_string(),
+            ));
+        }
+        let ordered = points
+            .windows(2)
+            .all(|pair| pair[0].0.partial_cmp(&pair[1].0) == Some(Ordering::Less));
+        if !ordered {
+            return Err(DaqmxError::Validation(
+                "The points of a piecewise linear signal must be in increasing order of time."
+                    .to_string(),
+            ));
+        }
+        Ok(Self { points })
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// The time of the last point.
+    pub fn duration(&self) -> f64 {
+        self.points.last().map_or(0.0, |&(time, _)| time)
+    }
+
+    /// The signal from the start to the last point, sampled at `sample_rate`.
+    pub fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        self.samples_for(sample_rate, self.duration())
+    }
+}
+
+impl Render for PiecewiseLinear {
+    fn render(&self, sample_rate: f64) -> Result<Vec<f64>> {
+        PiecewiseLinear::render(self, sample_rate)
+    }
+}
+
+impl Signal for PiecewiseLinear {
+    fn value_at(&self, t: f64) -> f64 {
+        let next = self.points.partition_point(|&(time, _)| time <= t);
+        match (self.points.get(next.wrapping_sub(1)), self.points.get(next)) {
+            (Some(&(t0, v0)), Some(&(t1, v1))) => v0 + (v1 - v0) * (t - t0) / (t1 - t0),
+            (Some(&(_, value)), None) | (None, Some(&(_, value))) => value,
+            (None, None) => 0.0,
+        }
+    }
+}
+
+/// Lays out one buffer per channel as a single buffer for a multi-channel write with
+/// `fill_mode`. The buffers must all be the same length.
+pub fn combine(channels: &[Vec<f64>], fill_mode: DataFillMode) -> Result<Vec<f64>> {
+    let samples = channels.first().map_or(0, Vec::len);
+    if channels.iter().any(|channel| channel.len() != samples) {
+        return Err(DaqmxError::Validation(
+            "Every channel needs the same number of samples.".to_string(),
+        ));
+    }
+    Ok(match fill_mode {
+        DataFillMode::GroupByChannel => channels.concat(),
+        DataFillMode::GroupByScanNumber => (0..samples)
+            .flat_map(|index| channels.iter().map(move |channel| channel[index]))
+            .collect(),
+    })
+}
+
+/// Line states for a buffered digital output, described by edges and pulses on each line.
+///
+/// Lines are numbered from 0 across the channels of the task, in channel order, so in a task with
+/// one channel of a whole port the number is the line of the port. Each line starts at its
+/// [`DigitalPattern::initial`] state, low by default, and holds the state of its latest edge.
+/// Where edges on a line coincide, the last one added wins. Edges land on the nearest sample.
+///
+/// [`Task::<DigitalOutput>::render_pattern`](crate::tasks::Task::render_pattern) renders the
+/// pattern for the channels and sample clock of a task.
+///
+/// ```
+/// use daqmx::types::DataFillMode;
+/// use daqmx::waveforms::DigitalPattern;
+///
+/// # fn main() -> daqmx::error::Result<()> {
+/// // Line 2 high for 5 ms at t = 10 ms, in a 20 ms pattern.
+/// let pattern = DigitalPattern::new().high(2, 10e-3, 5e-3).duration(20e-3);
+/// // One channel of four lines at 1 kS/s.
+/// let buffer = pattern.render(1000.0, &[4], DataFillMode::GroupByChannel)?;
+/// assert_eq!(buffer.len(), 20 * 4);
+/// assert_eq!(buffer[9 * 4..11 * 4], [0, 0, 0, 0, 0, 0, 1, 0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DigitalPattern {
+    initial: Vec<(usize, bool)>,
+    edges: Vec<Edge>,
+    /// The line and duration of each pulse, checked when rendering.
+    pulses: Vec<(usize, f64)>,
+    duration: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Edge {
+    line: usize,
+    at: f64,
+    high: bool,
+}
+
+impl DigitalPattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The state of `line` before its first edge.
+    pub fn initial(mut self, line: usize, high: bool) -> Self {
+        self.initial.retain(|&(other, _)| other != line);
+        self.initial.push((line, high));
+        self
+    }
+
+    /// Sets `line` to `high` from `at` seconds until its next edge. Rendering fails if `at` is
+    /// negative or not finite.
+    pub fn edge(mut self, line: usize, at: f64, high: bool) -> Self {
+        self.edges.push(Edge { line, at, high });
+        self
+    }
+
+    /// Sets `line` high `at` seconds from the start for `duration` seconds, then low. Rendering
+    /// fails if `at` is negative or `duration` isn't positive, or either isn't finite.
+    pub fn high(self, line: usize, at: f64, duration: f64) -> Self {
+        self.pulse(line, at, duration, true)
+    }
+
+    /// Sets `line` low `at` seconds from the start for `duration` seconds, then high. Rendering
+    /// fails if `at` is negative or `duration` isn't positive, or either isn't finite.
+    pub fn low(self, line: usize, at: f64, duration: f64) -> Self {
+        self.pulse(line, at, duration, false)
+    }
+
+    fn pulse(mut self, line: usize, at: f64, duration: f64, high: bool) -> Self {
+        self.pulses.push((line, duration));
+        self.edge(line, at, high).edge(line, at + duration, !high)
+    }
+
+    /// The length of the pattern in seconds. Defaults to the time of the last edge.
+    pub fn duration(mut self, seconds: f64) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    /// The length of the pattern in seconds.
+    pub fn total_duration(&self) -> f64 {
+        self.duration
+            .unwrap_or_else(|| self.edges.iter().map(|edge| edge.at).fold(0.0, f64::max))
+    }
+
+    /// The number of lines the pattern uses, which is one more than the highest line.
+    pub fn num_lines(&self) -> usize {
+        let edges = self.edges.iter().map(|edge| edge.line);
+        let initial = self.initial.iter().map(|&(line, _)| line);
+        edges.chain(initial).max().map_or(0, |line| line + 1)
+    }
+
+    /// The states of `line` sampled at `sample_rate` over the pattern, as 0 or 1.
+    pub fn line_samples(&self, line: usize, sample_rate: f64) -> Result<Vec<u8>> {
+        check_rate(sample_rate)?;
+        self.check_times()?;
+        Ok(self.render_line(line, sample_rate, self.sample_count(sample_rate)))
+    }
+
+    /// The pattern sampled at `sample_rate` for a task whose channels have `lines_per_channel`
+    /// lines, laid out as `fill_mode` for a write of `u8` line states.
+    ///
+    /// Each channel takes as many values per sample as the channel with the most lines, the
+    /// task's `WriteDigitalLinesBytesPerChannel`, with the values past its own lines left at 0.
+    pub fn render(
+        &self,
+        sample_rate: f64,
+        lines_per_channel: &[u32],
+        fill_mode: DataFillMode,
+    ) -> Result<Vec<u8>> {
+        check_rate(sample_rate)?;
+        self.check_times()?;
+        let total_lines = lines_per_channel.iter().sum::<u32>() as usize;
+        if self.num_lines() > total_lines {
+            return Err(DaqmxError::Validation(format!(
+                "The pattern uses line {}, but the task only has {total_lines} lines.",
+                self.num_lines() - 1
+            )));
+        }
+        let samples = self.sample_count(sample_rate);
+        if samples == 0 {
+            return Err(DaqmxError::Validation(format!(
+                "A pattern of {} s is shorter than a sample.",
+                self.total_duration()
+            )));
+        }
+
+        let width = lines_per_channel.iter().copied().max().unwrap_or(0) as usize;
+        let lines: Vec<Vec<u8>> = (0..total_lines)
+            .map(|line| self.render_line(line, sample_rate, samples))
+            .collect();
+        // The lines of each channel, as indices into `lines`.
+        let mut channels = Vec::with_capacity(lines_per_channel.len());
+        let mut first = 0;
+        for &count in lines_per_channel {
+            channels.push(first..first + count as usize);
+            first += count as usize;
+        }
+
+        let mut buffer = vec![0; samples * channels.len() * width];
+        for (channel, channel_lines) in channels.into_iter().enumerate() {
+            for (offset, line) in channel_lines.enumerate() {
+                for (sample, &state) in lines[line].iter().enumerate() {
+                    let position = match fill_mode {
+                        DataFillMode::GroupByChannel => channel * samples + sample,
+                        DataFillMode::GroupByScanNumber => {
+                            sample * lines_per_channel.len() + channel
+                        }
+                    };
+                    buffer[position * width + offset] = state;
+                }
+            }
+        }
+        Ok(buffer)
+    }
+
+    fn sample_count(&self, sample_rate: f64) -> usize {
+        (self.total_duration() * sample_rate).round() as usize
+    }
+
+    fn render_line(&self, line: usize, sample_rate: f64, samples: usize) -> Vec<u8> {
+        let initial = self
+            .initial
+            .iter()
+            .find(|&&(other, _)| other == line)
+            .is_some_and(|&(_, high)| high);
+        let mut edges: Vec<(usize, bool)> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.line == line)
+            .map(|edge| ((edge.at * sample_rate).round() as usize, edge.high))
+            .collect();
+        // A stable sort, so coinciding edges keep the order they were added in.
+        edges.sort_by_key(|&(sample, _)| sample);
+
+        let mut states = vec![u8::from(initial); samples];
+        for (index, &(start, high)) in edges.iter().enumerate() {
+            let end = edges.get(index + 1).map_or(samples, |&(next, _)| next);
+            let range = start.min(samples)..end.min(samples);
+            states[range].fill(u8::from(high));
+        }
+        states
+    }
+
+    fn check_times(&self) -> Result<()> {
+        let duration = self.total_duration();
+        if !duration.is_finite() || duration < 0.0 {
+            return Err(DaqmxError::Validation(format!(
+                "A pattern can't last {duration} s."
+            )));
+        }
+        if let Some(&(line, duration)) = self
+            .pulses
+            .iter()
+            .find(|&&(_, duration)| !(duration > 0.0 && duration.is_finite()))
+        {
+            return Err(DaqmxError::Validation(format!(
+                "Invalid duration for a pulse on line {line}: {duration} s."
+            )));
+        }
+        match self
+            .edges
+            .iter()
+            .find(|edge| !edge.at.is_finite() || edge.at < 0.0)
+        {
+            Some(edge) => Err(DaqmxError::Validation(format!(
+                "Invalid time for an edge on line {}: {} s.",
+                edge.line, edge.at
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn check_rate(sample_rate: f64) -> Result<()> {
+    if sample_rate > 0.0 && sample_rate.is_finite() {
+        Ok(())
+    } else {
+        Err(DaqmxError::Validation(format!(
+            "Invalid sample rate: {sample_rate} S/s."
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_shapes() {
+        let rate = 8.0;
+        assert_close(
+            &Periodic::triangle(1.0).periods(rate, 1).unwrap(),
+            &[0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -0.5],
+        );
+        assert_close(
+            &Periodic::sawtooth(1.0).periods(rate, 1).unwrap(),
+            &[0.0, 0.25, 0.5, 0.75, -1.0, -0.75, -0.5, -0.25],
+        );
+        assert_close(
+            &Periodic::square(2.0)
+                .amplitude(2.0)
+                .periods(rate, 2)
+                .unwrap(),
+            &[2.0, 2.0, -2.0, -2.0, 2.0, 2.0, -2.0, -2.0],
+        );
+        assert_close(
+            &Periodic::sine(1.0).offset(1.0).phase(90.0).samples(4.0, 4),
+            &[2.0, 1.0, 0.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn test_periods_coerce_frequency() {
+        // 300 Hz at 1 kS/s is 3.33 samples per period, so 3 periods take 10 samples exactly.
+        let sine = Periodic::sine(300.0);
+        assert_eq!(sine.periods(1000.0, 3).unwrap().len(), 10);
+        assert_eq!(sine.coerced_frequency(1000.0, 3).unwrap(), 300.0);
+        assert_eq!(sine.periods(1000.0, 1).unwrap().len(), 3);
+        assert!((sine.coerced_frequency(1000.0, 1).unwrap() - 333.333).abs() < 1e-3);
+
+        assert!(Periodic::sine(800.0).periods(1000.0, 1).is_err());
+        assert!(Periodic::sine(0.0).periods(1000.0, 1).is_err());
+        assert!(sine.periods(0.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_chirp() {
+        let chirp = Chirp::new(1.0, 3.0, 1.0).amplitude(0.5);
+        assert_eq!(chirp.render(1000.0).unwrap().len(), 1000);
+        // The phase advances by the mean frequency, 2 cycles, over the sweep.
+        assert!(chirp.value_at(1.0).abs() < 1e-9);
+        assert!((chirp.value_at(0.5) - 0.5 * (TAU * 0.75).sin()).abs() < 1e-9);
+
+        assert!(Chirp::new(1.0, 3.0, 0.0).render(1000.0).is_err());
+        assert!(Chirp::new(1.0, 3.0, -1.0).render(1000.0).is_err());
+        assert!(Chirp::new(1.0, 3.0, f64::INFINITY).render(1000.0).is_err());
+    }
+
+    #[test]
+    fn test_step_and_dc() {
+        assert_close(
+            &Step::new(0.0, 5.0, 0.2, 0.5).render(10.0).unwrap(),
+            &[0.0, 0.0, 5.0, 5.0, 5.0],
+        );
+        assert_close(&Step::dc(1.5, 0.3).render(10.0).unwrap(), &[1.5; 3]);
+    }
+
+    #[test]
+    fn test_piecewise_linear() {
+        let signal = PiecewiseLinear::new(vec![(0.1, 0.0), (0.3, 1.0), (0.5, -1.0)]).unwrap();
+        assert_close(&signal.render(10.0).unwrap(), &[0.0, 0.0, 0.5, 1.0, 0.0]);
+        assert_eq!(signal.value_at(1.0), -1.0);
+
+        assert!(PiecewiseLinear::new(vec![]).is_err());
+        assert!(PiecewiseLinear::new(vec![(0.2, 0.0), (0.1, 1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_combine() {
+        let channels = [vec![1.0, 2.0], vec![10.0, 20.0]];
+        assert_eq!(
+            combine(&channels, DataFillMode::GroupByChannel).unwrap(),
+            vec![1.0, 2.0, 10.0, 20.0]
+        );
+        assert_eq!(
+            combine(&channels, DataFillMode::GroupByScanNumber).unwrap(),
+            vec![1.0, 10.0, 2.0, 20.0]
+        );
+        assert!(combine(&[vec![1.0], vec![]], DataFillMode::GroupByChannel).is_err());
+    }
+
+    #[test]
+    fn test_digital_pattern_lines() {
+        let pattern = DigitalPattern::new()
+            .high(0, 0.2, 0.3)
+            .initial(1, true)
+            .low(1, 0.1, 0.1)
+            .edge(2, 0.4, true)
+            // Replaces the rising edge added at the same time.
+            .edge(2, 0.4, false)
+            .duration(0.8);
+        assert_eq!(pattern.num_lines(), 3);
+        let line = |line| pattern.line_samples(line, 10.0).unwrap();
+        assert_eq!(line(0), [0, 0, 1, 1, 1, 0, 0, 0]);
+        assert_eq!(line(1), [1, 0, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(line(2), [0; 8]);
+        assert_eq!(line(3), [0; 8]);
+
+        // Without a duration, the pattern ends at the last edge.
+        assert_eq!(
+            DigitalPattern::new().high(0, 0.25, 0.5).total_duration(),
+            0.75
+        );
+    }
+
+    #[test]
+    fn test_digital_pattern_layout() {
+        // Two channels of two lines and one line, so two values per channel and sample.
+        let pattern = DigitalPattern::new()
+            .high(0, 0.0, 0.1)
+            .high(1, 0.1, 0.1)
+            .edge(2, 0.0, true)
+            .duration(0.2);
+        assert_eq!(
+            pattern
+                .render(10.0, &[2, 1], DataFillMode::GroupByChannel)
+                .unwrap(),
+            [1, 0, 0, 1, 1, 0, 1, 0]
+        );
+        assert_eq!(
+            pattern
+                .render(10.0, &[2, 1], DataFillMode::GroupByScanNumber)
+                .unwrap(),
+            [1, 0, 1, 0, 0, 1, 1, 0]
+        );
+
+        let fill_mode = DataFillMode::GroupByChannel;
+        assert!(pattern.render(10.0, &[1, 1], fill_mode).is_err());
+        assert!(pattern.render(0.0, &[2, 1], fill_mode).is_err());
+        assert!(DigitalPattern::new().render(10.0, &[1], fill_mode).is_err());
+        assert!(DigitalPattern::new()
+            .edge(0, -0.1, true)
+            .duration(1.0)
+            .render(10.0, &[1], fill_mode)
+            .is_err());
+        for (at, duration) in [
+            (-0.1, 0.2),
+            (0.5, -0.2),
+            (0.5, 0.0),
+            (f64::NAN, 0.2),
+            (0.5, f64::INFINITY),
+        ] {
+            let pulse = DigitalPattern::new().high(0, at, duration).duration(1.0);
+            assert!(
+                pulse.render(10.0, &[1], fill_mode).is_err(),
+                "{at} {duration}"
+            );
+            assert!(pulse.line_samples(0, 10.0).is_err(), "{at} {duration}");
+        }
+    }
+}
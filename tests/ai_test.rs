@@ -118,6 +118,41 @@ fn test_buffered_read_with_timeout() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_partial_read_with_timeout() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("partial")?;
+    let ch1 = VoltageChannel::builder("my_name", "PCIe-6363_test/ai0")?.build()?;
+    task.create_channel(ch1)?;
+    task.configure_sample_clock_timing(
+        None,
+        10.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        100,
+    )?;
+
+    let mut buffer = [0.0; 100];
+
+    task.start()?;
+    let outcome = task.read_partial(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        &mut buffer[..],
+    )?;
+
+    assert!(outcome.timed_out());
+    assert!(outcome.samples_per_channel > 0);
+    assert!(outcome.samples_per_channel < 100);
+    assert_ne!(buffer[0], 0.0);
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn test_buffered_read_2() -> Result<()> {
@@ -190,14 +225,15 @@ fn test_stop() -> Result<()> {
 
     assert_eq!(read_result.is_err(), true);
 
-    if let Some(daqmx::error::DaqmxError::DaqmxError(code, _)) = read_result
-        .unwrap_err()
-        .downcast_ref::<daqmx::error::DaqmxError>(
-    ) {
-        assert_eq!(*code, -200473);
-    } else {
-        panic!("Expected DaqmxError with code -200473");
-    }
+    let error = read_result.unwrap_err();
+    assert_eq!(error.code(), Some(-200473));
+    assert_eq!(
+        error.kind(),
+        Some(daqmx::error::ErrorKind::InvalidTaskState)
+    );
+    let context = error.context().expect("driver errors carry context");
+    assert_eq!(context.operation, Some("DAQmxReadAnalogF64"));
+    assert_eq!(context.task.as_deref(), Some("scalar"));
     Ok(())
 }
 
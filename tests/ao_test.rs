@@ -10,6 +10,7 @@ use daqmx::types::ClockEdge::Rising;
 use daqmx::types::DataFillMode;
 use daqmx::types::SampleMode;
 use daqmx::types::Timeout;
+use daqmx::waveforms::{Periodic, PiecewiseLinear, Render, Step};
 
 #[test]
 #[serial]
@@ -47,10 +48,9 @@ fn test_ao_buffered() -> Result<()> {
 
     task.start()?;
 
-    let mut buffer = [0.0; 100];
-    for i in 0..100 {
-        buffer[i] = i as f64 / 100.0;
-    }
+    // A ramp from 0 to 1 V over 100 samples.
+    let buffer = PiecewiseLinear::new(vec![(0.0, 0.0), (0.1, 1.0)])?.render(1000.0)?;
+    assert_eq!(buffer.len(), 100);
 
     let written = task.write(
         Timeout::Seconds(10.0),
@@ -143,3 +143,87 @@ fn test_ao_buffered_multi_channel() -> Result<()> {
     task.stop()?;
     Ok(())
 }
+
+#[test]
+#[serial]
+fn test_ao_regenerated_sine() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let ch1 = VoltageChannel::builder("my name", "PCIe-6363_test/ao0")?
+        .max(1.0)
+        .build()?;
+
+    let mut task: Task<AnalogOutput> = Task::new("AnalogOutTest")?;
+    task.create_channel(ch1)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        Rising,
+        SampleMode::ContinuousSamples,
+        1000_u64,
+    )?;
+
+    // 30 Hz doesn't divide 1 kS/s, so ask for enough periods to loop cleanly.
+    let sine = Periodic::sine(30.0).amplitude(0.5).offset(0.25);
+    let buffer = sine.periods(task.sample_clock_rate()?, 3)?;
+    assert_eq!(buffer.len(), 100);
+
+    let written = task.write(
+        Timeout::Seconds(10.0),
+        DataFillMode::GroupByChannel,
+        None,
+        &buffer,
+    )?;
+    assert_eq!(written, 100);
+
+    std::thread::sleep(std::time::Duration::from_millis(250));
+    assert!(!task.is_done()?);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_ao_render_signals_at_task_rate() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogOutput> = Task::new("AnalogOutTest")?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.create_channel(
+        VoltageChannel::builder("ao1", "PCIe-6363_test/ao1")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::FiniteSamples, 100)?;
+
+    // Both signals last 100 ms, so 100 samples each at the task's 1 kS/s.
+    let sine = Periodic::sine(50.0).amplitude(0.5).repeat(5);
+    let step = Step::new(0.0, 1.0, 0.05, 0.1);
+    let buffer = task.render_signals(&[&sine, &step], DataFillMode::GroupByScanNumber)?;
+    assert_eq!(buffer.len(), 200);
+    assert_eq!(buffer[1], 0.0);
+    assert_eq!(buffer[199], 1.0);
+
+    let written = task.write(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByScanNumber,
+        None,
+        &buffer,
+    )?;
+    assert_eq!(written, 100);
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    task.stop()?;
+
+    // One signal per channel.
+    let signals: [&dyn Render; 1] = [&step];
+    assert!(task
+        .render_signals(&signals, DataFillMode::GroupByChannel)
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,316 @@
+#![cfg(feature = "async")]
+mod common;
+use anyhow::Result;
+use futures_core::Stream;
+use serial_test::serial;
+use std::future::{poll_fn, Future};
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use daqmx::channels::*;
+use daqmx::tasks::output::{OutputTask, WriteConfig};
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+/// Wakes a thread blocked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Polls a future to completion on the current thread, as a minimal executor.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+fn ai_task(name: &str, rate: f64) -> Result<Task<AnalogInput>> {
+    let mut task: Task<AnalogInput> = Task::new(name)?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        rate,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    Ok(task)
+}
+
+#[test]
+#[serial]
+fn test_read_async() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("async_read", 1000.0)?;
+    task.start()?;
+
+    let (buffer, samples) = block_on(task.read_async(
+        Timeout::Seconds(2.0),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        vec![0.0; 100],
+    ))?;
+
+    assert_eq!(samples, 100);
+    assert_eq!(buffer.len(), 100);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_read_async_timeout() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("async_timeout", 10.0)?;
+    task.start()?;
+
+    let error = block_on(task.read_async(
+        Timeout::Seconds(0.1),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        vec![0.0; 100],
+    ))
+    .unwrap_err();
+
+    assert!(error.is_timeout());
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_dropped_read_releases_task() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    // 1000 samples at 10 Hz won't arrive before the future is dropped.
+    let mut task = ai_task("async_cancel", 10.0)?;
+    task.start()?;
+    let mut read = Box::pin(task.read_async(
+        Timeout::WaitForever,
+        DataFillMode::GroupByChannel,
+        Some(1000),
+        vec![0.0; 1000],
+    ));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    drop(read);
+    drop(task);
+    thread::sleep(Duration::from_millis(100));
+
+    // The name is free again once the worker released the task.
+    ai_task("async_cancel", 10.0)?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_dropped_read_of_all_samples_releases_task() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    // Reading every sample of a finite acquisition waits for all 1000 of them at 10 Hz.
+    let mut task = ai_task("async_cancel_all", 10.0)?;
+    task.configure_sample_clock_timing(
+        None,
+        10.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        1000,
+    )?;
+    task.start()?;
+    let mut read = Box::pin(task.read_async(
+        Timeout::WaitForever,
+        DataFillMode::GroupByChannel,
+        None,
+        vec![0.0; 1000],
+    ));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    drop(read);
+    drop(task);
+    thread::sleep(Duration::from_millis(100));
+
+    ai_task("async_cancel_all", 10.0)?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_read_async_available_samples() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("async_available", 1000.0)?;
+    task.start()?;
+    thread::sleep(Duration::from_millis(50));
+
+    // A continuous task reads what is available without waiting, however long the timeout.
+    let (_, samples) = block_on(task.read_async(
+        Timeout::Seconds(f64::INFINITY),
+        DataFillMode::GroupByChannel,
+        None,
+        vec![0.0; 1000],
+    ))?;
+
+    assert!(samples > 0);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_dropped_stream_releases_task() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("async_stream_cancel", 10.0)?;
+    task.start()?;
+    let mut stream = task.stream(1000)?.into_async();
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+
+    drop(stream);
+    drop(task);
+    thread::sleep(Duration::from_millis(100));
+
+    ai_task("async_stream_cancel", 10.0)?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_write_async() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogOutput> = Task::new("async_write")?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        100,
+    )?;
+
+    let buffer = (0..100).map(|i| i as f64 / 100.0).collect();
+    let written = block_on(task.write_async(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        buffer,
+    ))?;
+
+    assert_eq!(written, 100);
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_dropped_write_releases_task() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogOutput> = Task::new("async_write_cancel")?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        10.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        100,
+    )?;
+    task.configure_write(&WriteConfig::streaming().buffer_size(100))?;
+    // Fills the buffer and starts the task.
+    task.write(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        &[0.0; 100],
+    )?;
+
+    // More than the buffer holds can never be written while the task runs.
+    let error = block_on(task.write_async(
+        Timeout::WaitForever,
+        DataFillMode::GroupByChannel,
+        Some(200),
+        vec![0.0; 200],
+    ))
+    .unwrap_err();
+    assert!(matches!(error, daqmx::error::DaqmxError::Validation(_)));
+
+    // The full buffer won't have room for 100 more samples at 10 Hz before the future is dropped.
+    let mut write = Box::pin(task.write_async(
+        Timeout::WaitForever,
+        DataFillMode::GroupByChannel,
+        None,
+        vec![0.5; 100],
+    ));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(write.as_mut().poll(&mut cx).is_pending());
+
+    drop(write);
+    drop(task);
+    thread::sleep(Duration::from_millis(100));
+
+    let _task: Task<AnalogOutput> = Task::new("async_write_cancel")?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_async_stream() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("async_stream", 1000.0)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        300,
+    )?;
+    task.start()?;
+
+    let mut stream = task.stream(100)?.into_async();
+    let mut chunks = 0;
+    while let Some(chunk) = block_on(poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))) {
+        assert_eq!(chunk?.len(), 100);
+        chunks += 1;
+    }
+
+    assert_eq!(chunks, 3);
+    task.stop()?;
+    Ok(())
+}
@@ -1,14 +1,46 @@
 use anyhow::Result;
+use daqmx::backend::{self, SimulatedBackend, SimulatedDevice};
+use daqmx::error::DaqmxError;
 use daqmx::info;
+use std::io::Write;
+use std::sync::{Arc, Once};
+
+const TEST_DEVICE: &str = "PCIe-6363_test";
+
+/// Runs the tests on a simulated `PCIe-6363_test` unless `DAQMX_BACKEND` is set, for example
+/// to `ffi` to run them on the real device.
+fn init_backend() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        if std::env::var_os("DAQMX_BACKEND").is_none() {
+            backend::set_default_backend(Arc::new(
+                SimulatedBackend::new().with_device(SimulatedDevice::x_series(TEST_DEVICE)),
+            ));
+        }
+    });
+}
+
+/// Reports a skipped test on stderr directly, so the test harness doesn't capture it.
+fn skip(reason: &str) {
+    let _ = writeln!(std::io::stderr(), "SKIPPED: {reason}");
+}
 
 pub fn test_device_or_skip() -> Result<Option<String>> {
-    let dev = "PCIe-6363_test".to_string();
-    let devices = info::get_device_names()?;
+    init_backend();
+    let dev = TEST_DEVICE.to_string();
+    let devices = match info::get_device_names() {
+        Ok(devices) => devices,
+        Err(DaqmxError::DriverNotInstalled(reason)) => {
+            skip(&format!("NI-DAQmx driver not installed ({reason})"));
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
 
     if devices.iter().any(|d| d == &dev) {
         Ok(Some(dev))
     } else {
-        eprintln!("Skipping test: required device '{}' not present", dev);
+        skip(&format!("required device '{dev}' not present"));
         Ok(None)
     }
 }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use daqmx::channels::{CounterOutputPulseTimeChannel, DigitalChannel, VoltageChannel};
-use daqmx::error::DaqmxError;
+use daqmx::error::ErrorKind;
 use daqmx::tasks::output::{OutputTask, WriteOptions};
 use daqmx::tasks::{
     AnalogInput, CounterOutput, CounterOutputTask, DigitalInput, DigitalOutput, Task,
@@ -10,13 +10,6 @@ use serial_test::serial;
 
 mod common;
 
-fn is_daqmx_error_code(err: &anyhow::Error, code: i32) -> bool {
-    matches!(
-        err.downcast_ref::<DaqmxError>(),
-        Some(DaqmxError::DaqmxError(c, _)) if *c == code
-    )
-}
-
 #[test]
 #[serial]
 fn pfi_master_trigger_starts_ai_di_do() -> Result<()> {
@@ -286,20 +279,20 @@ fn dual_do_shared_resource_smoke() -> Result<()> {
         WriteOptions::default().auto_start(false),
     ) {
         // Some devices reserve a single DO timing/resource domain across multiple DO tasks.
-        if is_daqmx_error_code(&err, -50103) {
+        if err.kind() == Some(ErrorKind::ResourceReserved) {
             eprintln!("Skipping test: device reserves shared DO resources ({err})");
             return Ok(());
         }
-        return Err(err);
+        return Err(err.into());
     }
 
     do_a.start()?;
     if let Err(err) = do_b.start() {
-        if is_daqmx_error_code(&err, -50103) {
+        if err.kind() == Some(ErrorKind::ResourceReserved) {
             eprintln!("Skipping test: device reserves shared DO resources ({err})");
             return Ok(());
         }
-        return Err(err);
+        return Err(err.into());
     }
 
     do_a.wait_until_done(Timeout::Seconds(5.0))?;
@@ -109,3 +109,68 @@ fn test_digital_input_builder_bool() -> Result<()> {
     task.stop()?;
     Ok(())
 }
+
+#[test]
+#[serial]
+fn test_partial_read_bool_with_timeout() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let ch1 = DigitalChannel::builder("my_digital_input", "PCIe-6363_test/port0/line0")?.build()?;
+
+    let mut task: Task<DigitalInput> = Task::new("partial_bool")?;
+    task.create_channel(ch1)?;
+    task.configure_sample_clock_timing(None, 10.0, Rising, SampleMode::FiniteSamples, 100)?;
+
+    // The line alternates low and high, so the samples read replace the initial values.
+    let mut buffer = [true; 100];
+
+    task.start()?;
+    let outcome = task.read_partial(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        &mut buffer[..],
+    )?;
+
+    assert!(outcome.timed_out());
+    assert!(outcome.samples_per_channel > 0);
+    assert!(outcome.samples_per_channel < 100);
+    assert!(!buffer[0]);
+    assert!(buffer[1]);
+    // The samples past those read are left alone.
+    assert!(buffer[outcome.samples_per_channel as usize..]
+        .iter()
+        .all(|&line| line));
+
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_failed_read_bool_reports_read_error() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let ch1 = DigitalChannel::builder("my_digital_input", "PCIe-6363_test/port0/line0")?.build()?;
+
+    let mut task: Task<DigitalInput> = Task::new("failed_bool")?;
+    task.create_channel(ch1)?;
+    task.configure_sample_clock_timing(None, 100.0, Rising, SampleMode::FiniteSamples, 100)?;
+    task.set_read_auto_start(false)?;
+
+    let mut buffer = [true; 10];
+    let error = task
+        .read(
+            Timeout::Seconds(1.0),
+            DataFillMode::GroupByChannel,
+            Some(10),
+            &mut buffer[..],
+        )
+        .unwrap_err();
+
+    assert!(error.to_string().contains("Auto Start"), "{error}");
+    assert!(buffer.iter().all(|&line| line));
+    Ok(())
+}
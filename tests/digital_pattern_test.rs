@@ -0,0 +1,58 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+
+use daqmx::channels::*;
+use daqmx::tasks::output::OutputTask;
+use daqmx::tasks::*;
+use daqmx::types::*;
+use daqmx::waveforms::DigitalPattern;
+
+#[test]
+#[serial]
+fn test_render_pattern_for_task() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<DigitalOutput> = Task::new("digital_pattern")?;
+    task.create_channel(DigitalChannel::builder("port", "PCIe-6363_test/port0/line0:3")?.build()?)?;
+    task.create_channel(DigitalChannel::builder("line", "PCIe-6363_test/port0/line4")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        20,
+    )?;
+
+    let port: DigitalChannelBase<DigitalOutput> = task.get_channel("port")?;
+    assert_eq!(port.num_lines()?, 4);
+
+    // Line 2 of the port high for 5 ms at t = 10 ms, and the single line high throughout.
+    let pattern = DigitalPattern::new()
+        .high(2, 10e-3, 5e-3)
+        .edge(4, 0.0, true)
+        .duration(20e-3);
+    let buffer = task.render_pattern(&pattern, DataFillMode::GroupByScanNumber)?;
+    // Both channels take four values per sample.
+    assert_eq!(buffer.len(), 20 * 2 * 4);
+    assert_eq!(buffer[9 * 8..10 * 8], [0, 0, 0, 0, 1, 0, 0, 0]);
+    assert_eq!(buffer[10 * 8..11 * 8], [0, 0, 1, 0, 1, 0, 0, 0]);
+    assert_eq!(buffer[15 * 8..16 * 8], [0, 0, 0, 0, 1, 0, 0, 0]);
+
+    let written = task.write(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByScanNumber,
+        None,
+        &buffer,
+    )?;
+    assert_eq!(written, 20);
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    task.stop()?;
+
+    let too_wide = DigitalPattern::new().high(5, 0.0, 1e-3);
+    assert!(task
+        .render_pattern(&too_wide, DataFillMode::GroupByChannel)
+        .is_err());
+    Ok(())
+}
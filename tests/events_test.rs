@@ -0,0 +1,265 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use daqmx::channels::*;
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+/// A callback which signals once it runs, then blocks until the returned sender is dropped, so
+/// the task can be dropped while the callback is in flight.
+fn in_flight_callback() -> (
+    impl FnMut() + Send + 'static,
+    mpsc::Receiver<()>,
+    mpsc::Sender<()>,
+) {
+    let (entered_sender, entered) = mpsc::channel();
+    let (release, released) = mpsc::channel::<()>();
+    let callback = move || {
+        if entered_sender.send(()).is_ok() {
+            let _ = released.recv_timeout(Duration::from_secs(2));
+        }
+    };
+    (callback, entered, release)
+}
+
+/// Waits for the name of a dropped task to be free again, which it is once the task is cleared.
+fn wait_for_clear(name: &str) -> Result<()> {
+    for _ in 0..100 {
+        if Task::<AnalogInput>::new(name).is_ok() {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    anyhow::bail!("The task {name} was never cleared.")
+}
+
+fn ai_task(name: &str) -> Result<Task<AnalogInput>> {
+    let mut task: Task<AnalogInput> = Task::new(name)?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    Ok(task)
+}
+
+#[test]
+#[serial]
+fn test_every_n_samples_reads() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("every_n")?;
+    let (sender, receiver) = mpsc::channel();
+    task.on_every_n_samples(
+        100,
+        EveryNSamplesEvent::AcquiredIntoBuffer,
+        move |task, samples| {
+            let mut buffer = vec![0.0; samples as usize];
+            let read = task.read(
+                Timeout::Seconds(0.0),
+                DataFillMode::GroupByChannel,
+                Some(samples),
+                &mut buffer,
+            );
+            let _ = sender.send(read.map_err(|e| e.to_string()));
+        },
+    )?;
+
+    task.start()?;
+    for _ in 0..3 {
+        let read = receiver.recv_timeout(Duration::from_secs(2))?;
+        assert_eq!(read, Ok(100));
+    }
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_every_n_samples_panic_is_caught() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("every_n_panic")?;
+    let (sender, receiver) = mpsc::channel();
+    task.on_every_n_samples(
+        50,
+        EveryNSamplesEvent::AcquiredIntoBuffer,
+        move |_, samples| {
+            let _ = sender.send(samples);
+            panic!("callback failed");
+        },
+    )?;
+
+    task.start()?;
+    // The callback keeps being called after a panic.
+    for _ in 0..2 {
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(2))?, 50);
+    }
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_every_n_samples_unregistered_on_drop() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("every_n_drop")?;
+    let (sender, receiver) = mpsc::channel();
+    task.on_every_n_samples(
+        10,
+        EveryNSamplesEvent::AcquiredIntoBuffer,
+        move |_, samples| {
+            let _ = sender.send(samples);
+        },
+    )?;
+
+    task.start()?;
+    receiver.recv_timeout(Duration::from_secs(2))?;
+    drop(task);
+    // Dropping the task drops the callback and with it the sender.
+    while receiver.recv_timeout(Duration::from_secs(2)).is_ok() {}
+    assert!(matches!(
+        receiver.try_recv(),
+        Err(mpsc::TryRecvError::Disconnected)
+    ));
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_done_event_after_pulse_train() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<CounterOutput> = Task::new("done_pulses")?;
+    task.create_channel(
+        CounterOutputPulseTimeChannel::builder("co0", "PCIe-6363_test/ctr0")?
+            .idle_state(IdleState::Low)
+            .low_time(0.005)
+            .high_time(0.005)
+            .build()?,
+    )?;
+    task.configure_implicit_timing(SampleMode::FiniteSamples, 5)?;
+
+    let (pulse_sender, pulses) = mpsc::channel();
+    task.on_signal(SignalEvent::CounterOutput, move |_| {
+        let _ = pulse_sender.send(());
+    })?;
+    let (done_sender, done) = mpsc::channel();
+    task.on_done(move |task, status| {
+        let _ = done_sender.send((status, task.is_done()));
+    })?;
+
+    task.start()?;
+    let (status, is_done) = done.recv_timeout(Duration::from_secs(2))?;
+    assert_eq!(status, Ok(()));
+    assert!(is_done?);
+    let count = (0..5)
+        .take_while(|_| pulses.recv_timeout(Duration::from_secs(1)).is_ok())
+        .count();
+    assert_eq!(count, 5);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_sample_clock_signal() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("sample_clock_signal")?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        20,
+    )?;
+    let (sender, ticks) = mpsc::channel();
+    task.on_signal(SignalEvent::SampleClock, move |_| {
+        let _ = sender.send(());
+    })?;
+
+    task.start()?;
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    // The last event may still be on its way after the task is done.
+    let count = (0..20)
+        .take_while(|_| ticks.recv_timeout(Duration::from_secs(1)).is_ok())
+        .count();
+    assert_eq!(count, 20);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_drop_during_every_n_samples_callback() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("every_n_in_flight")?;
+    let (mut callback, entered, release) = in_flight_callback();
+    task.on_every_n_samples(10, EveryNSamplesEvent::AcquiredIntoBuffer, move |_, _| {
+        callback()
+    })?;
+
+    task.start()?;
+    entered.recv_timeout(Duration::from_secs(2))?;
+    // The callback holds the last handle when it returns.
+    drop(task);
+    drop(release);
+    wait_for_clear("every_n_in_flight")
+}
+
+#[test]
+#[serial]
+fn test_drop_during_done_callback() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<CounterOutput> = Task::new("done_in_flight")?;
+    task.create_channel(
+        CounterOutputPulseTimeChannel::builder("co0", "PCIe-6363_test/ctr0")?
+            .low_time(0.005)
+            .high_time(0.005)
+            .build()?,
+    )?;
+    task.configure_implicit_timing(SampleMode::FiniteSamples, 2)?;
+    let (mut callback, entered, release) = in_flight_callback();
+    task.on_done(move |_, _| callback())?;
+
+    task.start()?;
+    entered.recv_timeout(Duration::from_secs(2))?;
+    drop(task);
+    drop(release);
+    wait_for_clear("done_in_flight")
+}
+
+#[test]
+#[serial]
+fn test_drop_during_signal_callback() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("signal_in_flight")?;
+    let (mut callback, entered, release) = in_flight_callback();
+    task.on_signal(SignalEvent::SampleClock, move |_| callback())?;
+
+    task.start()?;
+    entered.recv_timeout(Duration::from_secs(2))?;
+    drop(task);
+    drop(release);
+    wait_for_clear("signal_in_flight")
+}
@@ -0,0 +1,91 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+
+use daqmx::channels::*;
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+#[test]
+#[serial]
+fn test_channel_names() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("frame_names")?;
+    task.create_channel(VoltageChannel::builder("ai", "PCIe-6363_test/ai0:1")?.build()?)?;
+    task.create_channel(VoltageChannel::builder("ai2", "PCIe-6363_test/ai2")?.build()?)?;
+
+    assert_eq!(task.channel_names()?, vec!["ai0", "ai1", "ai2"]);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_read_frame() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("frame_read")?;
+    task.create_channel(
+        VoltageChannel::builder("first", "PCIe-6363_test/ai0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.create_channel(
+        VoltageChannel::builder("second", "PCIe-6363_test/ai1")?
+            .max(10.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        50,
+    )?;
+
+    let frame = task.read_frame(Timeout::Seconds(1.0), DataFillMode::GroupByScanNumber, 50)?;
+
+    assert_eq!(frame.channel_names(), &["first", "second"]);
+    assert_eq!(frame.samples_per_channel(), 50);
+    assert_eq!(frame.layout(), DataFillMode::GroupByScanNumber);
+    // The ranges of the channels tell them apart after reordering.
+    assert!(frame
+        .channel_by_name("second")
+        .unwrap()
+        .any(|value| value.abs() > 1.0));
+    let grouped = frame.clone().into_grouped_by_channel();
+    assert!(grouped
+        .channel_slice(0)
+        .unwrap()
+        .iter()
+        .all(|value| value.abs() <= 1.0));
+    assert!(frame.rows().all(|row| row.count() == 2));
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_read_frame_digital_lines() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<DigitalInput> = Task::new("frame_di")?;
+    task.create_channel(DigitalChannel::builder("di", "PCIe-6363_test/port0/line0:1")?.build()?)?;
+
+    // A single channel of two lines doesn't fit a frame.
+    let error = task
+        .read_frame::<bool>(Timeout::Seconds(1.0), DataFillMode::GroupByChannel, 1)
+        .unwrap_err();
+    assert!(matches!(error, daqmx::error::DaqmxError::Validation(_)));
+
+    let mut task: Task<DigitalInput> = Task::new("frame_di_lines")?;
+    task.create_channel(DigitalChannel::builder("line0", "PCIe-6363_test/port0/line0")?.build()?)?;
+    task.create_channel(DigitalChannel::builder("line1", "PCIe-6363_test/port0/line1")?.build()?)?;
+    let frame = task.read_frame::<bool>(Timeout::Seconds(1.0), DataFillMode::GroupByChannel, 1)?;
+    assert_eq!(frame.channel_names(), &["line0", "line1"]);
+    assert_eq!(frame.data().len(), 2);
+    Ok(())
+}
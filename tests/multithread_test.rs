@@ -130,3 +130,116 @@ fn test_control_from_thread() -> Result<()> {
 
     Ok(())
 }
+
+#[serial]
+#[test]
+/// Reads from two threads while this thread runs the task through the run guard and stops it.
+fn test_run_guard_control_from_thread() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("scalar")?;
+    let ch1 = VoltageChannel::builder("my name", "PCIe-6363_test/ai0")?.build()?;
+    task.create_channel(ch1)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+
+    let mut buffer = [0.0; 1000];
+
+    task.set_read_auto_start(false)?;
+    let mut thread_task_1 = task.clone();
+    let mut thread_task_2 = task.clone();
+
+    let mut running = task.run()?;
+    assert!(!running.is_done()?);
+
+    let join_handle_1 = std::thread::spawn(move || {
+        println!("Starting read 1");
+        for _ in 0..10 {
+            let result = thread_task_1.read(
+                Timeout::Seconds(1.0),
+                DataFillMode::GroupByChannel,
+                Some(100),
+                &mut buffer[..],
+            );
+
+            if result.is_err() {
+                return;
+            } else {
+                println!("First: {:?}", result);
+            }
+        }
+
+        //If we complete the iterations we weren't stopped. panic.
+        panic!("Expected thread to be stopped by the task being stopped.");
+    });
+
+    let join_handle_2 = std::thread::spawn(move || {
+        println!("Starting read 2");
+        for _ in 0..10 {
+            let result = thread_task_2.read(
+                Timeout::Seconds(1.0),
+                DataFillMode::GroupByChannel,
+                Some(100),
+                &mut buffer[..],
+            );
+
+            if result.is_err() {
+                return;
+            } else {
+                println!("Second: {:?}", result);
+            }
+        }
+
+        //If we complete the iterations we weren't stopped. panic.
+        panic!("Expected thread to be stopped by the task being stopped.");
+    });
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    println!("Sending stop");
+    running.finish()?;
+
+    join_handle_1
+        .join()
+        .map_err(|_| anyhow::anyhow!("Thread 1 panicked"))?;
+    join_handle_2
+        .join()
+        .map_err(|_| anyhow::anyhow!("Thread 2 panicked"))?;
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+/// A panic while the task runs must still stop it.
+fn test_run_guard_stops_on_panic() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("guard")?;
+    let ch1 = VoltageChannel::builder("my name", "PCIe-6363_test/ai0")?.build()?;
+    task.create_channel(ch1)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut running = task.run().unwrap();
+        assert!(!running.is_done().unwrap());
+        panic!("Failure while the task is running");
+    }));
+    assert!(result.is_err());
+
+    // Stopped by the guard, so the task can be started again.
+    task.run()?.finish()?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+use daqmx::channels::*;
+use daqmx::error::{DaqmxError, ErrorKind};
+use daqmx::tasks::output::WriteConfig;
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+fn ao_task(name: &str, buffer_size: u32) -> Result<Task<AnalogOutput>> {
+    let mut task: Task<AnalogOutput> = Task::new(name)?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0:1")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    task.configure_write(&WriteConfig::default().buffer_size(buffer_size))?;
+    Ok(task)
+}
+
+#[test]
+#[serial]
+fn test_stream_iterator_to_end() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let task = ao_task("output_stream_iterator", 300)?;
+    // Two channels of 100 samples per chunk.
+    let chunks = (0..8).map(|chunk| vec![chunk as f64 / 10.0; 200]);
+
+    let start = Instant::now();
+    let mut streamer = task.streamer(chunks)?;
+    assert_eq!(streamer.run()?, 800);
+    assert!(streamer.is_finished());
+    // The task generated every sample before it was stopped.
+    assert!(start.elapsed() >= Duration::from_millis(750));
+    assert_eq!(
+        task.write_config()?.regeneration,
+        Some(RegenerationMode::DoNotAllowRegeneration)
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_stream_reports_underflow() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ao_task("output_stream_underflow", 200)?;
+    let mut streamer = task
+        .streamer(std::iter::from_fn(|| Some(vec![0.25; 200])))?
+        .fill_mode(DataFillMode::GroupByScanNumber);
+
+    assert_eq!(streamer.prime()?, 200);
+    task.start()?;
+    assert_eq!(streamer.top_up()?, 0);
+    // Nothing tops the buffer up, so it runs dry.
+    std::thread::sleep(Duration::from_millis(300));
+
+    match streamer.top_up() {
+        Err(DaqmxError::OutputUnderflow {
+            samples_written,
+            source,
+        }) => {
+            assert_eq!(samples_written, 200);
+            assert_eq!(source.kind(), Some(ErrorKind::OutputUnderflow));
+        }
+        other => panic!("expected an underflow, got {other:?}"),
+    }
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_prime_stops_when_buffer_is_full() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let task = ao_task("output_stream_prime_full", 300)?;
+    let mut streamer = task.streamer(std::iter::repeat_with(|| vec![0.25; 400]))?;
+
+    // The second chunk of 200 samples doesn't fit in the 100 samples left.
+    let start = Instant::now();
+    assert_eq!(streamer.prime()?, 200);
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(streamer.prime()?, 0);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_chunk_larger_than_buffer() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let task = ao_task("output_stream_large_chunk", 100)?;
+    let mut streamer = task.streamer(std::iter::repeat_with(|| vec![0.25; 400]))?;
+
+    let start = Instant::now();
+    let error = streamer.prime().unwrap_err();
+    assert!(matches!(error, DaqmxError::Validation(_)));
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(streamer.samples_written(), 0);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_stream_digital_lines() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<DigitalOutput> = Task::new("output_stream_do")?;
+    task.create_channel(
+        DigitalChannel::builder(
+            "do0",
+            "PCIe-6363_test/port0/line0, PCIe-6363_test/port0/line1",
+        )?
+        .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+
+    // One channel of two lines, 50 samples per chunk.
+    let mut chunks = 0;
+    let producer = std::iter::from_fn(|| {
+        chunks += 1;
+        (chunks <= 4).then(|| vec![chunks % 2 == 0; 100])
+    });
+    assert_eq!(task.streamer(producer)?.run()?, 200);
+
+    let error = task
+        .streamer(std::iter::once(vec![true; 3]))?
+        .run()
+        .unwrap_err();
+    assert!(matches!(error, DaqmxError::Validation(_)));
+    Ok(())
+}
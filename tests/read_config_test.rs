@@ -0,0 +1,113 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::time::Duration;
+
+use daqmx::channels::*;
+use daqmx::error::ErrorKind;
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+fn continuous_ai(name: &str, config: &ReadConfig) -> Result<Task<AnalogInput>> {
+    let mut task: Task<AnalogInput> = Task::new(name)?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    task.configure_read(config)?;
+    Ok(task)
+}
+
+#[test]
+fn test_latest_clamps_offset() {
+    assert_eq!(ReadConfig::latest(100).offset, Some(-100));
+    assert_eq!(ReadConfig::latest(u32::MAX).offset, Some(-i32::MAX));
+}
+
+#[test]
+#[serial]
+fn test_read_config_round_trip() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ai("read_config_round_trip", &ReadConfig::default())?;
+    let defaults = task.read_config()?;
+    assert_eq!(
+        defaults.overwrite,
+        Some(OverwriteMode::DoNotOverwriteUnreadSamples)
+    );
+    assert_eq!(
+        defaults.relative_to,
+        Some(ReadRelativeTo::CurrentReadPosition)
+    );
+    assert_eq!(defaults.offset, Some(0));
+
+    let config = ReadConfig::latest(50).buffer_size(5000);
+    task.configure_read(&config)?;
+    assert_eq!(task.read_config()?, config);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_latest_samples_without_draining() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ai(
+        "read_config_latest",
+        &ReadConfig::latest(50).buffer_size(200),
+    )?;
+    task.start()?;
+
+    let mut buffer = [0.0; 50];
+    for _ in 0..3 {
+        // Long enough to fill the buffer several times over.
+        std::thread::sleep(Duration::from_millis(300));
+        let read = task.read(
+            Timeout::Seconds(1.0),
+            DataFillMode::GroupByChannel,
+            Some(50),
+            &mut buffer,
+        )?;
+        assert_eq!(read, 50);
+    }
+    // The latest samples are always there however many are acquired.
+    assert!(task.total_samples_acquired_per_channel()? >= 900);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_do_not_overwrite_fails_when_full() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ai(
+        "read_config_do_not_overwrite",
+        &ReadConfig::default().buffer_size(200),
+    )?;
+    task.start()?;
+    std::thread::sleep(Duration::from_millis(400));
+
+    let mut buffer = [0.0; 50];
+    let error = task
+        .read(
+            Timeout::Seconds(1.0),
+            DataFillMode::GroupByChannel,
+            Some(50),
+            &mut buffer,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        Some(ErrorKind::BufferOverwritten | ErrorKind::SamplesNoLongerAvailable)
+    ));
+    task.stop()?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::time::Duration;
+
+use daqmx::channels::*;
+use daqmx::tasks::output::{OutputTask, WriteOptions};
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+#[test]
+#[serial]
+fn test_input_status() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("status_input")?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    task.start()?;
+    std::thread::sleep(Duration::from_millis(200));
+
+    let mut buffer = [0.0; 100];
+    let read = task.read(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(100),
+        &mut buffer,
+    )?;
+    let available = task.available_samples_per_channel()?;
+    let total = task.total_samples_acquired_per_channel()?;
+
+    assert!(total >= 150, "{total} samples acquired");
+    // Everything acquired has been read or is waiting.
+    assert!(total - (read as u64 + available as u64) < 10);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_output_write_space() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogOutput> = Task::new("status_output")?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        100,
+    )?;
+    assert_eq!(task.write_space_available()?, 100);
+
+    task.write_with_options(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(60),
+        &[0.5; 60],
+        WriteOptions::default().auto_start(false),
+    )?;
+
+    assert_eq!(task.write_space_available()?, 40);
+    Ok(())
+}
@@ -0,0 +1,126 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::time::Duration;
+
+use daqmx::channels::*;
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+fn ai_task(name: &str, mode: SampleMode, samples: u64) -> Result<Task<AnalogInput>> {
+    let mut task: Task<AnalogInput> = Task::new(name)?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0:1")?.build()?)?;
+    task.configure_sample_clock_timing(None, 1000.0, ClockEdge::Rising, mode, samples)?;
+    Ok(task)
+}
+
+#[test]
+#[serial]
+fn test_stream_ends_when_stopped() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("stream_stop", SampleMode::ContinuousSamples, 1000)?;
+    task.start()?;
+
+    let mut stopper = task.clone();
+    let stop_thread = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(350));
+        stopper.stop()
+    });
+
+    let mut chunks = 0;
+    for chunk in task.stream(100)? {
+        // Two channels of 100 samples each.
+        assert_eq!(chunk?.len(), 200);
+        chunks += 1;
+    }
+
+    stop_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("Thread panicked"))??;
+    assert!((2..=4).contains(&chunks), "{chunks} chunks");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_stream_finite_acquisition() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("stream_finite", SampleMode::FiniteSamples, 250)?;
+    task.start()?;
+
+    let mut stream = task.stream(100)?.fill_mode(DataFillMode::GroupByScanNumber);
+    let mut lengths = Vec::new();
+    while let Some(chunk) = stream.next_chunk() {
+        lengths.push(chunk?.len());
+    }
+
+    assert_eq!(lengths, vec![200, 200, 100]);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_stream_digital_lines() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<DigitalInput> = Task::new("stream_di")?;
+    task.create_channel(
+        DigitalChannel::builder(
+            "di0",
+            "PCIe-6363_test/port0/line0, PCIe-6363_test/port0/line1",
+        )?
+        .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        20,
+    )?;
+    task.start()?;
+
+    let chunks = task.stream::<bool>(10)?.collect::<Result<Vec<_>, _>>()?;
+
+    // One channel of two lines.
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks.iter().all(|chunk| chunk.len() == 20));
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_stream_counter_counts() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<CounterInput> = Task::new("stream_ci")?;
+    task.create_channel(
+        CounterInputCountEdgesChannel::builder("ci0", "PCIe-6363_test/ctr0")?.build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        100.0,
+        ClockEdge::Rising,
+        SampleMode::FiniteSamples,
+        20,
+    )?;
+    task.start()?;
+
+    let counts: Vec<u32> = task
+        .stream::<u32>(5)?
+        .collect::<Result<Vec<_>, _>>()?
+        .concat();
+
+    assert_eq!(counts.len(), 20);
+    assert!(counts.windows(2).all(|pair| pair[0] <= pair[1]));
+    task.stop()?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+use daqmx::channels::*;
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+fn ai_task(name: &str, rate: f64, mode: SampleMode) -> Result<Task<AnalogInput>> {
+    let mut task: Task<AnalogInput> = Task::new(name)?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(None, rate, ClockEdge::Rising, mode, 100)?;
+    Ok(task)
+}
+
+#[test]
+#[serial]
+fn test_commit_and_restart() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("control", 1000.0, SampleMode::FiniteSamples)?;
+    task.verify()?;
+    task.reserve()?;
+    task.commit()?;
+
+    let mut buffer = [0.0; 100];
+    for _ in 0..3 {
+        task.start()?;
+        task.read(
+            Timeout::Seconds(1.0),
+            DataFillMode::GroupByChannel,
+            Some(100),
+            &mut buffer,
+        )?;
+        task.stop()?;
+    }
+
+    task.unreserve()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_verify_without_channels() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("empty")?;
+    assert!(task.verify().is_err());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_abort_from_thread() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    // 1000 samples at 10 Hz won't arrive before the abort.
+    let mut task = ai_task("abort", 10.0, SampleMode::ContinuousSamples)?;
+    task.start()?;
+
+    let mut reader = task.clone();
+    let read_thread = std::thread::spawn(move || {
+        let mut buffer = [0.0; 1000];
+        let started = Instant::now();
+        let result = reader.read(
+            Timeout::Seconds(10.0),
+            DataFillMode::GroupByChannel,
+            Some(1000),
+            &mut buffer,
+        );
+        (result.is_err(), started.elapsed())
+    });
+
+    std::thread::sleep(Duration::from_millis(200));
+    task.abort()?;
+
+    let (failed, elapsed) = read_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("Thread panicked"))?;
+    assert!(failed);
+    assert!(elapsed < Duration::from_secs(5));
+    Ok(())
+}
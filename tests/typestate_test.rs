@@ -0,0 +1,134 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+
+use daqmx::channels::*;
+use daqmx::tasks::output::{OutputTask, WriteOptions};
+use daqmx::tasks::typestate::TypedTask;
+use daqmx::tasks::{AnalogInput, AnalogOutput, InputTask, Task};
+use daqmx::types::ClockEdge::Rising;
+use daqmx::types::DataFillMode;
+use daqmx::types::SampleMode;
+use daqmx::types::Timeout;
+
+#[test]
+#[serial]
+fn test_typed_read_and_reconfigure() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = TypedTask::<AnalogInput>::new("typed ai")?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::FiniteSamples, 10)?;
+
+    let mut task = task.start()?;
+    let mut buffer = [0.0; 10];
+    let read = task.read(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(10),
+        &mut buffer,
+    )?;
+    assert_eq!(read, 10);
+
+    // Back in the configuring state the timing can change.
+    let mut task = task.stop()?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::FiniteSamples, 5)?;
+
+    let mut task = task.commit()?.start()?;
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    assert!(task.is_done()?);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_typed_preload_committed_output() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = TypedTask::<AnalogOutput>::new("typed ao")?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::FiniteSamples, 10)?;
+
+    let mut task = task.commit()?;
+    let buffer = [0.5; 10];
+    let written = task.write_with_options(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        Some(10),
+        &buffer,
+        WriteOptions::default().auto_start(false),
+    )?;
+    assert_eq!(written, 10);
+
+    let mut task = task.start()?;
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_typed_start_without_channels() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let task = TypedTask::<AnalogInput>::new("typed empty")?;
+    assert!(task.start().is_err());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_typed_failed_start_returns_task() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let task = TypedTask::<AnalogInput>::new("typed retry")?;
+    let Err(error) = task.start() else {
+        panic!("a task without channels started");
+    };
+    assert!(error.error.code().is_some());
+
+    // The task is handed back to be configured and started again.
+    let mut task = error.task;
+    assert_eq!(task.name()?, "typed retry");
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::FiniteSamples, 10)?;
+    let mut task = task.start()?;
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_typed_from_running_task_is_refused() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task: Task<AnalogInput> = Task::new("typed running")?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::ContinuousSamples, 1000)?;
+    task.start()?;
+
+    let Err(error) = TypedTask::try_from(task) else {
+        panic!("a running task converted to a configuring one");
+    };
+
+    // Once stopped, the task handed back converts.
+    let mut task = error.task;
+    task.stop()?;
+    let mut task = TypedTask::try_from(task)?;
+    task.configure_sample_clock_timing(None, 1000.0, Rising, SampleMode::FiniteSamples, 10)?;
+    let mut task = task.start()?;
+    task.wait_until_done(Timeout::Seconds(1.0))?;
+    task.stop()?;
+    Ok(())
+}
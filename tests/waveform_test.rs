@@ -0,0 +1,80 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::ffi::CString;
+use std::time::{Duration, SystemTime};
+
+use daqmx::channels::*;
+use daqmx::scales::{LinearScale, PreScaledUnits};
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+fn ai_task(name: &str) -> Result<Task<AnalogInput>> {
+    let mut task: Task<AnalogInput> = Task::new(name)?;
+    task.create_channel(VoltageChannel::builder("ai0", "PCIe-6363_test/ai0")?.build()?)?;
+    task.configure_sample_clock_timing(
+        None,
+        500.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    Ok(task)
+}
+
+#[test]
+#[serial]
+fn test_read_waveforms() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let _scale = LinearScale::new("WaveformScale", 2.0, 0.0, PreScaledUnits::Volts, "mA")?;
+    let mut task = ai_task("waveform_read")?;
+    task.create_channel(
+        VoltageChannel::builder("scaled", "PCIe-6363_test/ai1")?
+            .scale(VoltageScale::CustomScale(Some(CString::new(
+                "WaveformScale",
+            )?)))
+            .build()?,
+    )?;
+    task.start()?;
+
+    let waveforms = task.read_waveforms(Timeout::Seconds(1.0), 50)?;
+
+    assert_eq!(waveforms.len(), 2);
+    assert_eq!(waveforms[0].channel, "ai0");
+    assert_eq!(waveforms[0].units, "Volts");
+    assert_eq!(waveforms[1].channel, "scaled");
+    assert_eq!(waveforms[1].units, "mA");
+    for waveform in &waveforms {
+        assert_eq!(waveform.len(), 50);
+        assert_eq!(waveform.sample_rate(), task.sample_clock_rate()?);
+        // Timestamps are off by default.
+        assert_eq!(waveform.t0, None);
+    }
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_waveform_timestamps_follow_reads() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = ai_task("waveform_timestamps")?;
+    task.set_first_sample_timestamp_enabled(true)?;
+    assert!(task.first_sample_timestamp_enabled()?);
+    let before = SystemTime::now();
+    task.start()?;
+
+    let first = task.read_waveforms(Timeout::Seconds(1.0), 50)?.remove(0);
+    let second = task.read_waveforms(Timeout::Seconds(1.0), 50)?.remove(0);
+
+    let t0 = first.t0.expect("first sample timestamp");
+    assert!(t0.duration_since(before)? < Duration::from_millis(100));
+    // The second read starts where the first ended.
+    assert_eq!(second.t0, first.timestamp(50));
+    task.stop()?;
+    Ok(())
+}
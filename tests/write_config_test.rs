@@ -0,0 +1,133 @@
+mod common;
+use anyhow::Result;
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+use daqmx::channels::*;
+use daqmx::error::ErrorKind;
+use daqmx::tasks::output::{OutputTask, WriteConfig, WriteOptions};
+use daqmx::tasks::*;
+use daqmx::types::*;
+
+fn continuous_ao(name: &str, config: &WriteConfig) -> Result<Task<AnalogOutput>> {
+    let mut task: Task<AnalogOutput> = Task::new(name)?;
+    task.create_channel(
+        VoltageChannel::builder("ao0", "PCIe-6363_test/ao0")?
+            .max(1.0)
+            .build()?,
+    )?;
+    task.configure_sample_clock_timing(
+        None,
+        1000.0,
+        ClockEdge::Rising,
+        SampleMode::ContinuousSamples,
+        1000,
+    )?;
+    task.configure_write(config)?;
+    Ok(task)
+}
+
+fn preload(task: &mut Task<AnalogOutput>, samples: usize) -> Result<i32> {
+    Ok(task.write_with_options(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        None,
+        &vec![0.5; samples],
+        WriteOptions::default().auto_start(false),
+    )?)
+}
+
+#[test]
+#[serial]
+fn test_write_config_round_trip() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ao("write_config_round_trip", &WriteConfig::default())?;
+    let defaults = task.write_config()?;
+    assert_eq!(
+        defaults.regeneration,
+        Some(RegenerationMode::AllowRegeneration)
+    );
+    assert_eq!(
+        defaults.relative_to,
+        Some(WriteRelativeTo::CurrentWritePosition)
+    );
+
+    let config = WriteConfig::streaming().buffer_size(2000);
+    task.configure_write(&config)?;
+    assert_eq!(task.write_config()?, config);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_regenerated_waveform_keeps_running() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ao("write_config_regenerate", &WriteConfig::regenerating())?;
+    preload(&mut task, 100)?;
+    task.start()?;
+    // Generates the 100 samples several times over.
+    std::thread::sleep(Duration::from_millis(300));
+    assert!(!task.is_done()?);
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_streaming_write_waits_for_space() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ao(
+        "write_config_stream_wait",
+        &WriteConfig::streaming().buffer_size(200),
+    )?;
+    preload(&mut task, 200)?;
+    task.start()?;
+
+    let start = Instant::now();
+    let written = task.write(
+        Timeout::Seconds(1.0),
+        DataFillMode::GroupByChannel,
+        None,
+        &[0.5; 100],
+    )?;
+    assert_eq!(written, 100);
+    // The buffer was full, so the write waited for 100 samples to be generated.
+    assert!(start.elapsed() >= Duration::from_millis(80));
+    task.stop()?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_streaming_underflow_stops_generation() -> Result<()> {
+    if common::test_device_or_skip()?.is_none() {
+        return Ok(());
+    }
+    let mut task = continuous_ao(
+        "write_config_stream_underflow",
+        &WriteConfig::streaming().buffer_size(1000),
+    )?;
+    preload(&mut task, 100)?;
+    task.start()?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let error = task.is_done().unwrap_err();
+    assert_eq!(error.kind(), Some(ErrorKind::OutputUnderflow));
+    let error = task
+        .write(
+            Timeout::Seconds(1.0),
+            DataFillMode::GroupByChannel,
+            None,
+            &[0.5; 100],
+        )
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ErrorKind::OutputUnderflow));
+    task.stop()?;
+    Ok(())
+}